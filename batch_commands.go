@@ -12,18 +12,28 @@ import (
 // an executor that implements BatchExecutor.
 type BatchCommands struct {
 	executor BatchExecutor
+
+	// commands runs the single-key operations and builds the stores, with the
+	// configuration of the client when the executor is a Client.
+	commands *Commands
 }
 
 // NewBatchCommands creates a new BatchCommands instance.
 // The executor must implement BatchExecutor (e.g., ServerPool or Client).
 func NewBatchCommands(executor BatchExecutor) *BatchCommands {
+	commands := NewCommands(executor)
+	if client, ok := executor.(*Client); ok {
+		commands = client.Commands
+	}
 	return &BatchCommands{
 		executor: executor,
+		commands: commands,
 	}
 }
 
 // MultiGet retrieves multiple items in a single batch operation.
 // Returns items in the same order as the keys, with Found=false for missing items.
+// A key given more than once is requested each time (see MultiGetUnique).
 func (b *BatchCommands) MultiGet(ctx context.Context, keys []string) ([]Item, error) {
 	if len(keys) == 0 {
 		return nil, nil
@@ -69,6 +79,35 @@ func (b *BatchCommands) MultiGet(ctx context.Context, keys []string) ([]Item, er
 	return items, nil
 }
 
+// MultiGetUnique is MultiGet requesting each distinct key once: the item of a
+// key given more than once is returned at each of its positions, sharing the
+// same Value slice. With Config.BufferPool, return that slice only once.
+func (b *BatchCommands) MultiGetUnique(ctx context.Context, keys []string) ([]Item, error) {
+	positions := make(map[string]int, len(keys))
+	unique := make([]string, 0, len(keys))
+	for _, key := range keys {
+		if _, ok := positions[key]; !ok {
+			positions[key] = len(unique)
+			unique = append(unique, key)
+		}
+	}
+
+	if len(unique) == len(keys) {
+		return b.MultiGet(ctx, keys)
+	}
+
+	uniqueItems, err := b.MultiGet(ctx, unique)
+	if err != nil {
+		return nil, err
+	}
+
+	items := make([]Item, len(keys))
+	for i, key := range keys {
+		items[i] = uniqueItems[positions[key]]
+	}
+	return items, nil
+}
+
 // MultiSet stores multiple items in a single batch operation.
 // Returns error on first failure.
 func (b *BatchCommands) MultiSet(ctx context.Context, items []Item) error {
@@ -79,11 +118,7 @@ func (b *BatchCommands) MultiSet(ctx context.Context, items []Item) error {
 	// Build batch requests
 	reqs := make([]*meta.Request, len(items))
 	for i, item := range items {
-		req := meta.NewRequest(meta.CmdSet, item.Key, item.Value)
-		if exptime := item.TTL.Expiration(); exptime != 0 {
-			req.AddTTL(exptime)
-		}
-		reqs[i] = req
+		reqs[i] = b.commands.NewSet(item.Key).Value(item.Value).TTL(item.TTL).Request()
 	}
 
 	// Execute batch
@@ -109,6 +144,169 @@ func (b *BatchCommands) MultiSet(ctx context.Context, items []Item) error {
 	return nil
 }
 
+// MultiStore stores multiple items in a single batch operation with the given
+// storage mode, e.g. to warm a cache after a deploy. Item.Flags and Item.CAS
+// (when non-zero) are stored with each item.
+//
+// Unlike MultiSet, it reports the outcome of each item: the returned slice
+// holds, in the order of the items, nil for a stored item, ErrNotStored,
+// ErrCASMismatch or the protocol error of the item. The error is only non-nil
+// when the batch itself failed.
+func (b *BatchCommands) MultiStore(ctx context.Context, items []Item, mode SetMode) ([]error, error) {
+	if len(items) == 0 {
+		return nil, nil
+	}
+
+	// Build batch requests
+	reqs := make([]*meta.Request, len(items))
+	for i, item := range items {
+		reqs[i] = b.commands.NewSet(item.Key).Value(item.Value).TTL(item.TTL).Mode(mode).CAS(item.CAS).Flags(item.Flags).Request()
+	}
+
+	// Execute batch
+	responses, err := b.executor.ExecuteBatch(ctx, reqs)
+	if err != nil {
+		return nil, err
+	}
+	if len(responses) != len(items) {
+		return nil, fmt.Errorf("memcache: got %d responses for %d items", len(responses), len(items))
+	}
+
+	results := make([]error, len(items))
+	for i, resp := range responses {
+		results[i] = storeResult(reqs[i], resp)
+	}
+
+	return results, nil
+}
+
+// Swap stores item and returns the item it replaced, with Found=false if the
+// key was missing. The get and the set of the key are sent as one batch, to
+// the same server. Item.Flags is stored with the item, and a NoTTL item gets
+// the Config.DefaultTTL of the client.
+//
+// The swap is not atomic: another client may write the key between the get
+// and the set, and the value it wrote is then overwritten without being
+// returned. When that race matters, read the item with its CAS value and
+// store it with SetBuilder.CAS instead.
+//
+// An error of the store is returned first; an error of the get is returned
+// even though item was stored.
+func (b *BatchCommands) Swap(ctx context.Context, item Item) (Item, error) {
+	reqs := []*meta.Request{
+		meta.NewRequest(meta.CmdGet, item.Key, nil).AddReturnValue().AddReturnClientFlags(),
+		b.commands.NewSet(item.Key).Value(item.Value).TTL(item.TTL).Flags(item.Flags).Request(),
+	}
+
+	responses, err := b.executor.ExecuteBatch(ctx, reqs)
+	if err != nil {
+		return Item{}, err
+	}
+	if len(responses) != len(reqs) {
+		return Item{}, fmt.Errorf("memcache: got %d responses for %d requests", len(responses), len(reqs))
+	}
+
+	if err := storeResult(reqs[1], responses[1]); err != nil {
+		return Item{}, err
+	}
+
+	get := responses[0]
+	if get.HasError() {
+		return Item{}, get.Error
+	}
+	if get.IsMiss() {
+		return Item{Key: item.Key, Found: false}, nil
+	}
+	if !get.IsSuccess() {
+		return Item{}, fmt.Errorf("unexpected response status for key %s: %s", item.Key, get.Status)
+	}
+
+	previous := Item{Key: item.Key, Value: get.Data, Found: true}
+	previous.Flags, _ = get.ClientFlags()
+	return previous, nil
+}
+
+// MultiTouch updates the expiration of multiple items to ttl in a single
+// batch operation, e.g. to keep the sessions used by a request alive. It
+// reads no value: each key is touched with an mg request carrying the T flag.
+// Returns, in the order of the keys, whether each item was found (and
+// touched).
+func (b *BatchCommands) MultiTouch(ctx context.Context, keys []string, ttl TTL) ([]bool, error) {
+	if len(keys) == 0 {
+		return nil, nil
+	}
+
+	reqs := make([]*meta.Request, len(keys))
+	for i, key := range keys {
+		reqs[i] = meta.NewRequest(meta.CmdGet, key, nil).AddTTL(ttl.Expiration())
+	}
+
+	responses, err := b.executor.ExecuteBatch(ctx, reqs)
+	if err != nil {
+		return nil, err
+	}
+	if len(responses) != len(keys) {
+		return nil, fmt.Errorf("memcache: got %d responses for %d keys", len(responses), len(keys))
+	}
+
+	found := make([]bool, len(keys))
+	for i, resp := range responses {
+		if resp.HasError() {
+			return nil, resp.Error
+		}
+
+		switch resp.Status {
+		case meta.StatusHD:
+			found[i] = true
+		case meta.StatusEN:
+		default:
+			return nil, fmt.Errorf("touch failed for key %s with status: %s", keys[i], resp.Status)
+		}
+	}
+
+	return found, nil
+}
+
+// MultiExists reports, in the order of the keys, whether each item is
+// stored, in a single batch operation, e.g. to find which of many items are
+// cached. It is the cheapest membership check: each key is checked with a
+// bare mg request, so no value nor metadata is returned.
+func (b *BatchCommands) MultiExists(ctx context.Context, keys []string) ([]bool, error) {
+	if len(keys) == 0 {
+		return nil, nil
+	}
+
+	reqs := make([]*meta.Request, len(keys))
+	for i, key := range keys {
+		reqs[i] = meta.NewRequest(meta.CmdGet, key, nil)
+	}
+
+	responses, err := b.executor.ExecuteBatch(ctx, reqs)
+	if err != nil {
+		return nil, err
+	}
+	if len(responses) != len(keys) {
+		return nil, fmt.Errorf("memcache: got %d responses for %d keys", len(responses), len(keys))
+	}
+
+	found := make([]bool, len(keys))
+	for i, resp := range responses {
+		if resp.HasError() {
+			return nil, resp.Error
+		}
+
+		switch resp.Status {
+		case meta.StatusHD:
+			found[i] = true
+		case meta.StatusEN:
+		default:
+			return nil, fmt.Errorf("exists check failed for key %s with status: %s", keys[i], resp.Status)
+		}
+	}
+
+	return found, nil
+}
+
 // MultiDelete removes multiple items in a single batch operation.
 // Returns error on first failure.
 func (b *BatchCommands) MultiDelete(ctx context.Context, keys []string) error {
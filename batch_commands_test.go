@@ -51,6 +51,32 @@ func TestBatchCommands_MultiGet(t *testing.T) {
 	})
 }
 
+func TestBatchCommands_MultiGet_DuplicateKeys(t *testing.T) {
+	t.Run("each position gets its response", func(t *testing.T) {
+		bc, mock := newBatchTestClient(t, "VA 2\r\nva\r\n", "VA 2\r\nvb\r\n", "VA 2\r\nva\r\n", "MN\r\n")
+
+		items, err := bc.MultiGet(context.Background(), []string{"a", "b", "a"})
+		require.NoError(t, err)
+		require.Len(t, items, 3)
+		assert.Equal(t, "va", string(items[0].Value))
+		assert.Equal(t, "vb", string(items[1].Value))
+		assert.Equal(t, "va", string(items[2].Value))
+		assert.Equal(t, "mg a v\r\nmg b v\r\nmg a v\r\nmn\r\n", mock.GetWrittenRequest())
+	})
+
+	t.Run("unique requests each key once", func(t *testing.T) {
+		bc, mock := newBatchTestClient(t, "VA 2\r\nva\r\n", "EN\r\n", "MN\r\n")
+
+		items, err := bc.MultiGetUnique(context.Background(), []string{"a", "b", "a"})
+		require.NoError(t, err)
+		require.Len(t, items, 3)
+		assert.Equal(t, Item{Key: "a", Value: []byte("va"), Found: true}, items[0])
+		assert.Equal(t, Item{Key: "b", Found: false}, items[1])
+		assert.Equal(t, items[0], items[2])
+		assert.Equal(t, "mg a v\r\nmg b v\r\nmn\r\n", mock.GetWrittenRequest())
+	})
+}
+
 func TestBatchCommands_MultiSet(t *testing.T) {
 	t.Run("success with TTL", func(t *testing.T) {
 		bc, mock := newBatchTestClient(t, "HD\r\n", "HD\r\n", "MN\r\n")
@@ -81,6 +107,140 @@ func TestBatchCommands_MultiSet(t *testing.T) {
 	})
 }
 
+func TestBatchCommands_MultiStore(t *testing.T) {
+	t.Run("per-item outcomes", func(t *testing.T) {
+		bc, mock := newBatchTestClient(t, "HD\r\n", "NS\r\n", "EX\r\n", "SERVER_ERROR out of memory\r\n", "MN\r\n")
+
+		items := []Item{
+			{Key: "k1", Value: []byte("v1"), TTL: ExpiresIn(time.Minute), Flags: 3},
+			{Key: "k2", Value: []byte("v2")},
+			{Key: "k3", Value: []byte("v3"), CAS: 42},
+			{Key: "k4", Value: []byte("v4")},
+		}
+		results, err := bc.MultiStore(context.Background(), items, SetModeAdd)
+		require.NoError(t, err)
+		require.Len(t, results, 4)
+
+		assert.NoError(t, results[0])
+		assert.ErrorIs(t, results[1], ErrNotStored)
+		assert.ErrorIs(t, results[2], ErrCASMismatch)
+		var serverErr *meta.ServerError
+		assert.ErrorAs(t, results[3], &serverErr)
+
+		assert.Equal(t, "ms k1 2 ME T60 F3\r\nv1\r\n"+
+			"ms k2 2 ME\r\nv2\r\n"+
+			"ms k3 2 ME C42\r\nv3\r\n"+
+			"ms k4 2 ME\r\nv4\r\n"+
+			"mn\r\n", mock.GetWrittenRequest())
+	})
+
+	t.Run("values with protocol bytes keep their framing", func(t *testing.T) {
+		bc, mock := newBatchTestClient(t, "HD\r\n", "HD\r\n", "MN\r\n")
+
+		items := []Item{
+			{Key: "k1", Value: []byte("a\r\nms k9 1\r\nb")},
+			{Key: "k2", Value: []byte{}},
+		}
+		results, err := bc.MultiStore(context.Background(), items, SetModeSet)
+		require.NoError(t, err)
+		assert.Equal(t, []error{nil, nil}, results)
+		assert.Equal(t, "ms k1 13\r\na\r\nms k9 1\r\nb\r\nms k2 0\r\n\r\nmn\r\n", mock.GetWrittenRequest())
+	})
+
+	t.Run("empty items", func(t *testing.T) {
+		bc, _ := newBatchTestClient(t)
+		results, err := bc.MultiStore(context.Background(), nil, SetModeSet)
+		require.NoError(t, err)
+		assert.Nil(t, results)
+	})
+}
+
+func TestBatchCommands_Swap(t *testing.T) {
+	t.Run("returns the replaced item", func(t *testing.T) {
+		bc, mock := newBatchTestClient(t, "VA 3 f7\r\nold\r\n", "HD\r\n", "MN\r\n")
+
+		previous, err := bc.Swap(context.Background(), Item{Key: "k", Value: []byte("new"), TTL: ExpiresIn(time.Minute)})
+		require.NoError(t, err)
+		assert.Equal(t, Item{Key: "k", Value: []byte("old"), Flags: 7, Found: true}, previous)
+		assert.Equal(t, "mg k v f\r\nms k 3 T60\r\nnew\r\nmn\r\n", mock.GetWrittenRequest())
+	})
+
+	t.Run("missing key", func(t *testing.T) {
+		bc, _ := newBatchTestClient(t, "EN\r\n", "HD\r\n", "MN\r\n")
+
+		previous, err := bc.Swap(context.Background(), Item{Key: "k", Value: []byte("new")})
+		require.NoError(t, err)
+		assert.False(t, previous.Found)
+	})
+
+	t.Run("default TTL of the client", func(t *testing.T) {
+		mock := testutils.NewConnectionMock("EN\r\n", "HD\r\n", "MN\r\n")
+		bc := NewBatchCommands(newTestClientWithConfig(t, mock, Config{DefaultTTL: ExpiresIn(time.Hour)}))
+
+		_, err := bc.Swap(context.Background(), Item{Key: "k", Value: []byte("new")})
+		require.NoError(t, err)
+		assert.Equal(t, "mg k v f\r\nms k 3 T3600\r\nnew\r\nmn\r\n", mock.GetWrittenRequest())
+	})
+
+	t.Run("store failure", func(t *testing.T) {
+		bc, _ := newBatchTestClient(t, "VA 3\r\nold\r\n", "SERVER_ERROR out of memory\r\n", "MN\r\n")
+
+		_, err := bc.Swap(context.Background(), Item{Key: "k", Value: []byte("new")})
+		var serverErr *meta.ServerError
+		require.ErrorAs(t, err, &serverErr)
+	})
+}
+
+func TestBatchCommands_MultiTouch(t *testing.T) {
+	t.Run("hits and misses in order", func(t *testing.T) {
+		bc, mock := newBatchTestClient(t, "HD\r\n", "EN\r\n", "HD\r\n", "MN\r\n")
+
+		found, err := bc.MultiTouch(context.Background(), []string{"k1", "k2", "k3"}, ExpiresIn(30*time.Minute))
+		require.NoError(t, err)
+		assert.Equal(t, []bool{true, false, true}, found)
+		assert.Equal(t, "mg k1 T1800\r\nmg k2 T1800\r\nmg k3 T1800\r\nmn\r\n", mock.GetWrittenRequest())
+	})
+
+	t.Run("protocol error", func(t *testing.T) {
+		bc, _ := newBatchTestClient(t, "HD\r\n", "CLIENT_ERROR bad command line format\r\n", "MN\r\n")
+
+		_, err := bc.MultiTouch(context.Background(), []string{"k1", "k2"}, ExpiresIn(time.Minute))
+		require.Error(t, err)
+	})
+
+	t.Run("empty keys", func(t *testing.T) {
+		bc, _ := newBatchTestClient(t)
+		found, err := bc.MultiTouch(context.Background(), nil, ExpiresIn(time.Minute))
+		require.NoError(t, err)
+		assert.Nil(t, found)
+	})
+}
+
+func TestBatchCommands_MultiExists(t *testing.T) {
+	t.Run("hits and misses in order", func(t *testing.T) {
+		bc, mock := newBatchTestClient(t, "EN\r\n", "HD\r\n", "EN\r\n", "MN\r\n")
+
+		found, err := bc.MultiExists(context.Background(), []string{"k1", "k2", "k3"})
+		require.NoError(t, err)
+		assert.Equal(t, []bool{false, true, false}, found)
+		assert.Equal(t, "mg k1\r\nmg k2\r\nmg k3\r\nmn\r\n", mock.GetWrittenRequest())
+	})
+
+	t.Run("unexpected status fails with key in error", func(t *testing.T) {
+		bc, _ := newBatchTestClient(t, "HD\r\n", "NS\r\n", "MN\r\n")
+
+		_, err := bc.MultiExists(context.Background(), []string{"k1", "k2"})
+		require.ErrorContains(t, err, "k2")
+	})
+
+	t.Run("empty keys", func(t *testing.T) {
+		bc, _ := newBatchTestClient(t)
+		found, err := bc.MultiExists(context.Background(), nil)
+		require.NoError(t, err)
+		assert.Nil(t, found)
+	})
+}
+
 func TestBatchCommands_MultiDelete(t *testing.T) {
 	t.Run("missing keys are not errors", func(t *testing.T) {
 		bc, mock := newBatchTestClient(t, "HD\r\n", "NF\r\n", "MN\r\n")
@@ -0,0 +1,727 @@
+package memcache
+
+import (
+	"context"
+	"fmt"
+	"strconv"
+	"time"
+
+	"github.com/pior/memcache/meta"
+)
+
+// SetMode selects how a set operation stores its value.
+type SetMode string
+
+// Storage modes, mapping to the meta protocol M flag.
+const (
+	SetModeSet     SetMode = meta.ModeSet     // store unconditionally (default)
+	SetModeAdd     SetMode = meta.ModeAdd     // store only if the key doesn't exist
+	SetModeReplace SetMode = meta.ModeReplace // store only if the key exists
+	SetModeAppend  SetMode = meta.ModeAppend  // append to the existing value
+	SetModePrepend SetMode = meta.ModePrepend // prepend to the existing value
+)
+
+// SetBuilder composes a set operation fluently, for stores that need more
+// than Commands.Set offers:
+//
+//	err := client.NewSet("key").
+//		Value(v).
+//		TTL(memcache.ExpiresIn(5 * time.Minute)).
+//		Mode(memcache.SetModeReplace).
+//		CAS(item.CAS).
+//		Do(ctx)
+//
+// A builder describes a single operation and is not safe for concurrent use.
+type SetBuilder struct {
+	commands  *Commands
+	key       string
+	value     []byte
+	ttl       TTL
+	mode      SetMode
+	cas       uint64
+	flags     uint32
+	vivify    bool
+	vivifyTTL TTL
+	invalid   bool
+	quiet     bool
+}
+
+// NewSet starts building a set operation for key.
+func (c *Commands) NewSet(key string) *SetBuilder {
+	return &SetBuilder{commands: c, key: key}
+}
+
+// Value sets the value to store.
+func (b *SetBuilder) Value(value []byte) *SetBuilder { b.value = value; return b }
+
+// TTL sets the item expiration. The default is NoTTL, standing for
+// Config.DefaultTTL when the client has one.
+func (b *SetBuilder) TTL(ttl TTL) *SetBuilder { b.ttl = ttl; return b }
+
+// Mode sets the storage mode. The default is SetModeSet.
+//
+// To store a value recomputed after winning a vivified get (meta N flag), keep
+// SetModeSet: the vivify stub is a regular item, so SetModeAdd fails with
+// ErrNotStored and concurrent callers recompute again until the stub expires.
+func (b *SetBuilder) Mode(mode SetMode) *SetBuilder { b.mode = mode; return b }
+
+// CAS makes the store conditional on the item's CAS value, as read with
+// GetBuilder.ReturnCAS: Do returns ErrCASMismatch if the item was modified
+// since. Zero means no CAS check (memcached never issues a zero CAS).
+func (b *SetBuilder) CAS(cas uint64) *SetBuilder { b.cas = cas; return b }
+
+// Flags sets the client flags stored with the item.
+func (b *SetBuilder) Flags(flags uint32) *SetBuilder { b.flags = flags; return b }
+
+// Vivify creates the item with the given TTL when the key is missing, instead
+// of failing with ErrNotStored. Only applies to SetModeAppend and
+// SetModePrepend.
+func (b *SetBuilder) Vivify(ttl TTL) *SetBuilder { b.vivify = true; b.vivifyTTL = ttl; return b }
+
+// Invalidate relaxes the CAS check for out-of-order writes: a value whose CAS
+// is older than the item's is still stored, but marked stale so readers see
+// it flagged as such and revalidate. A CAS newer than the item's still fails,
+// with ErrStaleWrite (which wraps ErrCASMismatch). The server reports a stale
+// store like any other store, so Do returns nil for it. Requires CAS.
+func (b *SetBuilder) Invalidate() *SetBuilder { b.invalid = true; return b }
+
+// Quiet asks the server not to send the nominal (stored) response, saving a
+// few bytes on the wire. Failures are still reported.
+func (b *SetBuilder) Quiet() *SetBuilder { b.quiet = true; return b }
+
+// Request returns the meta protocol request the builder sends.
+func (b *SetBuilder) Request() *meta.Request {
+	req := meta.NewRequest(meta.CmdSet, b.key, b.value)
+	if b.mode != "" && b.mode != SetModeSet {
+		req.AddMode(string(b.mode))
+	}
+	if b.vivify {
+		req.AddVivify(b.vivifyTTL.Expiration())
+	}
+	ttl := b.ttl
+	if b.commands != nil {
+		ttl = b.commands.storeTTL(ttl)
+	}
+	if exptime := ttl.Expiration(); exptime != 0 {
+		req.AddTTL(exptime)
+	}
+	if b.flags != 0 {
+		req.AddClientFlags(b.flags)
+	}
+	if b.cas != 0 {
+		req.AddCAS(b.cas)
+	}
+	if b.invalid {
+		req.AddInvalidate()
+	}
+	if b.quiet {
+		req.AddQuiet()
+	}
+	return req
+}
+
+// Do executes the set operation.
+// It returns ErrNotStored when the mode condition is not met, and
+// ErrCASMismatch when the CAS check fails.
+func (b *SetBuilder) Do(ctx context.Context) error {
+	req := b.Request()
+	resp, err := b.commands.executor.Execute(ctx, req)
+	if err != nil {
+		return err
+	}
+	return b.commands.storeErr(req, storeResult(req, resp))
+}
+
+// storeResult maps the response of the set request req to its error.
+func storeResult(req *meta.Request, resp *meta.Response) error {
+	if resp.HasError() {
+		return resp.Error
+	}
+
+	switch resp.Status {
+	case meta.StatusHD:
+		return nil
+	case meta.StatusNS, meta.StatusNF:
+		return ErrNotStored
+	default:
+		return statusErr(req, "set", resp.Status)
+	}
+}
+
+// GetBuilder composes a get operation fluently, for reads that need more
+// than Commands.Get offers:
+//
+//	item, err := client.NewGet("key").ReturnCAS().ReturnFlags().Do(ctx)
+//
+// A builder describes a single operation and is not safe for concurrent use.
+type GetBuilder struct {
+	commands    *Commands
+	key         string
+	returnCAS   bool
+	returnFlags bool
+	touch       bool
+	ttl         TTL
+	noLRUBump   bool
+	required    bool
+	minTTL      time.Duration
+	vivify      bool
+	vivifyTTL   TTL
+	recache     time.Duration
+}
+
+// NewGet starts building a get operation for key.
+func (c *Commands) NewGet(key string) *GetBuilder {
+	return &GetBuilder{commands: c, key: key}
+}
+
+// ReturnCAS populates Item.CAS, for a later conditional store.
+func (b *GetBuilder) ReturnCAS() *GetBuilder { b.returnCAS = true; return b }
+
+// ReturnFlags populates Item.Flags.
+func (b *GetBuilder) ReturnFlags() *GetBuilder { b.returnFlags = true; return b }
+
+// Touch updates the item expiration to ttl as part of the read.
+func (b *GetBuilder) Touch(ttl TTL) *GetBuilder { b.touch = true; b.ttl = ttl; return b }
+
+// NoLRUBump reads the item without bumping it in the server's LRU.
+func (b *GetBuilder) NoLRUBump() *GetBuilder { b.noLRUBump = true; return b }
+
+// Required makes a miss an error: Do returns ErrCacheMiss instead of an Item
+// with Found=false, for keys that are expected to exist.
+func (b *GetBuilder) Required() *GetBuilder { b.required = true; return b }
+
+// MinTTL treats an item expiring in less than d as a miss, so the caller
+// refreshes it before it expires: a client-side early expiration that doesn't
+// depend on the server's recache flag. Items that never expire always match.
+func (b *GetBuilder) MinTTL(d time.Duration) *GetBuilder { b.minTTL = d; return b }
+
+// Vivify creates an empty placeholder item with the given TTL on a miss, and
+// grants this client the right to compute the value: other clients then see
+// a miss that another client already won. Read the outcome with DoRecache.
+func (b *GetBuilder) Vivify(ttl TTL) *GetBuilder { b.vivify = true; b.vivifyTTL = ttl; return b }
+
+// Recache grants this client the right to refresh a hit expiring in less than
+// threshold, before it expires; other clients keep reading the value. Read
+// the outcome with DoRecache.
+func (b *GetBuilder) Recache(threshold time.Duration) *GetBuilder { b.recache = threshold; return b }
+
+// Request returns the meta protocol request the builder sends.
+func (b *GetBuilder) Request() *meta.Request {
+	req := meta.NewRequest(meta.CmdGet, b.key, nil).AddReturnValue()
+	if b.returnCAS {
+		req.AddReturnCAS()
+	}
+	if b.returnFlags {
+		req.AddReturnClientFlags()
+	}
+	if b.touch {
+		req.AddTTL(b.ttl.Expiration())
+	}
+	if b.noLRUBump {
+		req.AddNoLRUBump()
+	}
+	if b.minTTL > 0 {
+		req.AddReturnTTL()
+	}
+	if b.vivify {
+		req.AddVivify(b.vivifyTTL.Expiration())
+	}
+	if b.recache > 0 {
+		// Round up, like TTLs: a sub-second threshold must not become R0.
+		req.AddRecache(int((b.recache + time.Second - 1) / time.Second))
+	}
+	return req
+}
+
+// Do executes the get operation. A miss is not an error: it returns an Item
+// with Found=false, unless the key is Required.
+func (b *GetBuilder) Do(ctx context.Context) (Item, error) {
+	req := b.Request()
+	resp, err := b.commands.executor.Execute(ctx, req)
+	if err != nil {
+		return Item{}, err
+	}
+	return b.result(req, resp)
+}
+
+// DoStream executes the get like Do, except that a value larger than
+// threshold bytes (DefaultStreamThreshold if threshold <= 0) is not read into
+// memory: the item then has a nil Value, and the returned stream reads the
+// value from the connection. This avoids a large allocation for occasional
+// oversized items. Smaller values are buffered as by Do, with a nil stream.
+//
+// A stream holds its connection out of the pool until it is closed, which the
+// caller must do once done with it; the connection is discarded if the value
+// wasn't read entirely. ctx bounds the reads of the stream: it must not be
+// canceled before then.
+//
+// With an executor that doesn't implement StreamExecutor, values are always
+// buffered.
+func (b *GetBuilder) DoStream(ctx context.Context, threshold int) (Item, *ValueStream, error) {
+	executor, ok := b.commands.executor.(StreamExecutor)
+	if !ok {
+		item, err := b.Do(ctx)
+		return item, nil, err
+	}
+	if threshold <= 0 {
+		threshold = DefaultStreamThreshold
+	}
+
+	req := b.Request()
+	resp, stream, err := executor.ExecuteStream(ctx, req, threshold)
+	if err != nil {
+		return Item{}, nil, err
+	}
+
+	item, err := b.result(req, resp)
+	if stream != nil && (err != nil || !item.Found) {
+		// Too short a remaining TTL (MinTTL): the value is not returned.
+		_ = stream.Close()
+		stream = nil
+	}
+	return item, stream, err
+}
+
+// result maps the response of the get req to the item read.
+func (b *GetBuilder) result(req *meta.Request, resp *meta.Response) (Item, error) {
+	if resp.IsMiss() {
+		return b.miss(req)
+	}
+
+	if resp.HasError() {
+		return Item{}, b.commands.keyErr(req, resp.Error)
+	}
+
+	if !resp.IsSuccess() {
+		return Item{}, b.commands.keyErr(req, fmt.Errorf("unexpected response status: %s", resp.Status))
+	}
+
+	// The t flag reports -1 for an item that never expires.
+	if remaining, ok := resp.TTL(); ok && remaining >= 0 && time.Duration(remaining)*time.Second < b.minTTL {
+		return b.miss(req)
+	}
+
+	return b.item(resp), nil
+}
+
+// item returns the item read by the hit resp.
+func (b *GetBuilder) item(resp *meta.Response) Item {
+	item := Item{
+		Key:   b.key,
+		Value: resp.Data,
+		Found: true,
+	}
+	item.CAS, _ = resp.CAS()
+	item.Flags, _ = resp.ClientFlags()
+	return item
+}
+
+// miss reports a miss of req.
+func (b *GetBuilder) miss(req *meta.Request) (Item, error) {
+	if b.required {
+		return Item{Key: b.key, Found: false}, b.commands.keyErr(req, ErrCacheMiss)
+	}
+	return Item{Key: b.key, Found: false}, nil
+}
+
+// RecacheState is the state of an item read by GetBuilder.DoRecache.
+type RecacheState int
+
+const (
+	// RecacheMiss means there is no value to use: the item doesn't exist, or
+	// is the empty placeholder created by GetBuilder.Vivify.
+	RecacheMiss RecacheState = iota
+	// RecacheValue means the item holds a valid value.
+	RecacheValue
+	// RecacheStale means the item holds a value that was marked stale (see
+	// DeleteBuilder.Invalidate): usable while it is being refreshed.
+	RecacheStale
+)
+
+func (s RecacheState) String() string {
+	switch s {
+	case RecacheMiss:
+		return "miss"
+	case RecacheValue:
+		return "value"
+	case RecacheStale:
+		return "stale"
+	default:
+		return fmt.Sprintf("RecacheState(%d)", int(s))
+	}
+}
+
+// RecacheResult is the outcome of GetBuilder.DoRecache, the memcached
+// protection against dogpiling (many clients recomputing the same value at
+// once). A single switch covers the protocol:
+//
+//	switch res.State {
+//	case memcache.RecacheValue, memcache.RecacheStale:
+//		// Use res.Item.Value. If res.Won, refresh the item in the background.
+//	case memcache.RecacheMiss:
+//		// If res.Won, compute and store the value. Otherwise another client
+//		// is computing it: retry shortly, or compute it without storing it.
+//	}
+type RecacheResult struct {
+	State RecacheState
+
+	// Item is the item read; its Value is only meaningful for RecacheValue
+	// and RecacheStale.
+	Item Item
+
+	// Won means this client won the right to compute and store the value
+	// (W flag): the server grants it to a single client, the others see Lost.
+	Won bool
+
+	// Lost means another client already won the right to compute and store
+	// the value (Z flag).
+	Lost bool
+
+	// Vivified means the miss left an empty placeholder item in the server
+	// (see GetBuilder.Vivify), created by this request if Won.
+	Vivified bool
+}
+
+// DoRecache executes the get operation like Do, classifying the item with
+// the recache flags the server returns: see Vivify, Recache and
+// DeleteBuilder.Invalidate. A placeholder item can't be told apart from an
+// empty value: with Vivify, an empty item that another client won (or is to
+// win) is a RecacheMiss. Required and MinTTL only apply to Do.
+func (b *GetBuilder) DoRecache(ctx context.Context) (RecacheResult, error) {
+	req := b.Request()
+	resp, err := b.commands.executor.Execute(ctx, req)
+	if err != nil {
+		return RecacheResult{}, err
+	}
+
+	if resp.HasError() {
+		return RecacheResult{}, b.commands.keyErr(req, resp.Error)
+	}
+
+	result := RecacheResult{Won: resp.Win(), Lost: resp.AlreadyWon()}
+
+	switch {
+	case resp.Status == meta.StatusEN:
+		result.State = RecacheMiss
+		result.Item = Item{Key: b.key}
+		result.Vivified = b.vivify && (result.Won || result.Lost)
+	case !resp.IsSuccess():
+		return RecacheResult{}, b.commands.keyErr(req, fmt.Errorf("unexpected response status: %s", resp.Status))
+	case resp.Stale():
+		result.State = RecacheStale
+		result.Item = b.item(resp)
+	case b.vivify && len(resp.Data) == 0 && (result.Won || result.Lost):
+		result.State = RecacheMiss
+		result.Item = Item{Key: b.key}
+		result.Vivified = true
+	default:
+		result.State = RecacheValue
+		result.Item = b.item(resp)
+	}
+
+	return result, nil
+}
+
+// DeleteResult is the outcome of a delete operation.
+type DeleteResult int
+
+const (
+	// DeleteResultNotFound means the key didn't exist.
+	DeleteResultNotFound DeleteResult = iota
+	// DeleteResultDeleted means the item was removed.
+	DeleteResultDeleted
+	// DeleteResultInvalidated means the item was marked stale (see
+	// DeleteBuilder.Invalidate).
+	DeleteResultInvalidated
+)
+
+func (r DeleteResult) String() string {
+	switch r {
+	case DeleteResultNotFound:
+		return "not found"
+	case DeleteResultDeleted:
+		return "deleted"
+	case DeleteResultInvalidated:
+		return "invalidated"
+	default:
+		return fmt.Sprintf("DeleteResult(%d)", int(r))
+	}
+}
+
+// DeleteBuilder composes a delete operation fluently, for deletes that need
+// more than Commands.Delete offers:
+//
+//	result, err := client.NewDelete("key").Invalidate(memcache.ExpiresIn(30 * time.Second)).Do(ctx)
+//
+// A builder describes a single operation and is not safe for concurrent use.
+type DeleteBuilder struct {
+	commands   *Commands
+	key        string
+	cas        uint64
+	invalidate bool
+	ttl        TTL
+	required   bool
+}
+
+// NewDelete starts building a delete operation for key.
+func (c *Commands) NewDelete(key string) *DeleteBuilder {
+	return &DeleteBuilder{commands: c, key: key}
+}
+
+// CAS makes the delete conditional on the item's CAS value: Do returns
+// ErrCASMismatch if the item was modified since. Zero means no CAS check.
+//
+// The server doesn't return the CAS of a deleted item: to know it, read it
+// with GetBuilder.ReturnCAS and pass it here, so the delete only succeeds for
+// that version.
+func (b *DeleteBuilder) CAS(cas uint64) *DeleteBuilder { b.cas = cas; return b }
+
+// Invalidate marks the item stale instead of removing it, for
+// stale-while-revalidate: readers keep getting the stale value, flagged as
+// such, while one of them recomputes it. The item then expires after ttl.
+func (b *DeleteBuilder) Invalidate(ttl TTL) *DeleteBuilder {
+	b.invalidate = true
+	b.ttl = ttl
+	return b
+}
+
+// Required makes a missing key an error: Do returns ErrCacheMiss instead of
+// DeleteResultNotFound, for call sites where the key is expected to exist.
+func (b *DeleteBuilder) Required() *DeleteBuilder { b.required = true; return b }
+
+// Request returns the meta protocol request the builder sends.
+func (b *DeleteBuilder) Request() *meta.Request {
+	req := meta.NewRequest(meta.CmdDelete, b.key, nil)
+	if b.cas != 0 {
+		req.AddCAS(b.cas)
+	}
+	if b.invalidate {
+		req.AddInvalidate()
+		if exptime := b.ttl.Expiration(); exptime != 0 {
+			req.AddTTL(exptime)
+		}
+	}
+	return req
+}
+
+// Do executes the delete operation. A missing key is not an error: it
+// returns DeleteResultNotFound, unless the key is Required. It returns
+// ErrCASMismatch when the CAS check fails.
+func (b *DeleteBuilder) Do(ctx context.Context) (DeleteResult, error) {
+	req := b.Request()
+	resp, err := b.commands.executor.Execute(ctx, req)
+	if err != nil {
+		return DeleteResultNotFound, err
+	}
+
+	if resp.HasError() {
+		return DeleteResultNotFound, b.commands.keyErr(req, resp.Error)
+	}
+
+	switch resp.Status {
+	case meta.StatusHD:
+		if b.invalidate {
+			return DeleteResultInvalidated, nil
+		}
+		return DeleteResultDeleted, nil
+	case meta.StatusNF:
+		if b.required {
+			return DeleteResultNotFound, b.commands.keyErr(req, ErrCacheMiss)
+		}
+		return DeleteResultNotFound, nil
+	default:
+		return DeleteResultNotFound, b.commands.keyErr(req, statusErr(req, "delete", resp.Status))
+	}
+}
+
+// IncrementBuilder composes an arithmetic operation fluently, for counters
+// that need more than Commands.Increment offers, e.g. distinct expirations on
+// creation and on update:
+//
+//	// Created for a 1h window, not extended by later increments.
+//	n, err := client.NewIncrement("hits").Create(memcache.ExpiresIn(time.Hour)).Do(ctx)
+//
+// A builder describes a single operation and is not safe for concurrent use.
+type IncrementBuilder struct {
+	commands  *Commands
+	key       string
+	delta     int64
+	create    bool
+	createTTL TTL
+	refresh   bool
+	ttl       TTL
+}
+
+// NewIncrement starts building an arithmetic operation for key, adding 1 to
+// the counter unless Delta says otherwise.
+func (c *Commands) NewIncrement(key string) *IncrementBuilder {
+	return &IncrementBuilder{commands: c, key: key, delta: 1}
+}
+
+// Delta sets the amount added to the counter; a negative delta decrements
+// it. Memcached counters are unsigned: a decrement stops at 0.
+func (b *IncrementBuilder) Delta(delta int64) *IncrementBuilder { b.delta = delta; return b }
+
+// Create creates a missing counter (N flag), expiring after ttl, with the
+// delta as initial value (0 for a decrement). The ttl only applies on
+// creation: incrementing an existing counter leaves its expiration alone.
+// Without Create, Do returns ErrCacheMiss for a missing counter.
+func (b *IncrementBuilder) Create(ttl TTL) *IncrementBuilder {
+	b.create = true
+	b.createTTL = ttl
+	return b
+}
+
+// TTL updates the counter expiration to ttl on every operation (T flag),
+// e.g. to keep a counter alive while it is in use. On creation, it overrides
+// the Create ttl.
+func (b *IncrementBuilder) TTL(ttl TTL) *IncrementBuilder { b.refresh = true; b.ttl = ttl; return b }
+
+// Request returns the meta protocol request the builder sends.
+func (b *IncrementBuilder) Request() *meta.Request {
+	req := meta.NewRequest(meta.CmdArithmetic, b.key, nil).AddReturnValue()
+	if b.delta >= 0 {
+		req.AddDelta(uint64(b.delta))
+	} else {
+		req.AddDelta(uint64(-b.delta)).AddModeDecrement()
+	}
+	if b.create {
+		req.AddInitialValue(uint64(max(b.delta, 0)))
+		req.AddVivify(b.createTTL.Expiration())
+	}
+	if b.refresh {
+		req.AddTTL(b.ttl.Expiration())
+	}
+	return req
+}
+
+// Do executes the arithmetic operation and returns the counter value.
+func (b *IncrementBuilder) Do(ctx context.Context) (int64, error) {
+	value, _, err := b.do(ctx, b.Request())
+	return value, err
+}
+
+// maxCreateAttempts bounds the stores DoWithCreated makes to create a counter.
+const maxCreateAttempts = 2
+
+// DoWithCreated executes the arithmetic operation like Do, also reporting
+// whether it created the counter, e.g. to start a rate-limiting window on its
+// first hit. A created counter holds the initial value (the delta, 0 for a
+// decrement); later operations return the previous value plus the delta.
+//
+// The server doesn't report creations: on a missing counter, the counter is
+// created by a separate store in add mode (ME flag) instead of the N flag,
+// one more round trip on creation only. When a concurrent operation creates
+// it first, the arithmetic operation is sent again, up to maxCreateAttempts
+// times: a counter that keeps vanishing in between (e.g. deleted or evicted
+// right away) is then reported as ErrCacheMiss. Without Create, it never
+// reports a creation.
+//
+// The store gets the expiration the N flag would have: the ttl given to TTL
+// if any, else the one given to Create, as is. Like a counter created by Do,
+// the counter gets neither Config.DefaultTTL nor Config.TTLJitter: created
+// with NoTTL, it never expires.
+func (b *IncrementBuilder) DoWithCreated(ctx context.Context) (int64, bool, error) {
+	withoutCreate := *b
+	withoutCreate.create = false
+	req := withoutCreate.Request()
+
+	for attempt := 0; ; attempt++ {
+		if attempt > 0 {
+			if err := ctx.Err(); err != nil {
+				return 0, false, err
+			}
+		}
+		resp, err := b.commands.executor.Execute(ctx, req)
+		if err != nil {
+			return 0, false, err
+		}
+		if resp.Status != meta.StatusNF || !b.create || attempt == maxCreateAttempts {
+			value, _, err := b.result(req, resp)
+			return value, false, err
+		}
+
+		initial := uint64(max(b.delta, 0))
+		ttl := b.createTTL
+		if b.refresh {
+			ttl = b.ttl
+		}
+		// Built like the N flag of Request, not through NewSet: the counter
+		// TTL is used as given.
+		store := meta.NewRequest(meta.CmdSet, b.key, []byte(strconv.FormatUint(initial, 10))).AddModeAdd()
+		if exptime := ttl.Expiration(); exptime != 0 {
+			store.AddTTL(exptime)
+		}
+
+		resp, err = b.commands.executor.Execute(ctx, store)
+		if err != nil {
+			return 0, false, err
+		}
+		if resp.HasError() {
+			return 0, false, b.commands.keyErr(store, resp.Error)
+		}
+		if resp.IsSuccess() {
+			return int64(initial), true, nil
+		}
+		if !resp.IsNotStored() {
+			return 0, false, b.commands.keyErr(store, statusErr(store, "increment", resp.Status))
+		}
+	}
+}
+
+// CounterResult is the outcome of IncrementBuilder.DoWithTTL.
+type CounterResult struct {
+	// Value is the counter value after the operation.
+	Value int64
+
+	// TTL is the remaining lifetime of the counter, e.g. the time until a
+	// rate-limiting window resets. Zero if the counter never expires.
+	TTL time.Duration
+
+	// Expires is false for a counter that never expires.
+	Expires bool
+}
+
+// DoWithTTL executes the arithmetic operation like Do, also returning the
+// remaining lifetime of the counter (t flag), e.g. for a rate limiter to
+// report both the current count and the time until the window resets.
+func (b *IncrementBuilder) DoWithTTL(ctx context.Context) (CounterResult, error) {
+	req := b.Request().AddReturnTTL()
+	value, resp, err := b.do(ctx, req)
+	if err != nil {
+		return CounterResult{}, err
+	}
+
+	remaining, ok := resp.TTL()
+	if !ok {
+		return CounterResult{}, b.commands.keyErr(req, fmt.Errorf("increment response missing TTL"))
+	}
+
+	// The t flag reports -1 for a counter that never expires.
+	result := CounterResult{Value: value}
+	if remaining >= 0 {
+		result.TTL = time.Duration(remaining) * time.Second
+		result.Expires = true
+	}
+	return result, nil
+}
+
+// do executes req, the arithmetic request, returning the counter value and
+// the response.
+func (b *IncrementBuilder) do(ctx context.Context, req *meta.Request) (int64, *meta.Response, error) {
+	resp, err := b.commands.executor.Execute(ctx, req)
+	if err != nil {
+		return 0, nil, err
+	}
+	return b.result(req, resp)
+}
+
+// result maps resp, the response of the arithmetic request req, to the
+// counter value.
+func (b *IncrementBuilder) result(req *meta.Request, resp *meta.Response) (int64, *meta.Response, error) {
+	if resp.Status == meta.StatusNF {
+		return 0, nil, b.commands.keyErr(req, ErrCacheMiss)
+	}
+	value, err := b.commands.counterResult(req, resp)
+	return value, resp, err
+}
@@ -0,0 +1,593 @@
+package memcache
+
+import (
+	"context"
+	"io"
+	"testing"
+	"time"
+
+	"github.com/pior/memcache/internal/testutils"
+	"github.com/pior/memcache/meta"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestSetBuilder(t *testing.T) {
+	t.Run("all options", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("HD\r\n")
+		client := newTestClient(t, mockConn)
+
+		err := client.NewSet("key").
+			Value([]byte("value")).
+			TTL(ExpiresIn(5 * time.Minute)).
+			Mode(SetModeReplace).
+			Flags(7).
+			CAS(42).
+			Do(context.Background())
+
+		require.NoError(t, err)
+		assertRequest(t, mockConn, "ms key 5 MR T300 F7 C42\r\nvalue\r\n")
+	})
+
+	t.Run("defaults", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("HD\r\n")
+		client := newTestClient(t, mockConn)
+
+		err := client.NewSet("key").Value([]byte("value")).Mode(SetModeSet).Do(context.Background())
+
+		require.NoError(t, err)
+		assertRequest(t, mockConn, "ms key 5\r\nvalue\r\n")
+	})
+
+	t.Run("append with vivify", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("HD\r\n")
+		client := newTestClient(t, mockConn)
+
+		err := client.NewSet("key").Value([]byte("value")).Mode(SetModeAppend).Vivify(ExpiresIn(time.Minute)).Do(context.Background())
+
+		require.NoError(t, err)
+		assertRequest(t, mockConn, "ms key 5 MA N60\r\nvalue\r\n")
+	})
+
+	t.Run("invalidate older CAS", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("HD\r\n")
+		client := newTestClient(t, mockConn)
+
+		err := client.NewSet("key").Value([]byte("value")).CAS(42).Invalidate().Do(context.Background())
+
+		require.NoError(t, err)
+		assertRequest(t, mockConn, "ms key 5 C42 I\r\nvalue\r\n")
+	})
+
+	t.Run("invalidate with a newer CAS stored", func(t *testing.T) {
+		client := newTestClient(t, testutils.NewConnectionMock("EX\r\n"))
+
+		err := client.NewSet("key").Value([]byte("value")).CAS(42).Invalidate().Do(context.Background())
+
+		require.ErrorIs(t, err, ErrStaleWrite)
+		assert.ErrorIs(t, err, ErrCASMismatch)
+	})
+
+	t.Run("quiet success", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("MN\r\n")
+		client := newTestClient(t, mockConn)
+
+		err := client.NewSet("key").Value([]byte("value")).Quiet().Do(context.Background())
+
+		require.NoError(t, err)
+		assertRequest(t, mockConn, "ms key 5 q\r\nvalue\r\nmn\r\n")
+	})
+
+	t.Run("quiet failure", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("NS\r\n", "MN\r\n")
+		client := newTestClient(t, mockConn)
+
+		err := client.NewSet("key").Value([]byte("value")).Mode(SetModeAdd).Quiet().Do(context.Background())
+
+		require.ErrorIs(t, err, ErrNotStored)
+	})
+
+	t.Run("not stored", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("NS\r\n")
+		client := newTestClient(t, mockConn)
+
+		err := client.NewSet("key").Value([]byte("value")).Mode(SetModeAdd).Do(context.Background())
+
+		require.ErrorIs(t, err, ErrNotStored)
+	})
+
+	t.Run("CAS mismatch", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("EX\r\n")
+		client := newTestClient(t, mockConn)
+
+		err := client.NewSet("key").Value([]byte("value")).CAS(42).Do(context.Background())
+
+		require.ErrorIs(t, err, ErrCASMismatch)
+	})
+}
+
+func TestGetBuilder(t *testing.T) {
+	t.Run("with CAS and flags", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("VA 5 c42 f7\r\nhello\r\n")
+		client := newTestClient(t, mockConn)
+
+		item, err := client.NewGet("key").ReturnCAS().ReturnFlags().Do(context.Background())
+
+		require.NoError(t, err)
+		assert.True(t, item.Found)
+		assert.Equal(t, "hello", string(item.Value))
+		assert.Equal(t, uint64(42), item.CAS)
+		assert.Equal(t, uint32(7), item.Flags)
+		assertRequest(t, mockConn, "mg key v c f\r\n")
+	})
+
+	t.Run("touch and no LRU bump", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("VA 5\r\nhello\r\n")
+		client := newTestClient(t, mockConn)
+
+		_, err := client.NewGet("key").Touch(ExpiresIn(time.Minute)).NoLRUBump().Do(context.Background())
+
+		require.NoError(t, err)
+		assertRequest(t, mockConn, "mg key v T60 u\r\n")
+	})
+
+	t.Run("miss", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("EN\r\n")
+		client := newTestClient(t, mockConn)
+
+		item, err := client.NewGet("key").ReturnCAS().Do(context.Background())
+
+		require.NoError(t, err)
+		assert.False(t, item.Found)
+		assert.Equal(t, "key", item.Key)
+	})
+
+	t.Run("required miss", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("EN\r\n")
+		client := newTestClient(t, mockConn)
+
+		item, err := client.NewGet("key").Required().Do(context.Background())
+
+		require.ErrorIs(t, err, ErrCacheMiss)
+		assert.False(t, item.Found)
+		assertRequest(t, mockConn, "mg key v\r\n")
+	})
+
+	t.Run("required hit", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("VA 5\r\nhello\r\n")
+		client := newTestClient(t, mockConn)
+
+		item, err := client.NewGet("key").Required().Do(context.Background())
+
+		require.NoError(t, err)
+		assert.Equal(t, "hello", string(item.Value))
+	})
+
+	t.Run("min TTL", func(t *testing.T) {
+		tests := []struct {
+			response string
+			found    bool
+		}{
+			{response: "VA 5 t120\r\nhello\r\n", found: true},
+			{response: "VA 5 t60\r\nhello\r\n", found: true},
+			{response: "VA 5 t59\r\nhello\r\n", found: false},
+			{response: "VA 5 t-1\r\nhello\r\n", found: true},
+		}
+
+		for _, tt := range tests {
+			mockConn := testutils.NewConnectionMock(tt.response)
+			client := newTestClient(t, mockConn)
+
+			item, err := client.NewGet("key").MinTTL(time.Minute).Do(context.Background())
+
+			require.NoError(t, err)
+			assert.Equal(t, tt.found, item.Found, tt.response)
+			assertRequest(t, mockConn, "mg key v t\r\n")
+		}
+	})
+
+	t.Run("min TTL with required", func(t *testing.T) {
+		client := newTestClient(t, testutils.NewConnectionMock("VA 5 t10\r\nhello\r\n"))
+
+		_, err := client.NewGet("key").MinTTL(time.Minute).Required().Do(context.Background())
+
+		require.ErrorIs(t, err, ErrCacheMiss)
+	})
+}
+
+func TestGetBuilder_DoStream(t *testing.T) {
+	newClient := func(t *testing.T, responses ...string) (*Client, *mockDialer) {
+		dialer := &mockDialer{conn: testutils.NewConnectionMock(responses...)}
+		return newTestClientWithConfig(t, nil, Config{Dialer: dialer}), dialer
+	}
+
+	t.Run("small value is buffered", func(t *testing.T) {
+		client, _ := newClient(t, "VA 5\r\nhello\r\n")
+
+		item, stream, err := client.NewGet("key").DoStream(context.Background(), 5)
+
+		require.NoError(t, err)
+		assert.Nil(t, stream)
+		assert.Equal(t, "hello", string(item.Value))
+	})
+
+	t.Run("large value is streamed", func(t *testing.T) {
+		client, dialer := newClient(t, "VA 10 c42\r\n0123456789\r\n", "EN\r\n")
+
+		item, stream, err := client.NewGet("key").ReturnCAS().DoStream(context.Background(), 5)
+		require.NoError(t, err)
+		require.NotNil(t, stream)
+		assert.True(t, item.Found)
+		assert.Nil(t, item.Value)
+		assert.Equal(t, uint64(42), item.CAS)
+		assert.Equal(t, 10, stream.Size())
+
+		value, err := io.ReadAll(stream)
+		require.NoError(t, err)
+		assert.Equal(t, "0123456789", string(value))
+		require.NoError(t, stream.Close())
+
+		// The connection went back to the pool and is reused.
+		_, err = client.Get(context.Background(), "other")
+		require.NoError(t, err)
+		assert.Equal(t, int32(1), dialer.dials.Load())
+	})
+
+	t.Run("quiet request is rejected", func(t *testing.T) {
+		client, dialer := newClient(t, "EN\r\n")
+
+		req := meta.NewRequest(meta.CmdGet, "key", nil).AddReturnValue().AddQuiet()
+		_, _, err := client.ExecuteStream(context.Background(), req, 5)
+		var unsupported *UnsupportedRequestError
+		require.ErrorAs(t, err, &unsupported)
+
+		// Nothing was written: the connection is kept.
+		_, err = client.Get(context.Background(), "other")
+		require.NoError(t, err)
+		assert.Equal(t, int32(1), dialer.dials.Load())
+	})
+
+	t.Run("stream closed early discards the connection", func(t *testing.T) {
+		client, dialer := newClient(t, "VA 10\r\n0123456789\r\n")
+
+		_, stream, err := client.NewGet("key").DoStream(context.Background(), 5)
+		require.NoError(t, err)
+		_, err = stream.Read(make([]byte, 2))
+		require.NoError(t, err)
+		require.NoError(t, stream.Close())
+
+		_, err = stream.Read(make([]byte, 2))
+		require.Error(t, err)
+
+		_, _ = client.Get(context.Background(), "other")
+		assert.Equal(t, int32(2), dialer.dials.Load())
+	})
+
+	t.Run("miss", func(t *testing.T) {
+		client, _ := newClient(t, "EN\r\n")
+
+		item, stream, err := client.NewGet("key").DoStream(context.Background(), 5)
+
+		require.NoError(t, err)
+		assert.Nil(t, stream)
+		assert.False(t, item.Found)
+	})
+}
+
+func TestGetBuilder_DoRecache(t *testing.T) {
+	tests := []struct {
+		name     string
+		response string
+		expected RecacheResult
+	}{
+		{
+			name:     "value",
+			response: "VA 1\r\nv\r\n",
+			expected: RecacheResult{State: RecacheValue, Item: Item{Key: "key", Value: []byte("v"), Found: true}},
+		},
+		{
+			name:     "value to refresh",
+			response: "VA 1 W\r\nv\r\n",
+			expected: RecacheResult{State: RecacheValue, Item: Item{Key: "key", Value: []byte("v"), Found: true}, Won: true},
+		},
+		{
+			name:     "stale value to refresh",
+			response: "VA 1 W X\r\nv\r\n",
+			expected: RecacheResult{State: RecacheStale, Item: Item{Key: "key", Value: []byte("v"), Found: true}, Won: true},
+		},
+		{
+			name:     "stale value refreshed by another client",
+			response: "VA 1 X Z\r\nv\r\n",
+			expected: RecacheResult{State: RecacheStale, Item: Item{Key: "key", Value: []byte("v"), Found: true}, Lost: true},
+		},
+		{
+			name:     "vivified miss",
+			response: "EN W\r\n",
+			expected: RecacheResult{State: RecacheMiss, Item: Item{Key: "key"}, Won: true, Vivified: true},
+		},
+		{
+			name:     "placeholder created by this client",
+			response: "VA 0 W\r\n\r\n",
+			expected: RecacheResult{State: RecacheMiss, Item: Item{Key: "key"}, Won: true, Vivified: true},
+		},
+		{
+			name:     "placeholder created by another client",
+			response: "VA 0 Z\r\n\r\n",
+			expected: RecacheResult{State: RecacheMiss, Item: Item{Key: "key"}, Lost: true, Vivified: true},
+		},
+		{
+			name:     "plain miss",
+			response: "EN\r\n",
+			expected: RecacheResult{State: RecacheMiss, Item: Item{Key: "key"}},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			mockConn := testutils.NewConnectionMock(tt.response)
+			client := newTestClient(t, mockConn)
+
+			result, err := client.NewGet("key").Vivify(ExpiresIn(30 * time.Second)).Recache(time.Minute).DoRecache(context.Background())
+			require.NoError(t, err)
+			assert.Equal(t, tt.expected, result)
+			assertRequest(t, mockConn, "mg key v N30 R60\r\n")
+		})
+	}
+
+	t.Run("sub-second recache threshold rounds up", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("VA 1\r\nv\r\n")
+		client := newTestClient(t, mockConn)
+
+		_, err := client.NewGet("key").Recache(500 * time.Millisecond).DoRecache(context.Background())
+		require.NoError(t, err)
+		assertRequest(t, mockConn, "mg key v R1\r\n")
+	})
+
+	t.Run("protocol error", func(t *testing.T) {
+		client := newTestClient(t, testutils.NewConnectionMock("SERVER_ERROR out of memory\r\n"))
+
+		_, err := client.NewGet("key").Vivify(ExpiresIn(30 * time.Second)).DoRecache(context.Background())
+		require.Error(t, err)
+	})
+}
+
+func TestDeleteBuilder(t *testing.T) {
+	tests := []struct {
+		name     string
+		build    func(b *DeleteBuilder) *DeleteBuilder
+		response string
+		wantReq  string
+		want     DeleteResult
+	}{
+		{
+			name:     "deleted",
+			build:    func(b *DeleteBuilder) *DeleteBuilder { return b },
+			response: "HD\r\n",
+			wantReq:  "md key\r\n",
+			want:     DeleteResultDeleted,
+		},
+		{
+			name:     "not found",
+			build:    func(b *DeleteBuilder) *DeleteBuilder { return b },
+			response: "NF\r\n",
+			wantReq:  "md key\r\n",
+			want:     DeleteResultNotFound,
+		},
+		{
+			name:     "invalidated",
+			build:    func(b *DeleteBuilder) *DeleteBuilder { return b.Invalidate(ExpiresIn(30 * time.Second)) },
+			response: "HD\r\n",
+			wantReq:  "md key I T30\r\n",
+			want:     DeleteResultInvalidated,
+		},
+		{
+			name:     "invalidate not found",
+			build:    func(b *DeleteBuilder) *DeleteBuilder { return b.Invalidate(NoTTL) },
+			response: "NF\r\n",
+			wantReq:  "md key I\r\n",
+			want:     DeleteResultNotFound,
+		},
+		{
+			name:     "with CAS",
+			build:    func(b *DeleteBuilder) *DeleteBuilder { return b.CAS(42) },
+			response: "HD\r\n",
+			wantReq:  "md key C42\r\n",
+			want:     DeleteResultDeleted,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			mockConn := testutils.NewConnectionMock(tt.response)
+			client := newTestClient(t, mockConn)
+
+			result, err := tt.build(client.NewDelete("key")).Do(context.Background())
+
+			require.NoError(t, err)
+			assert.Equal(t, tt.want, result)
+			assertRequest(t, mockConn, tt.wantReq)
+		})
+	}
+
+	t.Run("CAS mismatch", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("EX\r\n")
+		client := newTestClient(t, mockConn)
+
+		_, err := client.NewDelete("key").CAS(42).Do(context.Background())
+
+		require.ErrorIs(t, err, ErrCASMismatch)
+	})
+
+	t.Run("required key not found", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("NF\r\n")
+		client := newTestClient(t, mockConn)
+
+		result, err := client.NewDelete("key").Required().Do(context.Background())
+
+		require.ErrorIs(t, err, ErrCacheMiss)
+		assert.Equal(t, DeleteResultNotFound, result)
+	})
+
+	t.Run("required key deleted", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("HD\r\n")
+		client := newTestClient(t, mockConn)
+
+		result, err := client.NewDelete("key").Required().Do(context.Background())
+
+		require.NoError(t, err)
+		assert.Equal(t, DeleteResultDeleted, result)
+	})
+}
+
+func TestIncrementBuilder(t *testing.T) {
+	tests := []struct {
+		name    string
+		build   func(b *IncrementBuilder) *IncrementBuilder
+		wantReq string
+	}{
+		{
+			name:    "default delta",
+			build:   func(b *IncrementBuilder) *IncrementBuilder { return b },
+			wantReq: "ma key v D1\r\n",
+		},
+		{
+			name:    "create TTL only applies on creation",
+			build:   func(b *IncrementBuilder) *IncrementBuilder { return b.Delta(5).Create(ExpiresIn(time.Hour)) },
+			wantReq: "ma key v D5 J5 N3600\r\n",
+		},
+		{
+			name:    "refresh TTL on every operation",
+			build:   func(b *IncrementBuilder) *IncrementBuilder { return b.TTL(ExpiresIn(time.Minute)) },
+			wantReq: "ma key v D1 T60\r\n",
+		},
+		{
+			name: "distinct create and refresh TTLs",
+			build: func(b *IncrementBuilder) *IncrementBuilder {
+				return b.Create(ExpiresIn(time.Hour)).TTL(ExpiresIn(time.Minute))
+			},
+			wantReq: "ma key v D1 J1 N3600 T60\r\n",
+		},
+		{
+			name:    "decrement creates at zero",
+			build:   func(b *IncrementBuilder) *IncrementBuilder { return b.Delta(-2).Create(NoTTL) },
+			wantReq: "ma key v D2 MD J0 N0\r\n",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			mockConn := testutils.NewConnectionMock("VA 1\r\n7\r\n")
+			client := newTestClient(t, mockConn)
+
+			value, err := tt.build(client.NewIncrement("key")).Do(context.Background())
+
+			require.NoError(t, err)
+			assert.Equal(t, int64(7), value)
+			assertRequest(t, mockConn, tt.wantReq)
+		})
+	}
+
+	t.Run("missing counter without create", func(t *testing.T) {
+		client := newTestClient(t, testutils.NewConnectionMock("NF\r\n"))
+
+		_, err := client.NewIncrement("key").Do(context.Background())
+
+		require.ErrorIs(t, err, ErrCacheMiss)
+	})
+
+	t.Run("with TTL", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("VA 1 t42\r\n7\r\n")
+		client := newTestClient(t, mockConn)
+
+		result, err := client.NewIncrement("key").Create(ExpiresIn(time.Minute)).DoWithTTL(context.Background())
+
+		require.NoError(t, err)
+		assert.Equal(t, CounterResult{Value: 7, TTL: 42 * time.Second, Expires: true}, result)
+		assertRequest(t, mockConn, "ma key v D1 J1 N60 t\r\n")
+	})
+
+	t.Run("with TTL never expiring", func(t *testing.T) {
+		client := newTestClient(t, testutils.NewConnectionMock("VA 1 t-1\r\n7\r\n"))
+
+		result, err := client.NewIncrement("key").DoWithTTL(context.Background())
+
+		require.NoError(t, err)
+		assert.Equal(t, CounterResult{Value: 7}, result)
+	})
+
+	t.Run("with created on creation", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("NF\r\n", "HD\r\n")
+		client := newTestClient(t, mockConn)
+
+		value, created, err := client.NewIncrement("key").Delta(5).Create(ExpiresIn(time.Hour)).DoWithCreated(context.Background())
+
+		require.NoError(t, err)
+		assert.Equal(t, int64(5), value)
+		assert.True(t, created)
+		assertRequest(t, mockConn, "ma key v D5\r\nms key 1 ME T3600\r\n5\r\n")
+	})
+
+	t.Run("with created on existing counter", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("VA 2\r\n10\r\n")
+		client := newTestClient(t, mockConn)
+
+		value, created, err := client.NewIncrement("key").Delta(5).Create(ExpiresIn(time.Hour)).DoWithCreated(context.Background())
+
+		require.NoError(t, err)
+		assert.Equal(t, int64(10), value)
+		assert.False(t, created)
+		assertRequest(t, mockConn, "ma key v D5\r\n")
+	})
+
+	t.Run("with created losing the creation race", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("NF\r\n", "NS\r\n", "VA 2\r\n10\r\n")
+		client := newTestClient(t, mockConn)
+
+		value, created, err := client.NewIncrement("key").Delta(5).Create(NoTTL).TTL(ExpiresIn(time.Minute)).DoWithCreated(context.Background())
+
+		require.NoError(t, err)
+		assert.Equal(t, int64(10), value)
+		assert.False(t, created)
+		assertRequest(t, mockConn, "ma key v D5 T60\r\nms key 1 ME T60\r\n5\r\nma key v D5 T60\r\n")
+	})
+
+	t.Run("with created gives up after repeated creation races", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("NF\r\n", "NS\r\n", "NF\r\n", "NS\r\n", "NF\r\n")
+		client := newTestClient(t, mockConn)
+
+		_, created, err := client.NewIncrement("key").Delta(5).Create(NoTTL).DoWithCreated(context.Background())
+
+		require.ErrorIs(t, err, ErrCacheMiss)
+		assert.False(t, created)
+		assertRequest(t, mockConn, "ma key v D5\r\nms key 1 ME\r\n5\r\nma key v D5\r\nms key 1 ME\r\n5\r\nma key v D5\r\n")
+	})
+
+	t.Run("with created stops retrying once the context is done", func(t *testing.T) {
+		ctx, cancel := context.WithCancel(context.Background())
+		mockConn := testutils.NewConnectionMock("NF\r\n", "NS\r\n")
+		client := newTestClientWithConfig(t, mockConn, Config{
+			// Cancels once the lost creation race is known.
+			SlowOpThreshold: time.Nanosecond,
+			Hooks: Hooks{OnSlowOp: func(addr, op string, keyLen int, elapsed time.Duration) {
+				if op == "ms" {
+					cancel()
+				}
+			}},
+		})
+
+		_, created, err := client.NewIncrement("key").Delta(5).Create(NoTTL).DoWithCreated(ctx)
+
+		require.ErrorIs(t, err, context.Canceled)
+		assert.False(t, created)
+		assertRequest(t, mockConn, "ma key v D5\r\nms key 1 ME\r\n5\r\n")
+	})
+
+	t.Run("with created without create", func(t *testing.T) {
+		client := newTestClient(t, testutils.NewConnectionMock("NF\r\n"))
+
+		_, created, err := client.NewIncrement("key").DoWithCreated(context.Background())
+
+		require.ErrorIs(t, err, ErrCacheMiss)
+		assert.False(t, created)
+	})
+}
@@ -0,0 +1,122 @@
+package memcache
+
+import (
+	"bytes"
+	"context"
+	"fmt"
+	"math/rand/v2"
+	"strconv"
+)
+
+// DefaultChunkSize is the chunk size used by SetChunked when none is given:
+// below memcached's default 1MB item size limit, leaving room for the item
+// header.
+const DefaultChunkSize = 1000 * 1024
+
+// MaxChunks is the largest number of chunks a value is split into by
+// SetChunked, and read back by GetChunked.
+const MaxChunks = 1 << 16
+
+// SetChunked stores a value larger than the server item size limit by
+// splitting it into chunks of at most chunkSize bytes (DefaultChunkSize if
+// chunkSize <= 0), stored under the keys "<key>:<generation>:0",
+// "<key>:<generation>:1", etc, where the generation is a random identifier of
+// this write. A manifest recording the generation, the chunk count and the
+// value size is stored under the key itself, after the chunks. All the items
+// get the item TTL. A value needing more than MaxChunks chunks is rejected.
+//
+// The chunks are not written atomically: a concurrent GetChunked may see a
+// miss while the value is being replaced. Concurrent writes of the same key
+// store their chunks under distinct generations, so a reader never mixes
+// the chunks of two writes: it gets the value of the last manifest stored.
+// The chunks of a replaced value are left to expire.
+func (b *BatchCommands) SetChunked(ctx context.Context, item Item, chunkSize int) error {
+	if chunkSize <= 0 {
+		chunkSize = DefaultChunkSize
+	}
+
+	if count := (len(item.Value) + chunkSize - 1) / chunkSize; count > MaxChunks {
+		return fmt.Errorf("memcache: value of %d bytes needs %d chunks of %d bytes, more than %d", len(item.Value), count, chunkSize, MaxChunks)
+	}
+
+	generation := strconv.FormatUint(rand.Uint64(), 16)
+
+	var chunks []Item
+	for i, value := 0, item.Value; len(value) > 0; i++ {
+		n := min(chunkSize, len(value))
+		chunks = append(chunks, Item{Key: chunkKey(item.Key, generation, i), Value: value[:n], TTL: item.TTL})
+		value = value[n:]
+	}
+
+	if err := b.MultiSet(ctx, chunks); err != nil {
+		return err
+	}
+
+	manifest := generation + " " + strconv.Itoa(len(chunks)) + " " + strconv.Itoa(len(item.Value))
+	return b.commands.Set(ctx, Item{Key: item.Key, Value: []byte(manifest), TTL: item.TTL})
+}
+
+// GetChunked retrieves a value stored with SetChunked. A missing chunk, or
+// chunks not adding up to the recorded size (e.g. a value being replaced),
+// is reported as a miss: an Item with Found=false.
+func (b *BatchCommands) GetChunked(ctx context.Context, key string) (Item, error) {
+	manifest, err := b.commands.Get(ctx, key)
+	if err != nil || !manifest.Found {
+		return manifest, err
+	}
+
+	generation, count, size, ok := parseChunkManifest(manifest.Value)
+	if !ok {
+		return Item{}, &ChunkManifestError{Manifest: manifest.Value}
+	}
+
+	keys := make([]string, count)
+	for i := range keys {
+		keys[i] = chunkKey(key, generation, i)
+	}
+
+	chunks, err := b.MultiGet(ctx, keys)
+	if err != nil {
+		return Item{}, err
+	}
+
+	// The size is checked against the chunks read before allocating, so a
+	// corrupt manifest can't claim a huge value.
+	total := 0
+	for _, chunk := range chunks {
+		if !chunk.Found {
+			return Item{Key: key, Found: false}, nil
+		}
+		total += len(chunk.Value)
+	}
+	if total != size {
+		return Item{Key: key, Found: false}, nil
+	}
+
+	value := make([]byte, 0, size)
+	for _, chunk := range chunks {
+		value = append(value, chunk.Value...)
+	}
+
+	return Item{Key: key, Value: value, Found: true}, nil
+}
+
+func chunkKey(key, generation string, i int) string {
+	return key + ":" + generation + ":" + strconv.Itoa(i)
+}
+
+// parseChunkManifest parses a manifest value:
+// "<generation> <chunk count> <value size>". A manifest with more than
+// MaxChunks chunks, or more chunks than bytes, is invalid.
+func parseChunkManifest(data []byte) (generation string, count, size int, ok bool) {
+	fields := bytes.Fields(data)
+	if len(fields) != 3 {
+		return "", 0, 0, false
+	}
+	count, countErr := strconv.Atoi(string(fields[1]))
+	size, sizeErr := strconv.Atoi(string(fields[2]))
+	if countErr != nil || sizeErr != nil || count < 0 || count > MaxChunks || size < count {
+		return "", 0, 0, false
+	}
+	return string(fields[0]), count, size, true
+}
@@ -0,0 +1,130 @@
+package memcache
+
+import (
+	"context"
+	"regexp"
+	"strconv"
+	"testing"
+	"time"
+
+	"github.com/pior/memcache/meta"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestBatchCommands_SetChunked(t *testing.T) {
+	t.Run("splits value and stores manifest last", func(t *testing.T) {
+		bc, mock := newBatchTestClient(t, "HD\r\n", "HD\r\n", "HD\r\n", "MN\r\n", "HD\r\n")
+
+		item := Item{Key: "k", Value: []byte("abcdefgh"), TTL: ExpiresIn(time.Minute)}
+		require.NoError(t, bc.SetChunked(context.Background(), item, 3))
+
+		written := mock.GetWrittenRequest()
+		match := regexp.MustCompile(`^ms k:(\w+):0 `).FindStringSubmatch(written)
+		require.NotNil(t, match, written)
+		gen := match[1]
+
+		assert.Equal(t, "ms k:"+gen+":0 3 T60\r\nabc\r\n"+
+			"ms k:"+gen+":1 3 T60\r\ndef\r\n"+
+			"ms k:"+gen+":2 2 T60\r\ngh\r\n"+
+			"mn\r\n"+
+			"ms k "+strconv.Itoa(len(gen)+4)+" T60\r\n"+gen+" 3 8\r\n", written)
+	})
+
+	t.Run("each write has its own generation", func(t *testing.T) {
+		bc, mock := newBatchTestClient(t, "HD\r\n", "MN\r\n", "HD\r\n", "HD\r\n", "MN\r\n", "HD\r\n")
+
+		item := Item{Key: "k", Value: []byte("abc")}
+		require.NoError(t, bc.SetChunked(context.Background(), item, 3))
+		require.NoError(t, bc.SetChunked(context.Background(), item, 3))
+
+		gens := regexp.MustCompile(`ms k:(\w+):0 `).FindAllStringSubmatch(mock.GetWrittenRequest(), -1)
+		require.Len(t, gens, 2)
+		assert.NotEqual(t, gens[0][1], gens[1][1])
+	})
+
+	t.Run("too many chunks", func(t *testing.T) {
+		bc, mock := newBatchTestClient(t)
+
+		err := bc.SetChunked(context.Background(), Item{Key: "k", Value: make([]byte, MaxChunks+1)}, 1)
+
+		require.Error(t, err)
+		assert.Empty(t, mock.GetWrittenRequest())
+	})
+
+	t.Run("chunk failure skips manifest", func(t *testing.T) {
+		bc, mock := newBatchTestClient(t, "HD\r\n", "SERVER_ERROR out of memory\r\n", "MN\r\n")
+
+		err := bc.SetChunked(context.Background(), Item{Key: "k", Value: []byte("abcdef")}, 3)
+
+		var serverErr *meta.ServerError
+		require.ErrorAs(t, err, &serverErr)
+		assert.NotContains(t, mock.GetWrittenRequest(), "ms k ")
+	})
+}
+
+func TestBatchCommands_GetChunked(t *testing.T) {
+	t.Run("reassembles chunks", func(t *testing.T) {
+		bc, mock := newBatchTestClient(t,
+			"VA 6\r\nab 3 8\r\n",
+			"VA 3\r\nabc\r\n", "VA 3\r\ndef\r\n", "VA 2\r\ngh\r\n", "MN\r\n",
+		)
+
+		item, err := bc.GetChunked(context.Background(), "k")
+		require.NoError(t, err)
+		assert.True(t, item.Found)
+		assert.Equal(t, "abcdefgh", string(item.Value))
+		assert.Equal(t, "mg k v\r\nmg k:ab:0 v\r\nmg k:ab:1 v\r\nmg k:ab:2 v\r\nmn\r\n", mock.GetWrittenRequest())
+	})
+
+	t.Run("missing manifest", func(t *testing.T) {
+		bc, _ := newBatchTestClient(t, "EN\r\n")
+
+		item, err := bc.GetChunked(context.Background(), "k")
+		require.NoError(t, err)
+		assert.False(t, item.Found)
+	})
+
+	t.Run("missing chunk is a miss", func(t *testing.T) {
+		bc, _ := newBatchTestClient(t, "VA 6\r\nab 2 6\r\n", "VA 3\r\nabc\r\n", "EN\r\n", "MN\r\n")
+
+		item, err := bc.GetChunked(context.Background(), "k")
+		require.NoError(t, err)
+		assert.False(t, item.Found)
+		assert.Equal(t, "k", item.Key)
+	})
+
+	t.Run("size mismatch is a miss", func(t *testing.T) {
+		bc, _ := newBatchTestClient(t, "VA 6\r\nab 2 6\r\n", "VA 3\r\nabc\r\n", "VA 2\r\nde\r\n", "MN\r\n")
+
+		item, err := bc.GetChunked(context.Background(), "k")
+		require.NoError(t, err)
+		assert.False(t, item.Found)
+	})
+
+	t.Run("invalid manifest", func(t *testing.T) {
+		bc, _ := newBatchTestClient(t, "VA 5\r\nhello\r\n")
+
+		_, err := bc.GetChunked(context.Background(), "k")
+		var manifestErr *ChunkManifestError
+		require.ErrorAs(t, err, &manifestErr)
+		assert.False(t, meta.ShouldCloseConnection(err))
+	})
+
+	t.Run("manifest with an impossible size is a miss", func(t *testing.T) {
+		bc, _ := newBatchTestClient(t, "VA 22\r\nx 1 999999999999999999\r\n", "VA 3\r\nabc\r\n", "MN\r\n")
+
+		item, err := bc.GetChunked(context.Background(), "k")
+		require.NoError(t, err)
+		assert.False(t, item.Found)
+	})
+
+	t.Run("manifest with too many chunks", func(t *testing.T) {
+		manifest := "x " + strconv.Itoa(MaxChunks+1) + " 999999999"
+		bc, _ := newBatchTestClient(t, "VA "+strconv.Itoa(len(manifest))+"\r\n"+manifest+"\r\n")
+
+		_, err := bc.GetChunked(context.Background(), "k")
+		var manifestErr *ChunkManifestError
+		require.ErrorAs(t, err, &manifestErr)
+	})
+}
@@ -2,8 +2,13 @@ package memcache
 
 import (
 	"context"
+	"errors"
 	"fmt"
+	"iter"
+	"log/slog"
+	"math/rand/v2"
 	"net"
+	"slices"
 	"sync"
 	"time"
 
@@ -21,19 +26,33 @@ type Item struct {
 	Key   string
 	Value []byte
 	TTL   TTL
-	Found bool // indicates whether the key was found in cache
+	Flags uint32 // client flags, returned by GetBuilder.ReturnFlags; convert to a named type for app-defined bits
+	CAS   uint64 // CAS token, returned by GetBuilder.ReturnCAS
+	Found bool   // indicates whether the key was found in cache
+}
+
+// CASMatches reports whether the item carries the expected CAS token, e.g. to
+// check a read-modify-write before storing. It is false if no CAS was returned.
+func (i Item) CASMatches(expected uint64) bool {
+	return i.CAS != 0 && i.CAS == expected
 }
 
 // Config holds configuration for the memcache client connection pool.
 type Config struct {
 	// MaxSize is the maximum number of connections in the pool.
+	// A connection carries one operation at a time, so MaxSize also bounds the
+	// requests in flight to each server: excess operations wait for a free
+	// connection (bounded by their context) instead of piling up. The current
+	// count is ConnPoolMetrics.ActiveConns, and waits show in AcquireWaitCount.
 	// Default: 10
 	// Required: must be > 0.
 	MaxSize int32
 
-	// MaxConnLifetime is the maximum duration a connection can be reused.
-	// Enforced when a connection is returned to the pool after an operation,
-	// and by the health check loop for idle connections.
+	// MaxConnLifetime is the maximum duration a connection can be reused,
+	// e.g. to rebalance connections across servers behind a load balancer
+	// after scaling. Enforced when a connection is checked out of the pool,
+	// when it is returned after an operation, and by the health check loop for
+	// idle connections.
 	// Zero means no limit.
 	MaxConnLifetime time.Duration
 
@@ -52,8 +71,13 @@ type Config struct {
 	// capped at Timeout. This ensures a long-lived context (e.g. a request- or
 	// job-scoped one) cannot leave an operation unbounded, so a hung-but-connected
 	// server fails fast instead of stalling the client.
+	// The context deadline is how a caller propagates its overall latency
+	// budget: when it stops the operation, the error matches
+	// context.DeadlineExceeded.
 	// Zero means no cap — the operation is bounded only by the context (not
 	// recommended for production).
+	// Use WithOperationTimeout to override it for a single call, e.g. a large
+	// bulk store.
 	// Recommended: 100ms-1s depending on your latency requirements.
 	Timeout time.Duration
 
@@ -66,6 +90,12 @@ type Config struct {
 	// Dialer is used to create new connections. If nil, a default
 	// net.Dialer is used.
 	//
+	// A net.Dialer already connects "Happy Eyeballs" style to hostnames that
+	// resolve to both IPv6 and IPv4: the IPv4 attempt starts if IPv6 hasn't
+	// connected within FallbackDelay (300ms by default), and the first to
+	// succeed wins. Set a net.Dialer with a shorter FallbackDelay to fall back
+	// sooner on networks where IPv6 is broken.
+	//
 	// To connect over TLS (memcached running with --enable-ssl), set a
 	// *tls.Dialer, which satisfies this interface:
 	//
@@ -79,6 +109,23 @@ type Config struct {
 	// selects its config based on the address.
 	Dialer Dialer
 
+	// Username and Password authenticate every new connection, for servers
+	// requiring authentication (memcached started with -Y, as many managed
+	// offerings are). They are sent with the text protocol's authentication
+	// command before the connection is handed out: SASL is only available in
+	// the binary protocol, which this client doesn't speak. A rejected
+	// authentication fails the connection with ErrAuthFailed. The username
+	// must not contain whitespace.
+	// Default: "" (no authentication)
+	Username string
+	Password string
+
+	// LocalAddr is the local address connections originate from, e.g. to pick
+	// the interface of a multi-homed host. The port is usually zero.
+	// Only applies to the default Dialer: set net.Dialer.LocalAddr on a custom
+	// one. Connections fail with the bind error if the address is unusable.
+	LocalAddr net.Addr
+
 	// NewPool is the connection pool factory function.
 	// If nil, uses the puddle-based pool.
 	NewPool func(constructor func(ctx context.Context) (*Connection, error), maxSize int32) (Pool, error)
@@ -92,6 +139,140 @@ type Config struct {
 	// If nil, no circuit breaker is used.
 	// The Name field in the settings will be overridden with the server address.
 	CircuitBreakerSettings *gobreaker.Settings
+
+	// TCPQuickAck enables TCP_QUICKACK on new connections, disabling delayed
+	// ACKs for lower request/response latency (Go already disables Nagle's
+	// algorithm by default). The option is set once after connecting, and the
+	// kernel may fall back to delayed ACKs later on.
+	// Linux only: ignored on other platforms.
+	TCPQuickAck bool
+
+	// SocketReadBuffer and SocketWriteBuffer set the kernel receive and send
+	// buffer sizes (SO_RCVBUF and SO_SNDBUF) of new connections, in bytes, to
+	// tune throughput of large pipelined batches. Zero keeps the system
+	// default. The kernel may clamp (or, on Linux, double) the requested
+	// size: read the effective value back with getsockopt if it matters.
+	SocketReadBuffer  int
+	SocketWriteBuffer int
+
+	// SlowOpThreshold is the latency above which an operation is reported to
+	// Hooks.OnSlowOp. Zero disables slow operation reporting.
+	SlowOpThreshold time.Duration
+
+	// LatencyHistogram records the latency of every operation, including the
+	// wait for a pooled connection, in an in-memory histogram read with
+	// Client.LatencySnapshot: quick latency introspection without a metrics
+	// system. Recording takes no lock. Off by default.
+	LatencyHistogram bool
+
+	// Hooks are optional callbacks observing the connection lifecycle.
+	Hooks Hooks
+
+	// DefaultTTL is the expiration of the items stored without a TTL (NoTTL)
+	// by the client's commands (Set, Add, NewSet), including the stores of
+	// the BatchCommands of the client (MultiSet, MultiStore, SetChunked,
+	// Swap). Use NeverExpire for items that must not expire.
+	// Default: NoTTL (items never expire)
+	DefaultTTL TTL
+
+	// TTLJitter moves the expiration of the items stored with a relative TTL
+	// (ExpiresIn, including a DefaultTTL) by a random offset within
+	// ±TTLJitter, so keys stored together with the same TTL don't all expire
+	// at once and hit the backend in a thundering herd. The jittered TTL is
+	// at least one second. It applies to the same stores as DefaultTTL.
+	// Default: 0 (no jitter)
+	TTLJitter time.Duration
+
+	// TTLJitterRand returns a random number in [0, n) for TTLJitter, e.g. to
+	// make tests deterministic.
+	// Default: rand.Int64N
+	TTLJitterRand func(n int64) int64
+
+	// MaxKeyLength is the longest key the client accepts, in bytes. Raise it
+	// for memcached builds compiled with a larger KEY_MAX_LENGTH: the limit
+	// is a build constant that the server doesn't report.
+	// Default: 250 (meta.MaxKeyLength)
+	MaxKeyLength int
+
+	// KeyTransformer, if set, maps every key to the key sent to the servers,
+	// e.g. to add a namespace, lowercase keys, or hash keys longer than
+	// MaxKeyLength down to a fixed size (see HashLongKeys). It runs before
+	// key validation and server selection, for every request of the client
+	// (single, batch and streamed), so no call site can forget it. Items are
+	// returned with the key the caller passed, but keys echoed by the server
+	// (k flag) are the transformed ones. The key of a request with the b flag
+	// is passed base64-encoded. It must be deterministic and safe for
+	// concurrent use.
+	KeyTransformer func(key string) string
+
+	// BufferPool provides the buffers that values are read into, to recycle
+	// them in very high throughput services instead of allocating one per
+	// value (see meta.NewBufferPool). Item.Value (and Response.Data) then
+	// belongs to the caller, who returns it with BufferPool.Put once done with
+	// it; a value that is not returned is simply garbage collected. A value
+	// must be returned at most once: MultiGetUnique hands the same Value to
+	// every position of a repeated key, so return it for one of them only, and
+	// the Value GetOrSet shares between coalesced callers comes from fill, not
+	// from the pool, so it must not be returned at all.
+	// Default: nil (a new allocation per value)
+	BufferPool meta.BufferPool
+
+	// MaxPipelineDepth splits the batches sent to a server (ExecuteBatch,
+	// BatchCommands, MultiGetSeq) into successive pipelines of at most this
+	// many requests, each ended by its own mn, to bound the memory a single
+	// huge batch takes. The caller still gets the responses of the whole
+	// batch, in order.
+	// Default: 0 (a single pipeline per server)
+	MaxPipelineDepth int
+
+	// LenientLineEndings accepts meta responses whose lines end with a bare
+	// LF instead of CRLF, for non-conformant proxies or emulators. Requests
+	// are always written with CRLF.
+	// Default: false (a bare LF is a protocol error)
+	LenientLineEndings bool
+
+	// KeyInErrors adds the key to the message of operation errors (OpError),
+	// to ease log triage. The failures of single-key commands that are not
+	// connection errors (protocol errors, unexpected statuses) are then
+	// returned as OpError too.
+	// Off by default: keys often carry user identifiers (PII).
+	KeyInErrors bool
+
+	// SkipOversizedValues makes the client's stores (Set, Add, NewSet) succeed
+	// without storing anything when the server rejects the value as larger
+	// than its item size limit, for best-effort caches where an occasional
+	// oversized object must not fail the request path. Beware: the store
+	// then returns nil although nothing was stored. BatchCommands are not
+	// affected. By default the server error is returned.
+	SkipOversizedValues bool
+
+	// WireLogger, if set, logs every byte sent to and received from the
+	// servers at debug level, as escaped strings. Meant for protocol-level
+	// debugging: it is verbose and slow, and logs keys and values.
+	WireLogger *slog.Logger
+
+	// WireLogMaxBytes truncates each logged chunk of wire data.
+	// Default: 256
+	WireLogMaxBytes int
+
+	// NegativeCacheSize enables an in-memory cache of recent misses, holding
+	// up to this many keys: a get of a key that missed less than
+	// NegativeCacheTTL ago returns a miss without a round trip. Meant for
+	// workloads repeatedly requesting the same missing keys.
+	// Writes through this client (stores, deletes, arithmetic, including
+	// batches) invalidate the key, and so do gets finding it; writes from
+	// other clients are not seen, so a key stored elsewhere may read as
+	// missing for up to NegativeCacheTTL, or the shorter TTL set on the
+	// context with WithNegativeCacheTTL. Only single gets without the k, O,
+	// q, b and N flags (Get, NewGet) are answered from the cache; batches
+	// always reach the servers.
+	// Default: 0 (disabled)
+	NegativeCacheSize int
+
+	// NegativeCacheTTL is how long a miss is remembered, and the maximum TTL
+	// of WithNegativeCacheTTL.
+	// Default: 1s (DefaultNegativeCacheTTL)
+	NegativeCacheTTL time.Duration
 }
 
 // Client is a memcache client that implements the Querier interface using a connection pool.
@@ -107,6 +288,9 @@ type Client struct {
 
 	config Config
 
+	// negCache is the cache of recent misses, nil when disabled.
+	negCache *negativeCache
+
 	// Health check management
 	stopHealthCheck chan struct{}
 	closeOnce       sync.Once
@@ -133,11 +317,14 @@ func NewClient(servers Servers, config Config) *Client {
 		config.ServerSelector = DefaultServerSelector
 	}
 	if config.Dialer == nil {
-		config.Dialer = &net.Dialer{}
+		config.Dialer = &net.Dialer{LocalAddr: config.LocalAddr}
 	}
 	if config.NewPool == nil {
 		config.NewPool = NewPuddlePool
 	}
+	if config.TTLJitterRand == nil {
+		config.TTLJitterRand = rand.Int64N
+	}
 
 	client := &Client{
 		servers:         servers,
@@ -145,9 +332,17 @@ func NewClient(servers Servers, config Config) *Client {
 		config:          config,
 		stopHealthCheck: make(chan struct{}),
 	}
+	if config.NegativeCacheSize > 0 {
+		client.negCache = newNegativeCache(config.NegativeCacheSize, config.NegativeCacheTTL)
+	}
 
 	// Initialize embedded Commands with execute function
 	client.Commands = NewCommands(client)
+	client.Commands.keyInErrors = config.KeyInErrors
+	client.Commands.defaultTTL = config.DefaultTTL
+	client.Commands.ttlJitter = config.TTLJitter
+	client.Commands.jitterRand = config.TTLJitterRand
+	client.Commands.skipOversized = config.SkipOversizedValues
 
 	// Start health check goroutine if enabled
 	if config.HealthCheckInterval > 0 {
@@ -157,21 +352,48 @@ func NewClient(servers Servers, config Config) *Client {
 	return client
 }
 
+// Execute sends req to the server owning its key and returns the raw
+// response. The commands map responses to Items and errors; Execute keeps the
+// exact status, for tools and tests that care about it: e.g. HD for a hit
+// without a value requested, VA for a hit with one. Build req by hand or with
+// a builder's Request method.
+//
+// Protocol errors are reported in Response.Error, not as a Go error.
 func (c *Client) Execute(ctx context.Context, req *meta.Request) (*meta.Response, error) {
+	req = c.transformKey(req)
 	sp, err := c.getPoolForKey(req.Key)
 	if err != nil {
 		return nil, err
 	}
+	if c.negCache != nil {
+		return c.negCache.execute(ctx, req, func() (*meta.Response, error) { return sp.Execute(ctx, req) })
+	}
 	return sp.Execute(ctx, req)
 }
 
+// ExecuteStream implements the StreamExecutor interface, executing the request
+// on the server of its key (see Connection.ExecuteStream). It bypasses the
+// negative cache.
+func (c *Client) ExecuteStream(ctx context.Context, req *meta.Request, threshold int) (*meta.Response, *ValueStream, error) {
+	req = c.transformKey(req)
+	sp, err := c.getPoolForKey(req.Key)
+	if err != nil {
+		return nil, nil, err
+	}
+	return sp.ExecuteStream(ctx, req, threshold)
+}
+
 // ExecuteBatch executes multiple requests with automatic server routing.
 // Requests are grouped by server and executed concurrently using pipelined requests.
 // Returns responses in the same order as requests.
 //
 // Responses are matched to requests by position, which requires every request
 // to produce a response: requests using the quiet flag are rejected. Use
-// Connection.ExecuteBatch directly for quiet pipelining.
+// Connection.ExecuteBatch directly for quiet pipelining. A NoOp (mn) request
+// is rejected with an *UnsupportedRequestError: the batch ends with its own.
+//
+// Keys are all validated before any request is sent: an invalid key rejects
+// the whole batch with a *meta.BatchKeyError carrying its position.
 //
 // If any server batch fails, an error is returned and the responses are
 // discarded, including those from servers that succeeded.
@@ -180,10 +402,28 @@ func (c *Client) ExecuteBatch(ctx context.Context, reqs []*meta.Request) ([]*met
 		return nil, nil
 	}
 
+	if c.config.KeyTransformer != nil {
+		transformed := make([]*meta.Request, len(reqs))
+		for i, req := range reqs {
+			transformed[i] = c.transformKey(req)
+		}
+		reqs = transformed
+	}
+
 	for _, req := range reqs {
 		if req.HasFlag(meta.FlagQuiet) {
 			return nil, fmt.Errorf("memcache: quiet flag is not supported in ExecuteBatch: responses are matched to requests by position")
 		}
+		if req.Command == meta.CmdNoOp {
+			return nil, &UnsupportedRequestError{Message: "mn is not allowed in a batch: it is the batch terminator"}
+		}
+	}
+
+	// Validate all keys up front: once the batch is split across servers, an
+	// invalid key would otherwise only fail its own server's batch while the
+	// others are already sent.
+	if err := meta.ValidateBatchKeys(reqs, c.maxKeyLength()); err != nil {
+		return nil, err
 	}
 
 	// Group requests by server
@@ -212,6 +452,10 @@ func (c *Client) ExecuteBatch(ctx context.Context, reqs []*meta.Request) ([]*met
 	// Prepare result slice
 	results := make([]*meta.Response, len(reqs))
 
+	if c.negCache != nil {
+		defer c.negCache.invalidateBatch(reqs, results)
+	}
+
 	// Execute batches concurrently per server
 	var wg sync.WaitGroup
 	errChan := make(chan error, len(serverBatches))
@@ -228,11 +472,16 @@ func (c *Client) ExecuteBatch(ctx context.Context, reqs []*meta.Request) ([]*met
 				return
 			}
 
-			// Execute batch using ServerPool.ExecuteBatch
-			responses, err := sp.ExecuteBatch(ctx, b.reqs)
-			if err != nil {
-				errChan <- err
-				return
+			// Execute batch using ServerPool.ExecuteBatch, one pipeline of
+			// at most MaxPipelineDepth requests at a time
+			var responses []*meta.Response
+			for chunk := range slices.Chunk(b.reqs, c.pipelineDepth(len(b.reqs))) {
+				chunkResponses, err := sp.ExecuteBatch(ctx, chunk)
+				if err != nil {
+					errChan <- err
+					return
+				}
+				responses = append(responses, chunkResponses...)
 			}
 
 			// Without quiet flags, Connection.ExecuteBatch guarantees one
@@ -264,6 +513,122 @@ func (c *Client) ExecuteBatch(ctx context.Context, reqs []*meta.Request) ([]*met
 	return results, nil
 }
 
+// MultiGetSeq retrieves multiple items, yielding each one as soon as its
+// server answers instead of waiting for the whole batch: the caller can start
+// processing the first items while the others are still in flight. Keys are
+// grouped by server and the server batches run concurrently, so items come in
+// no particular order; use Item.Key to match them, a miss being an Item with
+// Found=false.
+//
+// A protocol error for a key is yielded with that key and the iteration goes
+// on; a failed server batch yields its error once. Stopping the iteration
+// early cancels the batches still in flight.
+func (c *Client) MultiGetSeq(ctx context.Context, keys []string) iter.Seq2[Item, error] {
+	return func(yield func(Item, error) bool) {
+		if len(keys) == 0 {
+			return
+		}
+
+		for i, key := range keys {
+			if err := meta.ValidateKeyWithLimit(c.wireKey(key), false, c.maxKeyLength()); err != nil {
+				yield(Item{Key: key}, &meta.BatchKeyError{Index: i, Err: err})
+				return
+			}
+		}
+
+		// Group keys by server
+		serverKeys := make(map[string][]string)
+		for _, key := range keys {
+			addr, err := c.selectServerForKey(c.wireKey(key))
+			if err != nil {
+				yield(Item{Key: key}, err)
+				return
+			}
+			serverKeys[addr] = append(serverKeys[addr], key)
+		}
+
+		ctx, cancel := context.WithCancel(ctx)
+		defer cancel()
+
+		type result struct {
+			item Item
+			err  error
+		}
+		results := make(chan result)
+		send := func(r result) bool {
+			select {
+			case results <- r:
+				return true
+			case <-ctx.Done():
+				return false
+			}
+		}
+
+		var wg sync.WaitGroup
+		wg.Add(len(serverKeys))
+
+		for addr, keys := range serverKeys {
+			go func() {
+				defer wg.Done()
+
+				sp, err := c.getPoolForServer(addr)
+				if err != nil {
+					send(result{err: err})
+					return
+				}
+
+				reqs := make([]*meta.Request, len(keys))
+				for i, key := range keys {
+					reqs[i] = meta.NewRequest(meta.CmdGet, c.wireKey(key), nil).AddReturnValue()
+				}
+
+				i := 0
+				for chunk := range slices.Chunk(reqs, c.pipelineDepth(len(reqs))) {
+					for resp, err := range sp.ExecuteBatchSeq(ctx, chunk) {
+						if err != nil {
+							send(result{err: err})
+							return
+						}
+
+						key, req := keys[i], reqs[i]
+						i++
+
+						var r result
+						switch {
+						case resp.HasError():
+							r = result{item: Item{Key: key}, err: c.keyErr(req, resp.Error)}
+						case resp.IsMiss():
+							r = result{item: Item{Key: key, Found: false}}
+						case resp.IsSuccess():
+							r = result{item: Item{Key: key, Value: resp.Data, Found: true}}
+						default:
+							r = result{item: Item{Key: key}, err: fmt.Errorf("unexpected response status for key %s: %s", key, resp.Status)}
+						}
+						if !send(r) {
+							return
+						}
+					}
+				}
+			}()
+		}
+
+		go func() {
+			wg.Wait()
+			close(results)
+		}()
+
+		for r := range results {
+			if !yield(r.item, r.err) {
+				cancel()
+				for range results {
+					// Drain until the server batches have stopped
+				}
+				return
+			}
+		}
+	}
+}
+
 // Close closes the client and destroys all connections in all pools.
 // It is safe to call multiple times. Operations issued after Close fail.
 func (c *Client) Close() {
@@ -284,6 +649,49 @@ func (c *Client) Close() {
 	})
 }
 
+// wireKey returns the key sent to the servers for key (see
+// Config.KeyTransformer).
+func (c *Client) wireKey(key string) string {
+	if c.config.KeyTransformer == nil {
+		return key
+	}
+	return c.config.KeyTransformer(key)
+}
+
+// transformKey returns req with the key sent to the servers (see
+// Config.KeyTransformer), as a copy: req itself is left unchanged. Requests
+// without a key (mn, stats and admin commands) are returned as is.
+func (c *Client) transformKey(req *meta.Request) *meta.Request {
+	if c.config.KeyTransformer == nil {
+		return req
+	}
+	switch req.Command {
+	case meta.CmdGet, meta.CmdSet, meta.CmdDelete, meta.CmdArithmetic, meta.CmdDebug:
+		transformed := *req
+		transformed.Key = c.config.KeyTransformer(req.Key)
+		return &transformed
+	default:
+		return req
+	}
+}
+
+// pipelineDepth returns the number of requests to pipeline at once out of a
+// server batch of n requests.
+func (c *Client) pipelineDepth(n int) int {
+	if c.config.MaxPipelineDepth > 0 {
+		return c.config.MaxPipelineDepth
+	}
+	return n
+}
+
+// maxKeyLength returns the effective key length limit.
+func (c *Client) maxKeyLength() int {
+	if c.config.MaxKeyLength > 0 {
+		return c.config.MaxKeyLength
+	}
+	return meta.MaxKeyLength
+}
+
 // selectServerForKey picks the server address for a given key.
 // Uses the configured SelectServer function with the current server list.
 func (c *Client) selectServerForKey(key string) (string, error) {
@@ -428,6 +836,22 @@ func (c *Client) PoolMetrics() []PoolMetrics {
 	return metrics
 }
 
+// LatencySnapshot returns the latency percentiles of the operations executed
+// so far, across all servers. It is zero unless Config.LatencyHistogram is set.
+func (c *Client) LatencySnapshot() LatencySnapshot {
+	c.mu.RLock()
+	defer c.mu.RUnlock()
+
+	var counts [latencyBuckets]uint64
+	var maxLatency time.Duration
+	for _, sp := range c.pools {
+		if sp.latency != nil {
+			maxLatency = max(maxLatency, sp.latency.addTo(&counts))
+		}
+	}
+	return latencySnapshot(&counts, maxLatency)
+}
+
 // ServerStats contains statistics from a single memcache server.
 type ServerStats struct {
 	Addr  string            // Server address
@@ -463,32 +887,223 @@ func (c *Client) Stats(ctx context.Context, args ...string) ([]ServerStats, erro
 				return
 			}
 
-			// Acquire connection
-			res, err := sp.pool.Acquire(ctx)
+			results[idx].Error = sp.withConn(ctx, OpStats, func(conn *Connection) error {
+				stats, err := conn.ExecuteStats(ctx, args...)
+				if err != nil {
+					return err
+				}
+				results[idx].Stats = stats
+				return nil
+			})
+		}(i, addr)
+	}
+
+	wg.Wait()
+	return results, nil
+}
+
+// SetMemoryLimit changes the memory limit of every server, in megabytes, with
+// the cache_memlimit admin command. Servers are updated concurrently; the
+// errors of the servers that failed are joined in the returned error.
+func (c *Client) SetMemoryLimit(ctx context.Context, megabytes int) error {
+	return c.adminAll(ctx, meta.CmdCacheMemlimit, func(conn *Connection) error {
+		return conn.SetMemoryLimit(ctx, megabytes)
+	})
+}
+
+// SetVerbosity changes the logging level of every server with the verbosity
+// admin command, e.g. to raise it temporarily while debugging an incident.
+// Servers are updated concurrently; the errors of the servers that failed are
+// joined in the returned error.
+func (c *Client) SetVerbosity(ctx context.Context, level int) error {
+	return c.adminAll(ctx, meta.CmdVerbosity, func(conn *Connection) error {
+		return conn.SetVerbosity(ctx, level)
+	})
+}
+
+// adminAll runs the admin command cmd with fn on every server concurrently,
+// joining the errors of the servers that failed.
+func (c *Client) adminAll(ctx context.Context, cmd meta.CmdType, fn func(conn *Connection) error) error {
+	servers := c.servers.List()
+	if len(servers) == 0 {
+		return ErrNoServers
+	}
+
+	errs := make([]error, len(servers))
+	var wg sync.WaitGroup
+	wg.Add(len(servers))
+
+	for i, addr := range servers {
+		go func(idx int, serverAddr string) {
+			defer wg.Done()
+
+			sp, err := c.getPoolForServer(serverAddr)
+			if err != nil {
+				errs[idx] = err
+				return
+			}
+
+			errs[idx] = sp.withConn(ctx, string(cmd), fn)
+		}(i, addr)
+	}
+
+	wg.Wait()
+	return errors.Join(errs...)
+}
+
+// Cachedump lists up to limit items of a slab class (zero limit means all of
+// them) on the server at addr (one of the addresses of the client's Servers),
+// with the "stats cachedump" command. Meant for debugging: the command is
+// deprecated upstream, caps its output at 2MB and may not list every item;
+// prefer Metadump to walk the whole cache.
+func (c *Client) Cachedump(ctx context.Context, addr string, slabClass, limit int) ([]meta.CachedumpEntry, error) {
+	sp, err := c.getPoolForServer(addr)
+	if err != nil {
+		return nil, err
+	}
+
+	var entries []meta.CachedumpEntry
+	err = sp.withConn(ctx, OpStats, func(conn *Connection) error {
+		dump, err := conn.Cachedump(ctx, slabClass, limit)
+		entries = dump
+		return err
+	})
+	return entries, err
+}
+
+// Version returns the version of the server at addr (one of the addresses of
+// the client's Servers), e.g. to check that it supports a feature:
+//
+//	v, err := client.Version(ctx, addr)
+//	if err == nil && v.AtLeast(1, 6, 0) { ... }
+func (c *Client) Version(ctx context.Context, addr string) (meta.Version, error) {
+	sp, err := c.getPoolForServer(addr)
+	if err != nil {
+		return meta.Version{}, err
+	}
+
+	var version meta.Version
+	err = sp.withConn(ctx, string(meta.CmdVersion), func(conn *Connection) error {
+		v, err := conn.Version(ctx)
+		version = v
+		return err
+	})
+	return version, err
+}
+
+// StatsSeq streams the stats of the server at addr (one of the addresses of
+// the client's Servers), yielding each stat as it is read instead of
+// building a map like Stats: for large outputs, e.g. "stats slabs" on a big
+// server, of which only a few stats are needed. args are passed to the stats
+// command as in Stats.
+//
+// An error ends the iteration. Stopping the iteration early closes the
+// connection, as the rest of the response is still in flight.
+func (c *Client) StatsSeq(ctx context.Context, addr string, args ...string) iter.Seq2[meta.Stat, error] {
+	return func(yield func(meta.Stat, error) bool) {
+		sp, err := c.getPoolForServer(addr)
+		if err != nil {
+			yield(meta.Stat{}, err)
+			return
+		}
+
+		resource, err := sp.acquire(ctx)
+		if err != nil {
+			yield(meta.Stat{}, sp.wrapErr(string(meta.CmdStats), "", err))
+			return
+		}
+
+		for stat, err := range resource.Value().StatsSeq(ctx, args...) {
 			if err != nil {
-				results[idx].Error = sp.wrapErr(OpStats, "", err)
+				if meta.ShouldCloseConnection(err) {
+					sp.destroy(resource)
+				} else {
+					sp.release(resource)
+				}
+				yield(meta.Stat{}, sp.wrapErr(string(meta.CmdStats), "", err))
 				return
 			}
+			if !yield(stat, nil) {
+				sp.destroy(resource)
+				return
+			}
+		}
 
-			conn := res.Value()
+		sp.release(resource)
+	}
+}
+
+// Metadump streams the metadata of every item stored on the server at addr
+// (one of the addresses of the client's Servers), with the
+// "lru_crawler metadump all" admin command. Meant for cache-analysis tooling:
+// the dump walks the whole cache and can be large.
+//
+// An error ends the iteration. Stopping the iteration early closes the
+// connection, as the rest of the dump is still in flight.
+func (c *Client) Metadump(ctx context.Context, addr string) iter.Seq2[meta.MetadumpEntry, error] {
+	return func(yield func(meta.MetadumpEntry, error) bool) {
+		sp, err := c.getPoolForServer(addr)
+		if err != nil {
+			yield(meta.MetadumpEntry{}, err)
+			return
+		}
+
+		resource, err := sp.acquire(ctx)
+		if err != nil {
+			yield(meta.MetadumpEntry{}, sp.wrapErr(string(meta.CmdLRUCrawler), "", err))
+			return
+		}
 
-			// Execute stats command
-			stats, err := conn.ExecuteStats(ctx, args...)
+		for entry, err := range resource.Value().Metadump(ctx) {
 			if err != nil {
 				if meta.ShouldCloseConnection(err) {
-					res.Destroy()
+					sp.destroy(resource)
 				} else {
-					sp.release(res)
+					sp.release(resource)
 				}
-				results[idx].Error = sp.wrapErr(OpStats, "", err)
+				yield(meta.MetadumpEntry{}, sp.wrapErr(string(meta.CmdLRUCrawler), "", err))
 				return
 			}
+			if !yield(entry, nil) {
+				sp.destroy(resource)
+				return
+			}
+		}
 
-			results[idx].Stats = stats
-			sp.release(res)
-		}(i, addr)
+		sp.release(resource)
 	}
+}
 
-	wg.Wait()
-	return results, nil
+// Watch streams the log events of the server at addr (one of the addresses of
+// the client's Servers), for the given event classes, with the "watch"
+// command (see Connection.Watch). Meant for debugging tools observing the
+// traffic of a server.
+//
+// The stream takes a connection of the pool for itself: it is closed when the
+// iteration ends, rather than returned to the pool.
+func (c *Client) Watch(ctx context.Context, addr string, events ...string) iter.Seq2[meta.WatchEvent, error] {
+	return func(yield func(meta.WatchEvent, error) bool) {
+		sp, err := c.getPoolForServer(addr)
+		if err != nil {
+			yield(meta.WatchEvent{}, err)
+			return
+		}
+
+		resource, err := sp.acquire(ctx)
+		if err != nil {
+			yield(meta.WatchEvent{}, sp.wrapErr(string(meta.CmdWatch), "", err))
+			return
+		}
+		defer sp.destroy(resource)
+
+		for event, err := range resource.Value().Watch(ctx, events...) {
+			if err != nil {
+				yield(meta.WatchEvent{}, sp.wrapErr(string(meta.CmdWatch), "", err))
+				return
+			}
+			if !yield(event, nil) {
+				return
+			}
+		}
+	}
 }
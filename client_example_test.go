@@ -10,6 +10,7 @@ import (
 	"time"
 
 	"github.com/pior/memcache"
+	"github.com/pior/memcache/meta"
 	"github.com/sony/gobreaker/v2"
 )
 
@@ -103,3 +104,57 @@ func ExampleNewClient_tls() {
 
 	_ = client.Set(context.Background(), memcache.Item{Key: "user:123", Value: []byte("John")})
 }
+
+// ValueFormat is an application-defined bitfield stored in the client flags.
+type ValueFormat uint32
+
+const (
+	FormatGzip ValueFormat = 1 << iota
+	FormatJSON
+)
+
+// Example storing and reading client flags as an application-defined type
+func ExampleGetBuilder_ReturnFlags() {
+	client := memcache.NewClient(memcache.StaticServers("localhost:11211"), memcache.Config{})
+	defer client.Close()
+
+	ctx := context.Background()
+
+	err := client.NewSet("user:42").
+		Value([]byte(`{"name":"Ada"}`)).
+		Flags(uint32(FormatJSON)).
+		Do(ctx)
+	if err != nil {
+		fmt.Printf("Set failed: %v\n", err)
+		return
+	}
+
+	item, err := client.NewGet("user:42").ReturnFlags().Do(ctx)
+	if err != nil {
+		fmt.Printf("Get failed: %v\n", err)
+		return
+	}
+
+	format := ValueFormat(item.Flags)
+	fmt.Println("gzip:", format&FormatGzip != 0, "json:", format&FormatJSON != 0)
+}
+
+// Example reading the exact response status of an operation
+func ExampleClient_Execute() {
+	client := memcache.NewClient(memcache.StaticServers("localhost:11211"), memcache.Config{})
+	defer client.Close()
+
+	resp, err := client.Execute(context.Background(), client.NewGet("user:42").ReturnCAS().Request())
+	if err != nil {
+		fmt.Printf("Get failed: %v\n", err)
+		return
+	}
+
+	switch resp.Status {
+	case meta.StatusVA:
+		cas, _ := resp.CAS()
+		fmt.Printf("hit: %d bytes, cas %d\n", len(resp.Data), cas)
+	case meta.StatusEN:
+		fmt.Println("miss")
+	}
+}
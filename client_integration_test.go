@@ -426,7 +426,8 @@ func TestIntegration_ErrorCases(t *testing.T) {
 
 		// Try to increment - memcache should return CLIENT_ERROR
 		_, err = client.Increment(ctx, key, 1, NoTTL)
-		assert.EqualError(t, err, "CLIENT_ERROR: cannot increment or decrement non-numeric value")
+		assert.ErrorIs(t, err, ErrNonNumericValue)
+		assert.EqualError(t, err, "memcache: value is not a number: CLIENT_ERROR: cannot increment or decrement non-numeric value")
 	})
 }
 
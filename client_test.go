@@ -4,21 +4,31 @@ import (
 	"bytes"
 	"context"
 	"net"
+	"strconv"
 	"strings"
+	"sync/atomic"
 	"testing"
 	"time"
 
 	"github.com/pior/memcache/internal/testutils"
+	"github.com/pior/memcache/meta"
 	"github.com/stretchr/testify/assert"
 	"github.com/stretchr/testify/require"
 )
 
 // newTestClient creates a test client with a mock connection
 func newTestClient(t testing.TB, mockConn *testutils.ConnectionMock) *Client {
-	servers := StaticServers("localhost:11211")
-	client := NewClient(servers, Config{
-		Dialer: &mockDialer{conn: mockConn},
-	})
+	return newTestClientWithConfig(t, mockConn, Config{})
+}
+
+// newTestClientWithConfig creates a test client with a mock connection and
+// the given config. The mock connection is only dialed when config has no
+// Dialer of its own.
+func newTestClientWithConfig(t testing.TB, mockConn *testutils.ConnectionMock, config Config) *Client {
+	if config.Dialer == nil {
+		config.Dialer = &mockDialer{conn: mockConn}
+	}
+	client := NewClient(StaticServers("localhost:11211"), config)
 	t.Cleanup(func() {
 		client.Close()
 	})
@@ -28,9 +38,11 @@ func newTestClient(t testing.TB, mockConn *testutils.ConnectionMock) *Client {
 type mockDialer struct {
 	conn  net.Conn
 	error error
+	dials atomic.Int32
 }
 
 func (d *mockDialer) DialContext(ctx context.Context, network, address string) (net.Conn, error) {
+	d.dials.Add(1)
 	return d.conn, d.error
 }
 
@@ -47,6 +59,12 @@ func assertRequest(t *testing.T, mockConn *testutils.ConnectionMock, expected st
 // Get Tests
 // =============================================================================
 
+func TestItem_CASMatches(t *testing.T) {
+	assert.True(t, Item{CAS: 42}.CASMatches(42))
+	assert.False(t, Item{CAS: 42}.CASMatches(43))
+	assert.False(t, Item{}.CASMatches(0), "no CAS returned never matches")
+}
+
 func TestClient_Get_Success(t *testing.T) {
 	mockConn := testutils.NewConnectionMock("VA 5\r\nhello\r\n")
 	client := newTestClient(t, mockConn)
@@ -378,6 +396,23 @@ func TestClient_Increment_PositiveDelta_FirstCall(t *testing.T) {
 	assertRequest(t, mockConn, "ma key v D5 J5 N0\r\n")
 }
 
+// On creation, the server returns the initial value (J flag) without applying
+// the delta: J is the delta so that the first call returns the same value as
+// an increment of a zero counter would.
+func TestClient_Increment_PositiveDelta_SecondCall(t *testing.T) {
+	mockConn := testutils.NewConnectionMock("VA 1\r\n5\r\n", "VA 2\r\n10\r\n")
+	client := newTestClient(t, mockConn)
+
+	value, err := client.Increment(context.Background(), "key", 5, NoTTL)
+	require.NoError(t, err)
+	assert.Equal(t, int64(5), value)
+
+	value, err = client.Increment(context.Background(), "key", 5, NoTTL)
+	require.NoError(t, err)
+	assert.Equal(t, int64(10), value)
+	assertRequest(t, mockConn, "ma key v D5 J5 N0\r\nma key v D5 J5 N0\r\n")
+}
+
 func TestClient_Increment_PositiveDelta_WithTTL(t *testing.T) {
 	mockConn := testutils.NewConnectionMock("VA 1\r\n1\r\n")
 	client := newTestClient(t, mockConn)
@@ -389,6 +424,31 @@ func TestClient_Increment_PositiveDelta_WithTTL(t *testing.T) {
 	assertRequest(t, mockConn, "ma key v D1 J1 N60 T60\r\n")
 }
 
+// The delta is always sent explicitly, including the server's default of 1:
+// the wire format must not depend on which delta happens to be the default.
+func TestClient_Increment_DeltaAlwaysExplicit(t *testing.T) {
+	tests := []struct {
+		delta int64
+		want  string
+	}{
+		{delta: 1, want: "ma key v D1 J1 N0\r\n"},
+		{delta: 5, want: "ma key v D5 J5 N0\r\n"},
+		{delta: -1, want: "ma key v D1 MD J0 N0\r\n"},
+	}
+
+	for _, tt := range tests {
+		t.Run(strconv.FormatInt(tt.delta, 10), func(t *testing.T) {
+			mockConn := testutils.NewConnectionMock("VA 1\r\n1\r\n")
+			client := newTestClient(t, mockConn)
+
+			_, err := client.Increment(context.Background(), "key", tt.delta, NoTTL)
+
+			require.NoError(t, err)
+			assertRequest(t, mockConn, tt.want)
+		})
+	}
+}
+
 func TestClient_Increment_ZeroDelta(t *testing.T) {
 	mockConn := testutils.NewConnectionMock("VA 2\r\n42\r\n")
 	client := newTestClient(t, mockConn)
@@ -488,6 +548,16 @@ func TestClient_Increment_InvalidValueFormat(t *testing.T) {
 	assert.Contains(t, err.Error(), "failed to parse increment result")
 }
 
+func TestClient_Increment_ValueOverflowsInt64(t *testing.T) {
+	mockConn := testutils.NewConnectionMock("VA 20\r\n18446744073709551615\r\n")
+	client := newTestClient(t, mockConn)
+
+	_, err := client.Increment(context.Background(), "key", 1, NoTTL)
+
+	require.Error(t, err)
+	assert.Contains(t, err.Error(), "overflows int64")
+}
+
 func TestClient_Increment_ServerError(t *testing.T) {
 	mockConn := testutils.NewConnectionMock("SERVER_ERROR out of memory\r\n")
 	client := newTestClient(t, mockConn)
@@ -504,7 +574,9 @@ func TestClient_Increment_ClientError_NonNumeric(t *testing.T) {
 
 	_, err := client.Increment(context.Background(), "key", 1, NoTTL)
 
-	require.Error(t, err)
+	require.ErrorIs(t, err, ErrNonNumericValue)
+	var clientErr *meta.ClientError
+	assert.ErrorAs(t, err, &clientErr)
 	assert.Contains(t, err.Error(), "CLIENT_ERROR")
 }
 
@@ -648,3 +720,486 @@ func TestClient_MultiPool_CustomSelectServer(t *testing.T) {
 	assert.Len(t, allPoolMetrics, 1, "Should have only one pool since all keys go to first server")
 	assert.Equal(t, "server1:11211", allPoolMetrics[0].Addr)
 }
+
+func TestClient_SetMemoryLimit(t *testing.T) {
+	t.Run("success", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("OK\r\n")
+		client := newTestClient(t, mockConn)
+
+		err := client.SetMemoryLimit(context.Background(), 1024)
+		require.NoError(t, err)
+		assertRequest(t, mockConn, "cache_memlimit 1024\r\n")
+	})
+
+	t.Run("server error", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("CLIENT_ERROR bad command line format\r\n")
+		client := newTestClient(t, mockConn)
+
+		err := client.SetMemoryLimit(context.Background(), -1)
+
+		var opErr *OpError
+		require.ErrorAs(t, err, &opErr)
+		assert.Equal(t, "cache_memlimit", opErr.Op)
+		assert.Equal(t, "localhost:11211", opErr.Server)
+
+		var clientErr *meta.ClientError
+		require.ErrorAs(t, err, &clientErr)
+	})
+}
+
+func TestClient_SetVerbosity(t *testing.T) {
+	t.Run("success", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("OK\r\n")
+		client := newTestClient(t, mockConn)
+
+		err := client.SetVerbosity(context.Background(), 1)
+		require.NoError(t, err)
+		assertRequest(t, mockConn, "verbosity 1\r\n")
+	})
+
+	t.Run("server error", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("ERROR\r\n")
+		client := newTestClient(t, mockConn)
+
+		err := client.SetVerbosity(context.Background(), 1)
+
+		var opErr *OpError
+		require.ErrorAs(t, err, &opErr)
+		assert.Equal(t, "verbosity", opErr.Op)
+	})
+}
+
+func TestClient_Watch(t *testing.T) {
+	mockConn := testutils.NewConnectionMock(
+		"OK\r\n",
+		"ts=1700000000.000001 gid=1 type=item_get key=foo status=found\r\n",
+	)
+	client := newTestClient(t, mockConn)
+
+	for event, err := range client.Watch(context.Background(), "localhost:11211", "fetchers") {
+		require.NoError(t, err)
+		assert.Equal(t, "foo", event.Fields["key"])
+		break
+	}
+
+	assertRequest(t, mockConn, "watch fetchers\r\n")
+	assert.Equal(t, int32(0), client.PoolMetrics()[0].Conns.IdleConns, "connection must not be reused")
+	assert.Equal(t, int64(1), client.pools["localhost:11211"].lostConns.Load(), "the next connection must be reported as a reconnect")
+}
+
+func TestClient_Metadump(t *testing.T) {
+	t.Run("all entries", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock(
+			"key=k1 exp=-1 la=1700000000 cas=1 fetch=no cls=1 size=60\r\n",
+			"key=k2 exp=1700003600 la=1700000001 cas=2 fetch=yes cls=1 size=61\r\n",
+			"END\r\n",
+		)
+		client := newTestClient(t, mockConn)
+
+		var keys []string
+		for entry, err := range client.Metadump(context.Background(), "localhost:11211") {
+			require.NoError(t, err)
+			keys = append(keys, entry.Key)
+		}
+
+		assert.Equal(t, []string{"k1", "k2"}, keys)
+		assertRequest(t, mockConn, "lru_crawler metadump all\r\n")
+		assert.Equal(t, int32(1), client.PoolMetrics()[0].Conns.IdleConns, "connection must be reused")
+	})
+
+	t.Run("busy crawler", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("BUSY currently processing crawler request\r\n")
+		client := newTestClient(t, mockConn)
+
+		var errs []error
+		for _, err := range client.Metadump(context.Background(), "localhost:11211") {
+			errs = append(errs, err)
+		}
+
+		require.Len(t, errs, 1)
+		var adminErr *meta.AdminError
+		require.ErrorAs(t, errs[0], &adminErr)
+		assert.True(t, adminErr.Busy())
+	})
+
+	t.Run("early break closes the connection", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock(
+			"key=k1 exp=-1 la=1700000000 cas=1 fetch=no cls=1 size=60\r\n",
+			"key=k2 exp=-1 la=1700000000 cas=2 fetch=no cls=1 size=60\r\n",
+			"END\r\n",
+		)
+		client := newTestClient(t, mockConn)
+
+		for range client.Metadump(context.Background(), "localhost:11211") {
+			break
+		}
+		assert.Equal(t, int64(1), client.pools["localhost:11211"].lostConns.Load(), "the next connection must be reported as a reconnect")
+
+		// The pool destroys resources asynchronously: poll the counter.
+		assert.Eventually(t, func() bool {
+			return client.PoolMetrics()[0].Conns.DestroyedConns == 1
+		}, 2*time.Second, 10*time.Millisecond, "the rest of the dump is unread: the connection must be destroyed")
+	})
+}
+
+func TestClient_StatsSeq(t *testing.T) {
+	t.Run("all stats", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("STAT 1:chunk_size 96\r\nSTAT active_slabs 1\r\nEND\r\n")
+		client := newTestClient(t, mockConn)
+
+		var stats []meta.Stat
+		for stat, err := range client.StatsSeq(context.Background(), "localhost:11211", "slabs") {
+			require.NoError(t, err)
+			stats = append(stats, stat)
+		}
+
+		assert.Equal(t, []meta.Stat{{Name: "1:chunk_size", Value: "96"}, {Name: "active_slabs", Value: "1"}}, stats)
+		assertRequest(t, mockConn, "stats slabs\r\n")
+		assert.Equal(t, int32(1), client.PoolMetrics()[0].Conns.IdleConns, "connection must be reused")
+	})
+
+	t.Run("early break closes the connection", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("STAT pid 1\r\nSTAT uptime 2\r\nEND\r\n")
+		client := newTestClient(t, mockConn)
+
+		for range client.StatsSeq(context.Background(), "localhost:11211") {
+			break
+		}
+		assert.Equal(t, int64(1), client.pools["localhost:11211"].lostConns.Load(), "the next connection must be reported as a reconnect")
+
+		// The pool destroys resources asynchronously: poll the counter.
+		assert.Eventually(t, func() bool {
+			return client.PoolMetrics()[0].Conns.DestroyedConns == 1
+		}, 2*time.Second, 10*time.Millisecond, "the rest of the stats is unread: the connection must be destroyed")
+	})
+}
+
+func TestClient_Cachedump(t *testing.T) {
+	mockConn := testutils.NewConnectionMock(
+		"ITEM k1 [60 b; 0 s]\r\n",
+		"ITEM k2 [61 b; 1700003600 s]\r\n",
+		"END\r\n",
+	)
+	client := newTestClient(t, mockConn)
+
+	entries, err := client.Cachedump(context.Background(), "localhost:11211", 1, 100)
+	require.NoError(t, err)
+
+	assert.Equal(t, []meta.CachedumpEntry{
+		{Key: "k1", Size: 60},
+		{Key: "k2", Size: 61, Expiration: 1700003600},
+	}, entries)
+	assertRequest(t, mockConn, "stats cachedump 1 100\r\n")
+}
+
+func TestClient_Execute_MetadataOnlyGet(t *testing.T) {
+	t.Run("hit", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("HD t42\r\n")
+		client := newTestClient(t, mockConn)
+
+		resp, err := client.Execute(context.Background(), meta.NewRequest(meta.CmdGet, "key", nil).AddReturnTTL())
+		require.NoError(t, err)
+
+		assert.True(t, resp.IsSuccess(), "HD is a hit for a get without a value")
+		assert.False(t, resp.IsMiss())
+		assert.Empty(t, resp.Data)
+		ttl, ok := resp.TTL()
+		assert.True(t, ok)
+		assert.Equal(t, 42, ttl)
+		assertRequest(t, mockConn, "mg key t\r\n")
+	})
+
+	t.Run("miss", func(t *testing.T) {
+		client := newTestClient(t, testutils.NewConnectionMock("EN\r\n"))
+
+		resp, err := client.Execute(context.Background(), meta.NewRequest(meta.CmdGet, "key", nil).AddReturnTTL())
+		require.NoError(t, err)
+
+		assert.False(t, resp.IsSuccess())
+		assert.True(t, resp.IsMiss())
+	})
+}
+
+func TestClient_Version(t *testing.T) {
+	mockConn := testutils.NewConnectionMock("VERSION 1.6.21-1\r\n")
+	client := newTestClient(t, mockConn)
+
+	version, err := client.Version(context.Background(), "localhost:11211")
+	require.NoError(t, err)
+
+	assert.Equal(t, meta.Version{Major: 1, Minor: 6, Patch: 21, Suffix: "-1"}, version)
+	assert.True(t, version.AtLeast(1, 6, 0))
+	assertRequest(t, mockConn, "version\r\n")
+}
+
+func TestClient_KeyTransformer(t *testing.T) {
+	newClient := func(t *testing.T, mockConn *testutils.ConnectionMock) *Client {
+		return newTestClientWithConfig(t, mockConn, Config{
+			KeyTransformer: func(key string) string {
+				if len(key) > 20 {
+					key = "h:" + strconv.Itoa(len(key)) // stands for a hash
+				}
+				return "app:" + key
+			},
+		})
+	}
+
+	t.Run("single request", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("VA 1\r\nv\r\n")
+		client := newClient(t, mockConn)
+
+		item, err := client.Get(context.Background(), "key")
+
+		require.NoError(t, err)
+		assert.Equal(t, "key", item.Key)
+		assertRequest(t, mockConn, "mg app:key v\r\n")
+	})
+
+	t.Run("runs before key validation", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("HD\r\n")
+		client := newClient(t, mockConn)
+
+		require.NoError(t, client.Set(context.Background(), Item{Key: strings.Repeat("k", 300), Value: []byte("v")}))
+		assertRequest(t, mockConn, "ms app:h:300 1\r\nv\r\n")
+	})
+
+	t.Run("batch", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("HD\r\n", "HD\r\n", "MN\r\n")
+		client := newClient(t, mockConn)
+
+		req := meta.NewRequest(meta.CmdDelete, "k1", nil)
+		_, err := client.ExecuteBatch(context.Background(), []*meta.Request{req, meta.NewRequest(meta.CmdDelete, "k2", nil)})
+
+		require.NoError(t, err)
+		assertRequest(t, mockConn, "md app:k1\r\nmd app:k2\r\nmn\r\n")
+		assert.Equal(t, "k1", req.Key, "the request must not be modified")
+	})
+
+	t.Run("MultiGetSeq", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("VA 2\r\nv1\r\n", "MN\r\n")
+		client := newClient(t, mockConn)
+
+		for item, err := range client.MultiGetSeq(context.Background(), []string{"k1"}) {
+			require.NoError(t, err)
+			assert.Equal(t, "k1", item.Key)
+		}
+		assertRequest(t, mockConn, "mg app:k1 v\r\nmn\r\n")
+	})
+}
+
+func TestClient_MultiGetSeq(t *testing.T) {
+	t.Run("hits and misses", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("VA 2\r\nv1\r\n", "EN\r\n", "MN\r\n")
+		client := newTestClient(t, mockConn)
+
+		var items []Item
+		for item, err := range client.MultiGetSeq(context.Background(), []string{"k1", "k2"}) {
+			require.NoError(t, err)
+			items = append(items, item)
+		}
+
+		assert.Equal(t, []Item{
+			{Key: "k1", Value: []byte("v1"), Found: true},
+			{Key: "k2", Found: false},
+		}, items)
+		assertRequest(t, mockConn, "mg k1 v\r\nmg k2 v\r\nmn\r\n")
+		assert.Equal(t, int32(1), client.PoolMetrics()[0].Conns.IdleConns, "connection must be reused")
+	})
+
+	t.Run("protocol error for one key", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("SERVER_ERROR out of memory\r\n", "VA 2\r\nv2\r\n", "MN\r\n")
+		client := newTestClient(t, mockConn)
+
+		var keys []string
+		var errs []error
+		for item, err := range client.MultiGetSeq(context.Background(), []string{"k1", "k2"}) {
+			keys = append(keys, item.Key)
+			errs = append(errs, err)
+		}
+
+		assert.Equal(t, []string{"k1", "k2"}, keys)
+		var serverErr *meta.ServerError
+		assert.ErrorAs(t, errs[0], &serverErr)
+		assert.NoError(t, errs[1])
+	})
+
+	t.Run("early break closes the connection", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("VA 2\r\nv1\r\n", "VA 2\r\nv2\r\n", "MN\r\n")
+		client := newTestClient(t, mockConn)
+
+		for range client.MultiGetSeq(context.Background(), []string{"k1", "k2"}) {
+			break
+		}
+
+		// The pool destroys resources asynchronously: poll the counter.
+		assert.Eventually(t, func() bool {
+			return client.PoolMetrics()[0].Conns.DestroyedConns == 1
+		}, 2*time.Second, 10*time.Millisecond, "the rest of the batch is unread: the connection must be destroyed")
+	})
+}
+
+func TestClient_MaxPipelineDepth(t *testing.T) {
+	newClient := func(t *testing.T, responses ...string) (*Client, *testutils.ConnectionMock) {
+		mockConn := testutils.NewConnectionMock(responses...)
+		return newTestClientWithConfig(t, mockConn, Config{MaxPipelineDepth: 2}), mockConn
+	}
+
+	t.Run("batch", func(t *testing.T) {
+		client, mockConn := newClient(t, "VA 2\r\nv1\r\n", "EN\r\n", "MN\r\n", "VA 2\r\nv3\r\n", "MN\r\n")
+
+		items, err := NewBatchCommands(client).MultiGet(context.Background(), []string{"k1", "k2", "k3"})
+		require.NoError(t, err)
+
+		assert.Equal(t, []Item{
+			{Key: "k1", Value: []byte("v1"), Found: true},
+			{Key: "k2", Found: false},
+			{Key: "k3", Value: []byte("v3"), Found: true},
+		}, items)
+		assertRequest(t, mockConn, "mg k1 v\r\nmg k2 v\r\nmn\r\nmg k3 v\r\nmn\r\n")
+	})
+
+	t.Run("sequence", func(t *testing.T) {
+		client, mockConn := newClient(t, "VA 2\r\nv1\r\n", "EN\r\n", "MN\r\n", "VA 2\r\nv3\r\n", "MN\r\n")
+
+		var keys []string
+		for item, err := range client.MultiGetSeq(context.Background(), []string{"k1", "k2", "k3"}) {
+			require.NoError(t, err)
+			keys = append(keys, item.Key)
+		}
+
+		assert.Equal(t, []string{"k1", "k2", "k3"}, keys)
+		assertRequest(t, mockConn, "mg k1 v\r\nmg k2 v\r\nmn\r\nmg k3 v\r\nmn\r\n")
+	})
+}
+
+func TestClient_KeyInErrors(t *testing.T) {
+	newClient := func(t *testing.T, keyInErrors bool, responses ...string) *Client {
+		return newTestClientWithConfig(t, testutils.NewConnectionMock(responses...), Config{KeyInErrors: keyInErrors})
+	}
+
+	t.Run("status failure", func(t *testing.T) {
+		client := newClient(t, true, "NS\r\n")
+
+		err := client.Add(context.Background(), Item{Key: "foo", Value: []byte("v")})
+
+		require.ErrorIs(t, err, ErrNotStored)
+		var opErr *OpError
+		require.ErrorAs(t, err, &opErr)
+		assert.Equal(t, "foo", opErr.Key)
+		assert.Equal(t, `memcache: ms key "foo": memcache: item not stored: key already exists`, err.Error())
+	})
+
+	t.Run("protocol error", func(t *testing.T) {
+		client := newClient(t, true, "SERVER_ERROR out of memory\r\n")
+
+		err := client.NewSet("foo").Value([]byte("v")).Do(context.Background())
+
+		var serverErr *meta.ServerError
+		require.ErrorAs(t, err, &serverErr)
+		assert.Contains(t, err.Error(), `key "foo"`)
+	})
+
+	t.Run("connection error", func(t *testing.T) {
+		client := newClient(t, true) // no response: EOF
+
+		_, err := client.Get(context.Background(), "foo")
+
+		assert.ErrorContains(t, err, `memcache: mg key "foo" on localhost:11211: `)
+	})
+
+	t.Run("disabled by default", func(t *testing.T) {
+		client := newClient(t, false, "NS\r\n")
+
+		err := client.Add(context.Background(), Item{Key: "foo", Value: []byte("v")})
+
+		require.ErrorIs(t, err, ErrNotStored)
+		assert.NotContains(t, err.Error(), "foo")
+	})
+}
+
+func TestClient_LocalAddr(t *testing.T) {
+	listener, err := net.Listen("tcp", "127.0.0.1:0")
+	require.NoError(t, err)
+	t.Cleanup(func() { listener.Close() })
+
+	remoteAddrs := make(chan net.Addr, 1)
+	go func() {
+		conn, err := listener.Accept()
+		if err != nil {
+			return
+		}
+		remoteAddrs <- conn.RemoteAddr()
+		conn.Write([]byte("HD\r\n"))
+		conn.Close()
+	}()
+
+	localAddr := &net.TCPAddr{IP: net.IPv4(127, 0, 0, 1)}
+	client := NewClient(StaticServers(listener.Addr().String()), Config{
+		Timeout:   time.Second,
+		LocalAddr: localAddr,
+	})
+	t.Cleanup(client.Close)
+
+	require.NoError(t, client.Set(context.Background(), Item{Key: "key", Value: []byte("v")}))
+
+	remoteAddr := (<-remoteAddrs).(*net.TCPAddr)
+	assert.True(t, remoteAddr.IP.Equal(localAddr.IP))
+	assert.Equal(t, localAddr, client.config.Dialer.(*net.Dialer).LocalAddr)
+}
+
+func TestClient_Authentication(t *testing.T) {
+	ctx := context.Background()
+
+	t.Run("new connections authenticate first", func(t *testing.T) {
+		mock := testutils.NewConnectionMock("STORED\r\n", "HD\r\n")
+		client := newTestClientWithConfig(t, mock, Config{
+			Username: "user",
+			Password: "secret",
+		})
+
+		require.NoError(t, client.Set(ctx, Item{Key: "key", Value: []byte("v")}))
+		assertRequest(t, mock, "set auth 0 0 11\r\nuser secret\r\nms key 1\r\nv\r\n")
+	})
+
+	t.Run("rejected credentials", func(t *testing.T) {
+		mock := testutils.NewConnectionMock("CLIENT_ERROR authentication failure\r\n")
+		var connectErr error
+		client := newTestClientWithConfig(t, mock, Config{
+			Username: "user",
+			Password: "wrong",
+			Hooks:    Hooks{OnConnectError: func(addr string, err error) { connectErr = err }},
+		})
+
+		err := client.Set(ctx, Item{Key: "key", Value: []byte("v")})
+		require.ErrorIs(t, err, ErrAuthFailed)
+		assert.ErrorIs(t, connectErr, ErrAuthFailed)
+		assertRequest(t, mock, "set auth 0 0 10\r\nuser wrong\r\n")
+	})
+}
+
+// recordingBufferPool counts the buffers handed out by a BufferPool.
+type recordingBufferPool struct {
+	meta.BufferPool
+	gets atomic.Int32
+}
+
+func (p *recordingBufferPool) Get(n int) []byte {
+	p.gets.Add(1)
+	return p.BufferPool.Get(n)
+}
+
+func TestClient_BufferPool(t *testing.T) {
+	pool := &recordingBufferPool{BufferPool: meta.NewBufferPool()}
+	mockConn := testutils.NewConnectionMock("VA 5\r\nhello\r\n", "VA 1\r\na\r\nEN\r\nMN\r\n")
+	client := newTestClientWithConfig(t, mockConn, Config{BufferPool: pool})
+
+	item, err := client.Get(context.Background(), "key")
+	require.NoError(t, err)
+	assert.Equal(t, "hello", string(item.Value))
+	pool.Put(item.Value)
+
+	_, err = NewBatchCommands(client).MultiGet(context.Background(), []string{"k1", "k2"})
+	require.NoError(t, err)
+
+	assert.Equal(t, int32(2), pool.gets.Load(), "one buffer per value read")
+}
@@ -2,8 +2,11 @@ package memcache
 
 import (
 	"context"
+	"errors"
 	"fmt"
-	"strconv"
+	"math"
+	"strings"
+	"time"
 
 	"github.com/pior/memcache/meta"
 )
@@ -41,6 +44,25 @@ type StatsExecutor interface {
 // or embedded in Client for full resilience features.
 type Commands struct {
 	executor Executor
+
+	// keyInErrors adds the key to the message of command failures, see
+	// Config.KeyInErrors.
+	keyInErrors bool
+
+	// defaultTTL applies to stores without a TTL, see Config.DefaultTTL.
+	defaultTTL TTL
+
+	// ttlJitter randomizes the relative TTLs of stores, drawing from
+	// jitterRand, see Config.TTLJitter.
+	ttlJitter  time.Duration
+	jitterRand func(n int64) int64
+
+	// skipOversized makes stores of values too large for the server succeed,
+	// see Config.SkipOversizedValues.
+	skipOversized bool
+
+	// flights coalesces concurrent GetOrSet misses for the same key.
+	flights flightGroup
 }
 
 var _ Querier = (*Commands)(nil)
@@ -65,11 +87,11 @@ func (c *Commands) Get(ctx context.Context, key string) (Item, error) {
 	}
 
 	if resp.HasError() {
-		return Item{}, resp.Error
+		return Item{}, c.keyErr(req, resp.Error)
 	}
 
 	if !resp.IsSuccess() {
-		return Item{}, fmt.Errorf("unexpected response status: %s", resp.Status)
+		return Item{}, c.keyErr(req, fmt.Errorf("unexpected response status: %s", resp.Status))
 	}
 
 	return Item{
@@ -84,7 +106,7 @@ func (c *Commands) Set(ctx context.Context, item Item) error {
 	req := meta.NewRequest(meta.CmdSet, item.Key, item.Value)
 
 	// Add TTL flag if specified, otherwise use no expiration
-	if exptime := item.TTL.Expiration(); exptime != 0 {
+	if exptime := c.storeTTL(item.TTL).Expiration(); exptime != 0 {
 		req.AddTTL(exptime)
 	}
 
@@ -94,11 +116,11 @@ func (c *Commands) Set(ctx context.Context, item Item) error {
 	}
 
 	if resp.HasError() {
-		return resp.Error
+		return c.storeErr(req, resp.Error)
 	}
 
 	if !resp.IsSuccess() {
-		return fmt.Errorf("set failed with status: %s", resp.Status)
+		return c.keyErr(req, statusErr(req, "set", resp.Status))
 	}
 
 	return nil
@@ -107,7 +129,7 @@ func (c *Commands) Set(ctx context.Context, item Item) error {
 // Add stores an item in memcache only if the key doesn't already exist.
 func (c *Commands) Add(ctx context.Context, item Item) error {
 	req := meta.NewRequest(meta.CmdSet, item.Key, item.Value).AddModeAdd()
-	if exptime := item.TTL.Expiration(); exptime != 0 {
+	if exptime := c.storeTTL(item.TTL).Expiration(); exptime != 0 {
 		req.AddTTL(exptime)
 	}
 
@@ -117,15 +139,15 @@ func (c *Commands) Add(ctx context.Context, item Item) error {
 	}
 
 	if resp.HasError() {
-		return resp.Error
+		return c.storeErr(req, resp.Error)
 	}
 
 	if resp.IsNotStored() {
-		return fmt.Errorf("%w: key already exists", ErrNotStored)
+		return c.keyErr(req, fmt.Errorf("%w: key already exists", ErrNotStored))
 	}
 
 	if !resp.IsSuccess() {
-		return fmt.Errorf("add failed with status: %s", resp.Status)
+		return c.keyErr(req, statusErr(req, "add", resp.Status))
 	}
 
 	return nil
@@ -140,12 +162,12 @@ func (c *Commands) Delete(ctx context.Context, key string) error {
 	}
 
 	if resp.HasError() {
-		return resp.Error
+		return c.keyErr(req, resp.Error)
 	}
 
 	// Delete is successful even if key doesn't exist
 	if resp.Status != meta.StatusHD && resp.Status != meta.StatusNF {
-		return fmt.Errorf("delete failed with status: %s", resp.Status)
+		return c.keyErr(req, statusErr(req, "delete", resp.Status))
 	}
 
 	return nil
@@ -153,9 +175,16 @@ func (c *Commands) Delete(ctx context.Context, key string) error {
 
 // Increment increments a counter key by the given delta.
 // Creates the key with the delta value if it doesn't exist.
+// The ttl applies both on creation and on every increment; use NewIncrement
+// for distinct expirations.
 // This uses auto-vivify (N flag) with initial value (J flag) set to the delta,
 // so the returned value is correct even on first call.
 // NoTTL means infinite TTL.
+//
+// The server doesn't report whether the counter was created. For a counter
+// that is only ever incremented, a result equal to delta means this call
+// created it, e.g. the first request of a rate-limiting window; otherwise use
+// IncrementBuilder.DoWithCreated.
 func (c *Commands) Increment(ctx context.Context, key string, delta int64, ttl TTL) (int64, error) {
 	req := meta.NewRequest(meta.CmdArithmetic, key, nil).AddReturnValue()
 
@@ -186,23 +215,97 @@ func (c *Commands) Increment(ctx context.Context, key string, delta int64, ttl T
 		return 0, err
 	}
 
+	return c.counterResult(req, resp)
+}
+
+// counterResult maps the response of the arithmetic request req to the
+// counter value.
+func (c *Commands) counterResult(req *meta.Request, resp *meta.Response) (int64, error) {
 	if resp.HasError() {
-		return 0, resp.Error
+		if isNonNumericValue(resp.Error) {
+			return 0, c.keyErr(req, fmt.Errorf("%w: %w", ErrNonNumericValue, resp.Error))
+		}
+		return 0, c.keyErr(req, resp.Error)
 	}
 
 	if !resp.IsSuccess() {
-		return 0, fmt.Errorf("increment failed with status: %s", resp.Status)
+		return 0, c.keyErr(req, statusErr(req, "increment", resp.Status))
 	}
 
 	// Parse the returned value
 	if !resp.HasValue() {
-		return 0, fmt.Errorf("increment response missing value")
+		return 0, c.keyErr(req, fmt.Errorf("increment response missing value"))
 	}
 
-	value, err := strconv.ParseInt(string(resp.Data), 10, 64)
+	value, err := resp.CounterValue()
 	if err != nil {
-		return 0, fmt.Errorf("failed to parse increment result: %w", err)
+		return 0, c.keyErr(req, fmt.Errorf("failed to parse increment result: %w", err))
 	}
+	if value > math.MaxInt64 {
+		return 0, c.keyErr(req, fmt.Errorf("increment result overflows int64: %d", value))
+	}
+
+	return int64(value), nil
+}
 
-	return value, nil
+// storeTTL returns the TTL to store an item with: ttl, or the default TTL
+// when unset, jittered if the client has a TTL jitter.
+func (c *Commands) storeTTL(ttl TTL) TTL {
+	ttl = ttl.orDefault(c.defaultTTL)
+	if c.ttlJitter > 0 && c.jitterRand != nil {
+		ttl = ttl.jitter(c.ttlJitter, c.jitterRand)
+	}
+	return ttl
+}
+
+// storeErr reports a failure of the store request req, unless the server
+// rejected an oversized value and skipOversized is enabled.
+func (c *Commands) storeErr(req *meta.Request, err error) error {
+	if c.skipOversized && isValueTooLarge(err) {
+		return nil
+	}
+	return c.keyErr(req, err)
+}
+
+// isValueTooLarge reports whether err is the server rejecting a value larger
+// than its item size limit ("SERVER_ERROR object too large for cache").
+func isValueTooLarge(err error) bool {
+	var serverErr *meta.ServerError
+	return errors.As(err, &serverErr) && strings.Contains(serverErr.Message, "too large")
+}
+
+// isNonNumericValue reports whether err is the server rejecting arithmetic on
+// a value that is not a number ("CLIENT_ERROR cannot increment or decrement
+// non-numeric value").
+func isNonNumericValue(err error) bool {
+	var clientErr *meta.ClientError
+	return errors.As(err, &clientErr) && strings.Contains(clientErr.Message, "non-numeric")
+}
+
+// statusErr reports a response status that the operation op doesn't expect.
+// EX, the outcome of a failed compare, is ErrCASMismatch when req carries a
+// CAS value (C flag), whatever the command, and ErrStaleWrite for a store
+// that also carries the I flag; without a CAS value, it is reported as an
+// unexpected status like any other.
+func statusErr(req *meta.Request, op string, status meta.StatusType) error {
+	if status == meta.StatusEX && req.HasFlag(meta.FlagCAS) {
+		if req.Command == meta.CmdSet && req.HasFlag(meta.FlagInvalidate) {
+			return ErrStaleWrite
+		}
+		return ErrCASMismatch
+	}
+	return fmt.Errorf("%s failed with status: %s", op, status)
+}
+
+// keyErr reports a failure of the single-key request req: with keyInErrors
+// enabled, err is wrapped in an OpError whose message includes the key.
+func (c *Commands) keyErr(req *meta.Request, err error) error {
+	if err == nil || !c.keyInErrors {
+		return err
+	}
+	var opErr *OpError
+	if errors.As(err, &opErr) {
+		return err
+	}
+	return &OpError{Op: string(req.Command), Key: req.Key, Err: err, keyInMessage: true}
 }
@@ -24,8 +24,8 @@ func TestTTL_Expiration(t *testing.T) {
 		want string
 	}{
 		{name: "NoTTL means no expiration", ttl: NoTTL, want: "0"},
-		{name: "zero duration means no expiration", ttl: ExpiresIn(0), want: "0"},
-		{name: "negative duration means no expiration", ttl: ExpiresIn(-time.Hour), want: "0"},
+		{name: "zero duration is already expired", ttl: ExpiresIn(0), want: strconv.FormatInt(minAbsoluteExptime, 10)},
+		{name: "negative duration is already expired", ttl: ExpiresIn(-time.Hour), want: strconv.FormatInt(minAbsoluteExptime, 10)},
 		{name: "sub-second rounds up to 1s", ttl: ExpiresIn(500 * time.Millisecond), want: "1"},
 		{name: "1.5s rounds up to 2s", ttl: ExpiresIn(1500 * time.Millisecond), want: "2"},
 		{name: "exact seconds unchanged", ttl: ExpiresIn(time.Hour), want: "3600"},
@@ -34,6 +34,7 @@ func TestTTL_Expiration(t *testing.T) {
 		{name: "absolute time in the past stays absolute (expired)", ttl: ExpiresAt(ref.Add(-time.Hour)), want: strconv.FormatInt(ref.Add(-time.Hour).Unix(), 10)},
 		{name: "absolute time near the epoch is clamped to the absolute range", ttl: ExpiresAt(time.Unix(60, 0)), want: strconv.FormatInt(minAbsoluteExptime, 10)},
 		{name: "zero time means no expiration", ttl: ExpiresAt(time.Time{}), want: "0"},
+		{name: "NeverExpire means no expiration", ttl: NeverExpire, want: "0"},
 	}
 
 	for _, tt := range tests {
@@ -54,6 +55,243 @@ func TestTTL_Expiration(t *testing.T) {
 	})
 }
 
+// Never expiring is T0 (or no T flag on ms, which means the same), and never
+// what a zero duration asks for.
+func TestTTL_NeverExpireOnTheWire(t *testing.T) {
+	expired := "T" + strconv.FormatInt(minAbsoluteExptime, 10)
+
+	tests := []struct {
+		name     string
+		do       func(client *Client) error
+		response string // default: HD
+		want     string
+	}{
+		{
+			name: "Set with NeverExpire",
+			do: func(client *Client) error {
+				return client.Set(context.Background(), Item{Key: "key", Value: []byte("v"), TTL: NeverExpire})
+			},
+			want: "ms key 1\r\nv\r\n",
+		},
+		{
+			name: "Set with a zero duration",
+			do: func(client *Client) error {
+				return client.Set(context.Background(), Item{Key: "key", Value: []byte("v"), TTL: ExpiresIn(0)})
+			},
+			want: "ms key 1 " + expired + "\r\nv\r\n",
+		},
+		{
+			name: "get with touch to NeverExpire",
+			do: func(client *Client) error {
+				_, err := client.NewGet("key").Touch(NeverExpire).Do(context.Background())
+				return err
+			},
+			want: "mg key v T0\r\n",
+		},
+		{
+			name: "get with touch to a zero duration",
+			do: func(client *Client) error {
+				_, err := client.NewGet("key").Touch(ExpiresIn(0)).Do(context.Background())
+				return err
+			},
+			want: "mg key v " + expired + "\r\n",
+		},
+		{
+			// A standalone touch is an mg with the T flag and no v.
+			name: "touch to NeverExpire",
+			do: func(client *Client) error {
+				_, err := client.Execute(context.Background(), meta.NewRequest(meta.CmdGet, "key", nil).AddTTL(NeverExpire.Expiration()))
+				return err
+			},
+			want: "mg key T0\r\n",
+		},
+		{
+			name: "touch to a zero duration",
+			do: func(client *Client) error {
+				_, err := client.Execute(context.Background(), meta.NewRequest(meta.CmdGet, "key", nil).AddTTL(ExpiresIn(0).Expiration()))
+				return err
+			},
+			want: "mg key " + expired + "\r\n",
+		},
+		{
+			name: "MultiTouch to NeverExpire",
+			do: func(client *Client) error {
+				_, err := NewBatchCommands(client).MultiTouch(context.Background(), []string{"key"}, NeverExpire)
+				return err
+			},
+			response: "HD\r\nMN\r\n",
+			want:     "mg key T0\r\nmn\r\n",
+		},
+		{
+			name: "MultiTouch to a zero duration",
+			do: func(client *Client) error {
+				_, err := NewBatchCommands(client).MultiTouch(context.Background(), []string{"key"}, ExpiresIn(0))
+				return err
+			},
+			response: "HD\r\nMN\r\n",
+			want:     "mg key " + expired + "\r\nmn\r\n",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			response := tt.response
+			if response == "" {
+				response = "HD\r\n"
+			}
+			mockConn := testutils.NewConnectionMock(response)
+			client := newTestClient(t, mockConn)
+
+			require.NoError(t, tt.do(client))
+			assertRequest(t, mockConn, tt.want)
+		})
+	}
+}
+
+func TestClient_DefaultTTL(t *testing.T) {
+	newClient := func(t *testing.T, mockConn *testutils.ConnectionMock) *Client {
+		return newTestClientWithConfig(t, mockConn, Config{DefaultTTL: ExpiresIn(time.Hour)})
+	}
+
+	tests := []struct {
+		name string
+		ttl  TTL
+		want string
+	}{
+		{name: "NoTTL uses the default", ttl: NoTTL, want: "ms key 1 T3600\r\nv\r\n"},
+		{name: "explicit TTL overrides the default", ttl: ExpiresIn(time.Minute), want: "ms key 1 T60\r\nv\r\n"},
+		{name: "NeverExpire overrides the default", ttl: NeverExpire, want: "ms key 1\r\nv\r\n"},
+	}
+
+	for _, tt := range tests {
+		t.Run("Set: "+tt.name, func(t *testing.T) {
+			mockConn := testutils.NewConnectionMock("HD\r\n")
+			client := newClient(t, mockConn)
+
+			require.NoError(t, client.Set(context.Background(), Item{Key: "key", Value: []byte("v"), TTL: tt.ttl}))
+			assertRequest(t, mockConn, tt.want)
+		})
+
+		t.Run("NewSet: "+tt.name, func(t *testing.T) {
+			mockConn := testutils.NewConnectionMock("HD\r\n")
+			client := newClient(t, mockConn)
+
+			require.NoError(t, client.NewSet("key").Value([]byte("v")).TTL(tt.ttl).Do(context.Background()))
+			assertRequest(t, mockConn, tt.want)
+		})
+	}
+
+	t.Run("Add uses the default", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("HD\r\n")
+		client := newClient(t, mockConn)
+
+		require.NoError(t, client.Add(context.Background(), Item{Key: "key", Value: []byte("v")}))
+		assertRequest(t, mockConn, "ms key 1 ME T3600\r\nv\r\n")
+	})
+
+	t.Run("MultiSet uses the default", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("HD\r\n", "MN\r\n")
+		client := newClient(t, mockConn)
+
+		require.NoError(t, NewBatchCommands(client).MultiSet(context.Background(), []Item{{Key: "key", Value: []byte("v")}}))
+		assertRequest(t, mockConn, "ms key 1 T3600\r\nv\r\nmn\r\n")
+	})
+
+	t.Run("MultiStore uses the default", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("HD\r\n", "MN\r\n")
+		client := newClient(t, mockConn)
+
+		_, err := NewBatchCommands(client).MultiStore(context.Background(), []Item{{Key: "key", Value: []byte("v")}}, SetModeAdd)
+		require.NoError(t, err)
+		assertRequest(t, mockConn, "ms key 1 ME T3600\r\nv\r\nmn\r\n")
+	})
+
+	t.Run("SetChunked uses the default", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("HD\r\n", "MN\r\n", "HD\r\n")
+		client := newClient(t, mockConn)
+
+		require.NoError(t, NewBatchCommands(client).SetChunked(context.Background(), Item{Key: "key", Value: []byte("v")}, 0))
+		assert.Regexp(t, `^ms key:\w+:0 1 T3600\r\nv\r\nmn\r\nms key \d+ T3600\r\n`, mockConn.GetWrittenRequest())
+	})
+}
+
+func TestClient_TTLJitter(t *testing.T) {
+	newClient := func(t *testing.T, mockConn *testutils.ConnectionMock, randN func(int64) int64) *Client {
+		return newTestClientWithConfig(t, mockConn, Config{
+			TTLJitter:     10 * time.Second,
+			TTLJitterRand: randN,
+		})
+	}
+	lowest := func(int64) int64 { return 0 }
+	highest := func(n int64) int64 { return n - 1 }
+
+	tests := []struct {
+		name  string
+		ttl   TTL
+		randN func(int64) int64
+		want  string
+	}{
+		{name: "lowest offset", ttl: ExpiresIn(time.Minute), randN: lowest, want: "ms key 1 T50\r\nv\r\n"},
+		{name: "highest offset", ttl: ExpiresIn(time.Minute), randN: highest, want: "ms key 1 T70\r\nv\r\n"},
+		{name: "at least one second", ttl: ExpiresIn(5 * time.Second), randN: lowest, want: "ms key 1 T1\r\nv\r\n"},
+		{name: "NoTTL is not jittered", ttl: NoTTL, randN: highest, want: "ms key 1\r\nv\r\n"},
+		{name: "absolute time is not jittered", ttl: ExpiresAt(time.Unix(2000000000, 0)), randN: highest, want: "ms key 1 T2000000000\r\nv\r\n"},
+	}
+
+	for _, tt := range tests {
+		t.Run("Set: "+tt.name, func(t *testing.T) {
+			mockConn := testutils.NewConnectionMock("HD\r\n")
+			client := newClient(t, mockConn, tt.randN)
+
+			require.NoError(t, client.Set(context.Background(), Item{Key: "key", Value: []byte("v"), TTL: tt.ttl}))
+			assertRequest(t, mockConn, tt.want)
+		})
+
+		t.Run("NewSet: "+tt.name, func(t *testing.T) {
+			mockConn := testutils.NewConnectionMock("HD\r\n")
+			client := newClient(t, mockConn, tt.randN)
+
+			require.NoError(t, client.NewSet("key").Value([]byte("v")).TTL(tt.ttl).Do(context.Background()))
+			assertRequest(t, mockConn, tt.want)
+		})
+	}
+}
+
+func TestClient_SkipOversizedValues(t *testing.T) {
+	const tooLarge = "SERVER_ERROR object too large for cache\r\n"
+
+	newClient := func(t *testing.T, skip bool, responses ...string) *Client {
+		return newTestClientWithConfig(t, testutils.NewConnectionMock(responses...), Config{SkipOversizedValues: skip})
+	}
+
+	t.Run("error by default", func(t *testing.T) {
+		client := newClient(t, false, tooLarge)
+
+		err := client.Set(context.Background(), Item{Key: "key", Value: []byte("v")})
+
+		var serverErr *meta.ServerError
+		require.ErrorAs(t, err, &serverErr)
+	})
+
+	t.Run("skipped", func(t *testing.T) {
+		client := newClient(t, true, tooLarge, tooLarge, tooLarge)
+		item := Item{Key: "key", Value: []byte("v")}
+
+		require.NoError(t, client.Set(context.Background(), item))
+		require.NoError(t, client.Add(context.Background(), item))
+		require.NoError(t, client.NewSet("key").Value([]byte("v")).Do(context.Background()))
+	})
+
+	t.Run("other server errors are still returned", func(t *testing.T) {
+		client := newClient(t, true, "SERVER_ERROR out of memory storing object\r\n")
+
+		err := client.Set(context.Background(), Item{Key: "key", Value: []byte("v")})
+
+		var serverErr *meta.ServerError
+		require.ErrorAs(t, err, &serverErr)
+	})
+}
+
 func TestClient_ExecuteBatch_RejectsQuietFlag(t *testing.T) {
 	mockConn := testutils.NewConnectionMock()
 	client := newTestClient(t, mockConn)
@@ -68,6 +306,67 @@ func TestClient_ExecuteBatch_RejectsQuietFlag(t *testing.T) {
 	assert.Empty(t, mockConn.GetWrittenRequest(), "nothing must be written for a rejected batch")
 }
 
+func TestClient_ExecuteBatch_RejectsNoOp(t *testing.T) {
+	mockConn := testutils.NewConnectionMock()
+	client := newTestClient(t, mockConn)
+
+	reqs := []*meta.Request{
+		meta.NewRequest(meta.CmdGet, "key1", nil).AddReturnValue(),
+		meta.NewRequest(meta.CmdNoOp, "", nil),
+	}
+	resps, err := client.ExecuteBatch(context.Background(), reqs)
+
+	var unsupported *UnsupportedRequestError
+	require.ErrorAs(t, err, &unsupported)
+	assert.Nil(t, resps)
+	assert.Empty(t, mockConn.GetWrittenRequest(), "nothing must be written for a rejected batch")
+}
+
+func TestClient_ExecuteBatch_RejectsInvalidKey(t *testing.T) {
+	mockConn := testutils.NewConnectionMock()
+	client := newTestClient(t, mockConn)
+
+	reqs := []*meta.Request{
+		meta.NewRequest(meta.CmdGet, "key1", nil).AddReturnValue(),
+		meta.NewRequest(meta.CmdGet, "key2", nil).AddReturnValue(),
+		meta.NewRequest(meta.CmdGet, "bad key", nil).AddReturnValue(),
+	}
+	resps, err := client.ExecuteBatch(context.Background(), reqs)
+
+	var batchKeyErr *meta.BatchKeyError
+	require.ErrorAs(t, err, &batchKeyErr)
+	assert.Equal(t, 2, batchKeyErr.Index)
+	assert.Nil(t, resps)
+	assert.Empty(t, mockConn.GetWrittenRequest(), "nothing must be written for a rejected batch")
+}
+
+// An empty key is rejected with meta.ErrEmptyKey before anything is written,
+// instead of sending a malformed command.
+func TestClient_EmptyKey(t *testing.T) {
+	ctx := context.Background()
+	ops := map[string]func(c *Client) error{
+		"get":       func(c *Client) error { _, err := c.Get(ctx, ""); return err },
+		"set":       func(c *Client) error { return c.Set(ctx, Item{Key: "", Value: []byte("v")}) },
+		"delete":    func(c *Client) error { return c.Delete(ctx, "") },
+		"increment": func(c *Client) error { _, err := c.Increment(ctx, "", 1, NoTTL); return err },
+		"decrement": func(c *Client) error { _, err := c.Increment(ctx, "", -1, NoTTL); return err },
+	}
+
+	for name, op := range ops {
+		t.Run(name, func(t *testing.T) {
+			mockConn := testutils.NewConnectionMock()
+			client := newTestClient(t, mockConn)
+
+			err := op(client)
+
+			require.ErrorIs(t, err, meta.ErrEmptyKey)
+			var keyErr *meta.InvalidKeyError
+			assert.ErrorAs(t, err, &keyErr)
+			assert.Empty(t, mockConn.GetWrittenRequest())
+		})
+	}
+}
+
 func TestClient_OperationsAfterClose(t *testing.T) {
 	mockConn := testutils.NewConnectionMock()
 	client := newTestClient(t, mockConn)
@@ -78,3 +377,86 @@ func TestClient_OperationsAfterClose(t *testing.T) {
 	_, err := client.Get(context.Background(), "key")
 	require.ErrorIs(t, err, ErrClientClosed)
 }
+
+// EX is a failed compare: ErrCASMismatch when the request carried a CAS
+// value, an unexpected status otherwise.
+func TestClient_EXStatus(t *testing.T) {
+	tests := []struct {
+		name     string
+		do       func(client *Client) error
+		mismatch bool
+	}{
+		{
+			name: "NewSet with CAS",
+			do: func(client *Client) error {
+				return client.NewSet("key").Value([]byte("v")).CAS(42).Do(context.Background())
+			},
+			mismatch: true,
+		},
+		{
+			name: "NewDelete with CAS",
+			do: func(client *Client) error {
+				_, err := client.NewDelete("key").CAS(42).Do(context.Background())
+				return err
+			},
+			mismatch: true,
+		},
+		{
+			name: "MultiStore with CAS",
+			do: func(client *Client) error {
+				results, err := NewBatchCommands(client).MultiStore(context.Background(), []Item{{Key: "key", Value: []byte("v"), CAS: 42}}, SetModeSet)
+				if err != nil {
+					return err
+				}
+				return results[0]
+			},
+			mismatch: true,
+		},
+		{
+			name: "Set",
+			do: func(client *Client) error {
+				return client.Set(context.Background(), Item{Key: "key", Value: []byte("v")})
+			},
+		},
+		{
+			name: "Add",
+			do: func(client *Client) error {
+				return client.Add(context.Background(), Item{Key: "key", Value: []byte("v")})
+			},
+		},
+		{
+			name: "Delete",
+			do: func(client *Client) error {
+				return client.Delete(context.Background(), "key")
+			},
+		},
+		{
+			name: "NewDelete without CAS",
+			do: func(client *Client) error {
+				_, err := client.NewDelete("key").Do(context.Background())
+				return err
+			},
+		},
+		{
+			name: "Increment",
+			do: func(client *Client) error {
+				_, err := client.Increment(context.Background(), "key", 1, NoTTL)
+				return err
+			},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			client := newTestClient(t, testutils.NewConnectionMock("EX\r\n", "MN\r\n"))
+
+			err := tt.do(client)
+			if tt.mismatch {
+				require.ErrorIs(t, err, ErrCASMismatch)
+			} else {
+				require.ErrorContains(t, err, "failed with status: EX")
+				assert.NotErrorIs(t, err, ErrCASMismatch)
+			}
+		})
+	}
+}
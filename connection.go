@@ -2,9 +2,14 @@ package memcache
 
 import (
 	"bufio"
+	"bytes"
 	"context"
+	"errors"
 	"fmt"
+	"iter"
 	"net"
+	"strconv"
+	"strings"
 	"time"
 
 	"github.com/pior/memcache/meta"
@@ -15,12 +20,25 @@ import (
 // earlier of the context deadline and now+timeout (see setDeadline). Zero
 // timeout means no cap — the operation is bounded only by the context.
 func NewConnection(conn net.Conn, timeout time.Duration) *Connection {
-	return &Connection{
+	c := &Connection{
 		conn:           conn,
 		Reader:         bufio.NewReader(conn),
-		Writer:         bufio.NewWriter(conn),
 		defaultTimeout: timeout,
 	}
+	c.Writer = bufio.NewWriter(sentCounter{c})
+	return c
+}
+
+// sentCounter writes to the network connection, counting the bytes sent to
+// report partial writes.
+type sentCounter struct {
+	c *Connection
+}
+
+func (w sentCounter) Write(p []byte) (int, error) {
+	n, err := w.c.conn.Write(p)
+	w.c.sent += int64(n)
+	return n, err
 }
 
 // Connection wraps a network connection with buffered reader and writer for efficient I/O.
@@ -32,12 +50,120 @@ type Connection struct {
 	// defaultTimeout is a per-operation upper bound on the deadline, capping
 	// even a context that has a later (or no) deadline. Zero means no cap.
 	defaultTimeout time.Duration
+
+	// contextDeadline is set when the last deadline set by setDeadline is the
+	// context deadline, rather than the timeout cap: a timeout then means the
+	// caller's budget ran out (see deadlineErr).
+	contextDeadline bool
+
+	// MaxKeyLength is the longest key accepted, for servers built with a key
+	// limit other than the default. Zero means meta.MaxKeyLength.
+	MaxKeyLength int
+
+	// BufferPool, if not nil, provides the buffers response values are read
+	// into (see meta.ReadResponseWithPool).
+	BufferPool meta.BufferPool
+
+	// LenientLineEndings accepts responses terminated by a bare LF (see
+	// meta.ReadOptions).
+	LenientLineEndings bool
+
+	// watching is set once the connection streams watch events: it is then
+	// dedicated to them.
+	watching bool
+
+	// quit is set once the quit command was sent: the server closes the
+	// connection.
+	quit bool
+
+	// sent is the number of bytes written to the network connection.
+	sent int64
+
+	// written and read count the meta requests written (including the mn
+	// markers) and the responses read, see CommandCounts.
+	written uint64
+	read    uint64
+}
+
+// CommandCounts returns the number of meta requests written to the connection
+// (mg, ms, md, ma, me and the mn markers of quiet requests and batches) and
+// the number of their responses read, for diagnostics. A quiet request whose
+// nominal response was suppressed counts as read once the marker following it
+// is. Both are equal between operations: see checkBalance.
+func (c *Connection) CommandCounts() (written, read uint64) {
+	return c.written, c.read
+}
+
+// checkBalance guards the meta operations against a desynchronized
+// connection: between operations, every request written must have had its
+// response read. Otherwise, an earlier operation stopped mid-stream (e.g. a
+// batch iteration stopped early) or read too few or too many responses, and
+// the next response read would answer another request. The admin streams
+// (StatsSeq, Metadump, Watch) are guarded too, for the same reason.
+func (c *Connection) checkBalance() error {
+	if c.written != c.read {
+		return fmt.Errorf("%w: %d requests written, %d responses read", ErrProtocolDesync, c.written, c.read)
+	}
+	return nil
 }
 
+// readResponse reads the next response into resp.
+func (c *Connection) readResponse(resp *meta.Response) error {
+	return meta.ReadResponseWithOptions(c.Reader, resp, meta.ReadOptions{
+		Pool:               c.BufferPool,
+		LenientLineEndings: c.LenientLineEndings,
+	})
+}
+
+// maxKeyLength returns the effective key length limit.
+func (c *Connection) maxKeyLength() int {
+	if c.MaxKeyLength > 0 {
+		return c.MaxKeyLength
+	}
+	return meta.MaxKeyLength
+}
+
+// HasPendingWrites reports whether request bytes are sitting in the write
+// buffer. Every operation flushes before returning, so this only happens when
+// an operation failed mid-write: such a connection must not be reused.
+func (c *Connection) HasPendingWrites() bool {
+	return c.Writer.Buffered() > 0
+}
+
+// IsClosed reports whether the connection can no longer carry requests: it
+// was quit (see Quit), or the server closed it. The latter is detected with a
+// non-blocking peek at the socket, which reads the end of the stream once the
+// server closed it; it is only available for TCP connections on Linux, and
+// otherwise left to the next operation to fail.
+func (c *Connection) IsClosed() bool {
+	return c.quit || peerClosed(c.conn)
+}
+
+// Close closes the network connection. Pending writes are discarded: they
+// belong to an operation that already failed, e.g. a batch interrupted
+// mid-write, and the server never sees a truncated command since it drops
+// the partial line with the connection.
 func (c *Connection) Close() error {
 	return c.conn.Close()
 }
 
+// operationTimeoutKey is the context key of the timeout set by
+// WithOperationTimeout.
+type operationTimeoutKey struct{}
+
+// WithOperationTimeout returns a copy of ctx that overrides the connection's
+// default timeout (Config.Timeout) for the operations run with it, e.g. to
+// give a large bulk store more time than the rest of the traffic without a
+// separate client. The override replaces the per-operation cap only: a
+// sooner deadline of ctx still wins. A zero timeout lifts the cap, leaving
+// the operations bounded by ctx only.
+//
+// An operation stopped by the override fails with a timeout error like one
+// stopped by the default timeout, and its connection is closed the same way.
+func WithOperationTimeout(ctx context.Context, timeout time.Duration) context.Context {
+	return context.WithValue(ctx, operationTimeoutKey{}, timeout)
+}
+
 // setDeadline sets the connection deadline to the earlier of the context
 // deadline and now+defaultTimeout, so defaultTimeout is a per-operation upper
 // bound rather than a fallback that any context deadline disables. This matters
@@ -45,19 +171,38 @@ func (c *Connection) Close() error {
 // or job-scoped one), using the context deadline verbatim would leave the read
 // effectively unbounded and let a single unresponsive backend stall the client.
 // A zero defaultTimeout means "no cap, defer entirely to the context".
+// A timeout set on ctx with WithOperationTimeout replaces defaultTimeout.
 // Returns the deadline that was set (zero if no deadline).
+//
+// Every operation starts by setting its deadline: on a connection streaming
+// watch events, this fails with ErrConnectionInWatchMode, and on a connection
+// that was quit, with ErrConnectionQuit.
 func (c *Connection) setDeadline(ctx context.Context) (time.Time, error) {
+	if c.watching {
+		return time.Time{}, ErrConnectionInWatchMode
+	}
+	if c.quit {
+		return time.Time{}, ErrConnectionQuit
+	}
+
+	timeout := c.defaultTimeout
+	if override, ok := ctx.Value(operationTimeoutKey{}).(time.Duration); ok {
+		timeout = override
+	}
+
 	var deadline time.Time
 
-	if c.defaultTimeout > 0 {
-		deadline = time.Now().Add(c.defaultTimeout)
+	if timeout > 0 {
+		deadline = time.Now().Add(timeout)
 	}
 
-	// A context deadline that is sooner than the default-timeout cap wins; a
-	// later one is capped at now+defaultTimeout.
+	// A context deadline that is not later than the default-timeout cap wins;
+	// a later one is capped at now+defaultTimeout.
+	c.contextDeadline = false
 	if ctxDeadline, ok := ctx.Deadline(); ok {
-		if deadline.IsZero() || ctxDeadline.Before(deadline) {
+		if deadline.IsZero() || !ctxDeadline.After(deadline) {
 			deadline = ctxDeadline
+			c.contextDeadline = true
 		}
 	}
 
@@ -72,6 +217,22 @@ func (c *Connection) setDeadline(ctx context.Context) (time.Time, error) {
 // Execute implements the Executor interface.
 // Executes a single request and returns the response.
 // The deadline is the earlier of the context deadline and now+defaultTimeout.
+//
+// A request with the quiet flag is followed by a NoOp so a suppressed nominal
+// response doesn't block: the returned response then carries the suppressed
+// status (EN for mg, HD for ms and ma) and no flags. A quiet md suppresses
+// both HD and NF: its response is then MN, the outcome being unknown.
+//
+// The response is parsed directly into a single meta.Response: unlike
+// ExecuteBatch, no slice of responses is allocated for the common single-key
+// case (see BenchmarkConnection_Execute).
+//
+// A write failing after part of the request was sent, e.g. a connection reset
+// while sending a large value, returns a *PartialWriteError.
+//
+// When req has an opaque token (O flag), the response must echo it: a
+// mismatch means the connection is desynchronized, and returns
+// ErrProtocolDesync.
 func (c *Connection) Execute(ctx context.Context, req *meta.Request) (*meta.Response, error) {
 	// Set deadline from context or default timeout
 	if _, err := c.setDeadline(ctx); err != nil {
@@ -80,31 +241,202 @@ func (c *Connection) Execute(ctx context.Context, req *meta.Request) (*meta.Resp
 	// Clear deadline when done to avoid stale deadlines when connection is reused from pool
 	defer c.conn.SetDeadline(time.Time{})
 
-	// Write request to buffered writer
-	if err := meta.WriteRequest(c.Writer, req); err != nil {
+	if err := c.checkBalance(); err != nil {
 		return nil, err
 	}
 
+	// Write request to buffered writer
+	sent := c.sent
+	if err := meta.WriteRequestWithKeyLimit(c.Writer, req, c.maxKeyLength()); err != nil {
+		return nil, c.writeErr(req, sent, err)
+	}
+	c.written++
+
+	// A quiet request gets no response at all when its outcome is nominal:
+	// follow it with a NoOp so there is always a response to wait for.
+	quiet := req.HasFlag(meta.FlagQuiet)
+	if quiet {
+		if err := meta.WriteRequest(c.Writer, meta.NewRequest(meta.CmdNoOp, "", nil)); err != nil {
+			return nil, c.writeErr(req, sent, err)
+		}
+		c.written++
+	}
+
 	// Flush the buffered writer
 	if err := c.Writer.Flush(); err != nil {
-		return nil, err
+		return nil, c.writeErr(req, sent, err)
 	}
 
 	var resp meta.Response
-	if err := meta.ReadResponse(c.Reader, &resp); err != nil {
+	if err := c.readResponse(&resp); err != nil {
+		return nil, err
+	}
+	c.read++
+	if !quiet {
+		if err := checkOpaque(req, &resp); err != nil {
+			return nil, err
+		}
+		return &resp, nil
+	}
+
+	// The NoOp marker came first: the server suppressed the nominal response.
+	if resp.Status == meta.StatusMN {
+		c.read++
+		return &meta.Response{Status: quietStatus(req.Command)}, nil
+	}
+
+	// A connection-corrupting error will be closed by the caller: don't wait
+	// for a marker the server may never send.
+	if resp.Error != nil && meta.ShouldCloseConnection(resp.Error) {
+		return &resp, nil
+	}
+
+	// Consume the NoOp marker following the actual response.
+	var marker meta.Response
+	if err := c.readResponse(&marker); err != nil {
+		return nil, err
+	}
+	c.read++
+	if marker.Status != meta.StatusMN {
+		return nil, &meta.ParseError{Message: "expected MN after quiet response, got " + string(marker.Status)}
+	}
+	if err := checkOpaque(req, &resp); err != nil {
 		return nil, err
 	}
 	return &resp, nil
 }
 
+// checkOpaque verifies that resp, the response to req, echoes the opaque token
+// of req (O flag), if it has one. A response without it, or with another one,
+// answers another request: the connection is desynchronized.
+// Protocol errors (ERROR, CLIENT_ERROR, SERVER_ERROR) carry no flags.
+func checkOpaque(req *meta.Request, resp *meta.Response) error {
+	if resp.Error != nil {
+		return nil
+	}
+	sent, ok := req.Flags.Get(meta.FlagOpaque)
+	if !ok {
+		return nil
+	}
+	if echoed, _ := resp.Opaque(); !bytes.Equal(echoed, sent) {
+		return fmt.Errorf("%w: sent opaque %q, received %q", ErrProtocolDesync, sent, echoed)
+	}
+	return nil
+}
+
+// writeErr reports the failure to send req, which started once sent bytes had
+// been sent on the connection. A request that was partly sent is reported as
+// a PartialWriteError.
+func (c *Connection) writeErr(req *meta.Request, sent int64, err error) error {
+	written := int(c.sent - sent)
+	if written == 0 {
+		return err
+	}
+	var size byteCounter
+	_ = meta.WriteRequestWithKeyLimit(&size, req, c.maxKeyLength())
+	if written >= int(size) {
+		return err
+	}
+	return &PartialWriteError{Written: written, Total: int(size), Err: err}
+}
+
+// byteCounter is an io.Writer counting the bytes written to it.
+type byteCounter int
+
+func (b *byteCounter) Write(p []byte) (int, error) {
+	*b += byteCounter(len(p))
+	return len(p), nil
+}
+
+// ExecuteStream implements the StreamExecutor interface: it is Execute, except
+// that a value larger than threshold bytes is left on the connection rather
+// than buffered. The response then has a nil Data, and the returned stream
+// reads the value; the connection must not be used until the stream is
+// closed, and is closed with it if the value wasn't read entirely. Smaller
+// values are buffered, with a nil stream.
+//
+// Quiet requests are not supported: they are rejected with an
+// *UnsupportedRequestError. Write failures are reported as by Execute.
+func (c *Connection) ExecuteStream(ctx context.Context, req *meta.Request, threshold int) (*meta.Response, *ValueStream, error) {
+	if req.HasFlag(meta.FlagQuiet) {
+		return nil, nil, &UnsupportedRequestError{Message: "quiet flag is not supported in ExecuteStream"}
+	}
+
+	if _, err := c.setDeadline(ctx); err != nil {
+		return nil, nil, err
+	}
+	if err := c.checkBalance(); err != nil {
+		c.conn.SetDeadline(time.Time{})
+		return nil, nil, err
+	}
+
+	sent := c.sent
+	if err := meta.WriteRequestWithKeyLimit(c.Writer, req, c.maxKeyLength()); err != nil {
+		c.conn.SetDeadline(time.Time{})
+		return nil, nil, c.writeErr(req, sent, err)
+	}
+	c.written++
+	if err := c.Writer.Flush(); err != nil {
+		c.conn.SetDeadline(time.Time{})
+		return nil, nil, c.writeErr(req, sent, err)
+	}
+
+	var resp meta.Response
+	err := meta.ReadResponseWithOptions(c.Reader, &resp, meta.ReadOptions{
+		Pool:               c.BufferPool,
+		LenientLineEndings: c.LenientLineEndings,
+		StreamThreshold:    threshold,
+	})
+	if err == nil {
+		c.read++
+		err = checkOpaque(req, &resp)
+	}
+	if err != nil || resp.UnreadSize == 0 {
+		c.conn.SetDeadline(time.Time{})
+		if err != nil {
+			return nil, nil, err
+		}
+		return &resp, nil, nil
+	}
+
+	// The deadline is renewed by each read of the stream, and cleared by Close.
+	return &resp, &ValueStream{
+		ctx:  ctx,
+		conn: c,
+		data: meta.NewDataReader(c.Reader, resp.UnreadSize, c.LenientLineEndings),
+		size: resp.UnreadSize,
+	}, nil
+}
+
+// quietStatus returns the nominal status the server suppresses for a quiet
+// request: EN (miss) for mg, HD (success) for ms and ma. For md, which
+// suppresses both HD and NF, it is MN: the marker alone can't tell them
+// apart.
+func quietStatus(cmd meta.CmdType) meta.StatusType {
+	switch cmd {
+	case meta.CmdGet:
+		return meta.StatusEN
+	case meta.CmdDelete:
+		return meta.StatusMN
+	default:
+		return meta.StatusHD
+	}
+}
+
 // ExecuteBatch implements the BatchExecutor interface.
 // Executes multiple requests in a pipeline using the NoOp marker strategy.
-// Sends all requests followed by a NoOp command, then reads responses until the NoOp response.
+// Sends all requests followed by a NoOp command, reading responses until the
+// NoOp response while the requests are still being written, so a batch larger
+// than the socket buffers cannot deadlock.
 //
 // Returns responses in the same order as requests.
 // Individual request errors are captured in Response.Error (protocol errors).
 // I/O errors or connection failures are returned as Go errors.
 //
+// Responses are matched by position, relying on the server answering in
+// order: no opaque token is added, so the bytes written are exactly those of
+// the requests followed by "mn", and tests can assert them verbatim.
+//
 // If no request uses the quiet flag, the response count is guaranteed to match
 // the request count; a mismatch is reported as an error since it means the
 // connection is desynchronized. With quiet requests, nominal responses are
@@ -118,122 +450,254 @@ func (c *Connection) ExecuteBatch(ctx context.Context, reqs []*meta.Request) ([]
 		return nil, nil
 	}
 
-	// Validate all keys before writing anything, so a rejected request cannot
-	// leave earlier requests of the batch sitting in the write buffer.
-	hasQuiet := false
-	for _, req := range reqs {
-		if req.Command != meta.CmdNoOp && req.Command != meta.CmdStats {
-			if err := meta.ValidateKey(req.Key, req.HasFlag(meta.FlagBase64Key)); err != nil {
-				return nil, err
-			}
-		}
-		if req.HasFlag(meta.FlagQuiet) {
-			hasQuiet = true
+	responses := make([]*meta.Response, 0, len(reqs))
+	for resp, err := range c.ExecuteBatchSeq(ctx, reqs) {
+		if err != nil {
+			// Return responses collected so far
+			return responses, err
 		}
+		responses = append(responses, resp)
 	}
 
-	// Set initial deadline for writing all requests
-	if _, err := c.setDeadline(ctx); err != nil {
-		return nil, err
-	}
-	// Clear deadline when done to avoid stale deadlines when connection is reused from pool
-	defer c.conn.SetDeadline(time.Time{})
+	return responses, nil
+}
 
-	// Write all requests
-	for _, req := range reqs {
-		if err := meta.WriteRequest(c.Writer, req); err != nil {
-			return nil, err
+// ExecuteBatchSeq is the streaming form of ExecuteBatch: it yields each
+// response as soon as it is read off the wire, in the order of the requests,
+// so the caller can process the first results while later ones are still in
+// flight. The sequence ends at the NoOp marker, or after yielding an error.
+//
+// The connection is left mid-stream if the iteration stops early or fails
+// with an error that requires closing the connection: it must not be reused
+// then.
+func (c *Connection) ExecuteBatchSeq(ctx context.Context, reqs []*meta.Request) iter.Seq2[*meta.Response, error] {
+	return func(yield func(*meta.Response, error) bool) {
+		if len(reqs) == 0 {
+			return
 		}
-	}
 
-	// Write NoOp marker to signal end of batch
-	noopReq := meta.NewRequest(meta.CmdNoOp, "", nil)
-	if err := meta.WriteRequest(c.Writer, noopReq); err != nil {
-		return nil, err
-	}
-
-	// Flush all writes
-	if err := c.Writer.Flush(); err != nil {
-		return nil, err
-	}
+		// Validate all keys before writing anything, so a rejected request cannot
+		// leave earlier requests of the batch sitting in the write buffer.
+		if err := meta.ValidateBatchKeys(reqs, c.maxKeyLength()); err != nil {
+			yield(nil, err)
+			return
+		}
 
-	// Read responses until the NoOp marker. Protocol errors (stored in
-	// Response.Error) do not stop the loop: the server keeps processing the
-	// pipelined requests that follow, and stopping early would leave their
-	// responses unread on the connection.
-	responses := make([]*meta.Response, 0, len(reqs))
+		// The batch has a single NoOp terminator, appended below: a NoOp among
+		// the requests would end the read loop early, leaving the responses
+		// that follow it on the connection.
+		hasQuiet := false
+		for _, req := range reqs {
+			if req.Command == meta.CmdNoOp {
+				yield(nil, &UnsupportedRequestError{Message: "mn is not allowed in a batch: it is the batch terminator"})
+				return
+			}
+			if req.HasFlag(meta.FlagQuiet) {
+				hasQuiet = true
+			}
+		}
 
-	for {
-		// Extend deadline before each read to prevent cumulative timeout
-		// This is critical for large batches - each response gets a full timeout window
+		// Set initial deadline for writing all requests
 		if _, err := c.setDeadline(ctx); err != nil {
-			return responses, err
+			yield(nil, err)
+			return
 		}
+		// Clear deadline when done to avoid stale deadlines when connection is reused from pool
+		defer c.conn.SetDeadline(time.Time{})
 
-		var resp meta.Response
-		if err := meta.ReadResponse(c.Reader, &resp); err != nil {
-			// Return responses collected so far
-			return responses, err
+		if err := c.checkBalance(); err != nil {
+			yield(nil, err)
+			return
 		}
 
-		// Stop when we hit the NoOp marker (not part of the results)
-		if resp.Status == meta.StatusMN {
-			break
+		// Write from a separate goroutine while the responses are read below.
+		// Writing the whole batch before reading deadlocks on a large batch:
+		// once the server's send buffer is full of unread responses, it stops
+		// reading requests, the client's writes block, and neither side makes
+		// progress until the deadline.
+		writeErr := make(chan error, 1)
+		c.written += uint64(len(reqs)) + 1
+		go func() { writeErr <- c.writeBatch(reqs) }()
+
+		// stopWriting waits for the writer, aborting a write blocked on a
+		// server that is no longer read from. It returns the write error.
+		writing := true
+		stopWriting := func() error {
+			if !writing {
+				return nil
+			}
+			writing = false
+			_ = c.conn.SetWriteDeadline(time.Now())
+			return <-writeErr
 		}
+		defer stopWriting()
+
+		// Read responses until the NoOp marker. Protocol errors (stored in
+		// Response.Error) do not stop the loop: the server keeps processing the
+		// pipelined requests that follow, and stopping early would leave their
+		// responses unread on the connection.
+		count := 0
+		for {
+			// Extend deadline before each read to prevent cumulative timeout
+			// This is critical for large batches - each response gets a full timeout window
+			if _, err := c.setDeadline(ctx); err != nil {
+				yield(nil, err)
+				return
+			}
 
-		responses = append(responses, &resp)
+			var resp meta.Response
+			if err := c.readResponse(&resp); err != nil {
+				// A failed write is the root cause of the read failing.
+				if werr := stopWriting(); werr != nil {
+					err = werr
+				}
+				yield(nil, err)
+				return
+			}
+			c.read++
+
+			// Stop when we hit the NoOp marker (not part of the results). The
+			// server only sends it after reading the whole batch.
+			if resp.Status == meta.StatusMN {
+				if hasQuiet {
+					// The suppressed responses of quiet requests are complete too.
+					c.read = c.written
+				}
+				writing = false
+				if err := <-writeErr; err != nil {
+					yield(nil, err)
+					return
+				}
+				break
+			}
+
+			count++
+			if count > len(reqs) {
+				yield(nil, &meta.ParseError{Message: "received more responses than requests in batch"})
+				return
+			}
 
-		if len(responses) > len(reqs) {
-			return responses, &meta.ParseError{Message: "received more responses than requests in batch"}
+			if !yield(&resp, nil) {
+				return
+			}
 		}
-	}
 
-	if !hasQuiet && len(responses) != len(reqs) {
-		return responses, &meta.ParseError{
-			Message: fmt.Sprintf("received %d responses for %d requests in batch", len(responses), len(reqs)),
+		if !hasQuiet && count != len(reqs) {
+			yield(nil, &meta.ParseError{
+				Message: fmt.Sprintf("received %d responses for %d requests in batch", count, len(reqs)),
+			})
 		}
 	}
+}
 
-	return responses, nil
+// writeBatch writes the requests of a batch followed by the NoOp marker
+// that ends it, and flushes them.
+func (c *Connection) writeBatch(reqs []*meta.Request) error {
+	for _, req := range reqs {
+		if err := meta.WriteRequestWithKeyLimit(c.Writer, req, c.maxKeyLength()); err != nil {
+			return err
+		}
+	}
+	if err := meta.WriteRequest(c.Writer, meta.NewRequest(meta.CmdNoOp, "", nil)); err != nil {
+		return err
+	}
+	return c.Writer.Flush()
 }
 
 // ExecuteStats implements the StatsExecutor interface.
 // Executes the stats command and returns the stats as a map.
 func (c *Connection) ExecuteStats(ctx context.Context, args ...string) (map[string]string, error) {
-	// Set deadline from context or default timeout
+	stats := make(map[string]string)
+	for stat, err := range c.StatsSeq(ctx, args...) {
+		if err != nil {
+			return nil, err
+		}
+		stats[stat.Name] = stat.Value
+	}
+	return stats, nil
+}
+
+// StatsSeq is the streaming form of ExecuteStats: it yields each stat as its
+// line is read, without building a map, for large outputs (e.g.
+// "stats slabs" on a big server) of which only a few stats are needed.
+//
+// The deadline is extended before reading each stat, as for Metadump. The
+// connection is left mid-stream if the iteration stops early or fails with an
+// error that requires closing the connection: it must not be reused then.
+func (c *Connection) StatsSeq(ctx context.Context, args ...string) iter.Seq2[meta.Stat, error] {
+	return func(yield func(meta.Stat, error) bool) {
+		if _, err := c.setDeadline(ctx); err != nil {
+			yield(meta.Stat{}, err)
+			return
+		}
+		// Clear deadline when done to avoid stale deadlines when connection is reused from pool
+		defer c.conn.SetDeadline(time.Time{})
+
+		if err := c.checkBalance(); err != nil {
+			yield(meta.Stat{}, err)
+			return
+		}
+
+		// Build stats request
+		statsArg := ""
+		if len(args) > 0 {
+			statsArg = args[0]
+		}
+		req := &meta.Request{
+			Command: meta.CmdStats,
+			Key:     statsArg, // stats uses Key field for optional args
+		}
+
+		if err := meta.WriteRequest(c.Writer, req); err != nil {
+			yield(meta.Stat{}, err)
+			return
+		}
+		if err := c.Writer.Flush(); err != nil {
+			yield(meta.Stat{}, err)
+			return
+		}
+
+		for {
+			if _, err := c.setDeadline(ctx); err != nil {
+				yield(meta.Stat{}, err)
+				return
+			}
+
+			stat, ok, err := meta.ReadStat(c.Reader)
+			if err != nil {
+				yield(meta.Stat{}, err)
+				return
+			}
+			if !ok {
+				return
+			}
+			if !yield(stat, nil) {
+				return
+			}
+		}
+	}
+}
+
+// Cachedump lists up to limit items of the slab class with the
+// "stats cachedump" command (zero limit means all of them).
+func (c *Connection) Cachedump(ctx context.Context, slabClass, limit int) ([]meta.CachedumpEntry, error) {
 	if _, err := c.setDeadline(ctx); err != nil {
 		return nil, err
 	}
-	// Clear deadline when done to avoid stale deadlines when connection is reused from pool
 	defer c.conn.SetDeadline(time.Time{})
 
-	// Build stats request
-	statsArg := ""
-	if len(args) > 0 {
-		statsArg = args[0]
-	}
 	req := &meta.Request{
 		Command: meta.CmdStats,
-		Key:     statsArg, // stats uses Key field for optional args
+		Key:     "cachedump " + strconv.Itoa(slabClass) + " " + strconv.Itoa(limit), // stats uses Key field for args
 	}
-
-	// Send stats request
 	if err := meta.WriteRequest(c.Writer, req); err != nil {
 		return nil, err
 	}
-
-	// Flush the buffered writer
 	if err := c.Writer.Flush(); err != nil {
 		return nil, err
 	}
 
-	// Read stats response
-	stats, err := meta.ReadStatsResponse(c.Reader)
-	if err != nil {
-		return nil, err
-	}
-
-	return stats, nil
+	return meta.ReadCachedumpResponse(c.Reader)
 }
 
 // Ping performs a simple health check on a connection using the noop command.
@@ -253,3 +717,229 @@ func (c *Connection) Ping(ctx context.Context) error {
 
 	return nil
 }
+
+// SetMemoryLimit changes the server memory limit, in megabytes, with the
+// cache_memlimit admin command.
+func (c *Connection) SetMemoryLimit(ctx context.Context, megabytes int) error {
+	req := &meta.Request{
+		Command: meta.CmdCacheMemlimit,
+		Key:     strconv.Itoa(megabytes), // admin commands use Key field for args
+	}
+	return c.executeAdmin(ctx, req)
+}
+
+// Version returns the server version, with the version command.
+func (c *Connection) Version(ctx context.Context) (meta.Version, error) {
+	if _, err := c.setDeadline(ctx); err != nil {
+		return meta.Version{}, err
+	}
+	defer c.conn.SetDeadline(time.Time{})
+
+	if err := meta.WriteRequest(c.Writer, &meta.Request{Command: meta.CmdVersion}); err != nil {
+		return meta.Version{}, err
+	}
+	if err := c.Writer.Flush(); err != nil {
+		return meta.Version{}, err
+	}
+
+	return meta.ReadVersionResponse(c.Reader)
+}
+
+// Quit sends the quit command: the server closes the connection, without a
+// response. Every later operation fails with ErrConnectionQuit, and the
+// connection must be closed: the client's pools discard it when it is
+// released.
+func (c *Connection) Quit(ctx context.Context) error {
+	if _, err := c.setDeadline(ctx); err != nil {
+		return err
+	}
+	defer c.conn.SetDeadline(time.Time{})
+
+	if err := meta.WriteRequest(c.Writer, &meta.Request{Command: meta.CmdQuit}); err != nil {
+		return err
+	}
+	c.quit = true
+	return c.Writer.Flush()
+}
+
+// Authenticate sends the username and password with the authentication
+// command of memcached's text protocol (server started with -Y), which must
+// precede any other command on a connection to such a server. A rejection by
+// the server is reported as ErrAuthFailed.
+func (c *Connection) Authenticate(ctx context.Context, username, password string) error {
+	if _, err := c.setDeadline(ctx); err != nil {
+		return err
+	}
+	defer c.conn.SetDeadline(time.Time{})
+
+	if err := meta.WriteAuthRequest(c.Writer, username, password); err != nil {
+		return err
+	}
+	if err := c.Writer.Flush(); err != nil {
+		return err
+	}
+
+	err := meta.ReadAuthResponse(c.Reader)
+	var clientErr *meta.ClientError
+	if errors.As(err, &clientErr) {
+		return fmt.Errorf("%w: %w", ErrAuthFailed, err)
+	}
+	return err
+}
+
+// SetVerbosity changes the server logging level with the verbosity admin
+// command.
+func (c *Connection) SetVerbosity(ctx context.Context, level int) error {
+	req := &meta.Request{
+		Command: meta.CmdVerbosity,
+		Key:     strconv.Itoa(level), // admin commands use Key field for args
+	}
+	return c.executeAdmin(ctx, req)
+}
+
+// executeAdmin sends an admin command and reads its "OK" response.
+func (c *Connection) executeAdmin(ctx context.Context, req *meta.Request) error {
+	if _, err := c.setDeadline(ctx); err != nil {
+		return err
+	}
+	defer c.conn.SetDeadline(time.Time{})
+
+	if err := meta.WriteRequest(c.Writer, req); err != nil {
+		return err
+	}
+	if err := c.Writer.Flush(); err != nil {
+		return err
+	}
+
+	return meta.ReadOKResponse(c.Reader)
+}
+
+// Metadump streams the metadata of every item stored on the server, with the
+// "lru_crawler metadump all" admin command.
+//
+// The deadline is extended before reading each entry, so the timeout bounds
+// the wait for an entry rather than the whole dump. The connection is left
+// mid-stream if the iteration stops early or fails with an error that
+// requires closing the connection: it must not be reused then.
+func (c *Connection) Metadump(ctx context.Context) iter.Seq2[meta.MetadumpEntry, error] {
+	return func(yield func(meta.MetadumpEntry, error) bool) {
+		if _, err := c.setDeadline(ctx); err != nil {
+			yield(meta.MetadumpEntry{}, err)
+			return
+		}
+		defer c.conn.SetDeadline(time.Time{})
+
+		if err := c.checkBalance(); err != nil {
+			yield(meta.MetadumpEntry{}, err)
+			return
+		}
+
+		req := &meta.Request{
+			Command: meta.CmdLRUCrawler,
+			Key:     "metadump all", // admin commands use Key field for args
+		}
+		if err := meta.WriteRequest(c.Writer, req); err != nil {
+			yield(meta.MetadumpEntry{}, err)
+			return
+		}
+		if err := c.Writer.Flush(); err != nil {
+			yield(meta.MetadumpEntry{}, err)
+			return
+		}
+
+		for {
+			if _, err := c.setDeadline(ctx); err != nil {
+				yield(meta.MetadumpEntry{}, err)
+				return
+			}
+
+			entry, ok, err := meta.ReadMetadumpEntry(c.Reader)
+			if err != nil {
+				yield(meta.MetadumpEntry{}, err)
+				return
+			}
+			if !ok {
+				return
+			}
+			if !yield(entry, nil) {
+				return
+			}
+		}
+	}
+}
+
+// Watch streams the server's log events with the "watch" command, for the
+// given event classes (e.g. "fetchers", "mutations"; none for the server
+// default). An event that isn't a valid protocol token (empty, too long, or
+// holding whitespace, as checked by meta.ValidateKey) fails with an
+// *meta.InvalidKeyError before anything is written.
+//
+// Once the server accepted the command, the connection is dedicated to the
+// stream: the server keeps pushing events, which would be read as the
+// responses of other commands, so every other operation fails with
+// ErrConnectionInWatchMode. The connection must be closed when done.
+//
+// Events may be far apart: the wait for an event is bounded by ctx only, not
+// by the connection timeout. The iteration ends with ctx.Err() once ctx is
+// done.
+func (c *Connection) Watch(ctx context.Context, events ...string) iter.Seq2[meta.WatchEvent, error] {
+	return func(yield func(meta.WatchEvent, error) bool) {
+		for _, event := range events {
+			if err := meta.ValidateKey(event, false); err != nil {
+				yield(meta.WatchEvent{}, fmt.Errorf("invalid watch event %q: %w", event, err))
+				return
+			}
+		}
+
+		if _, err := c.setDeadline(ctx); err != nil {
+			yield(meta.WatchEvent{}, err)
+			return
+		}
+
+		if err := c.checkBalance(); err != nil {
+			yield(meta.WatchEvent{}, err)
+			return
+		}
+
+		req := &meta.Request{
+			Command: meta.CmdWatch,
+			Key:     strings.Join(events, " "), // admin commands use Key field for args
+		}
+		if err := meta.WriteRequest(c.Writer, req); err != nil {
+			yield(meta.WatchEvent{}, err)
+			return
+		}
+		if err := c.Writer.Flush(); err != nil {
+			yield(meta.WatchEvent{}, err)
+			return
+		}
+		if err := meta.ReadOKResponse(c.Reader); err != nil {
+			yield(meta.WatchEvent{}, err)
+			return
+		}
+		c.watching = true
+
+		// Only ctx bounds the wait: a context cancellation interrupts the
+		// blocked read, as the connection is not reused.
+		c.conn.SetDeadline(time.Time{})
+		if deadline, ok := ctx.Deadline(); ok {
+			c.conn.SetReadDeadline(deadline)
+		}
+		stop := context.AfterFunc(ctx, func() { _ = c.conn.SetReadDeadline(time.Now()) })
+		defer stop()
+
+		for {
+			event, err := meta.ReadWatchEvent(c.Reader)
+			if err != nil {
+				if ctx.Err() != nil {
+					err = ctx.Err()
+				}
+				yield(meta.WatchEvent{}, err)
+				return
+			}
+			if !yield(event, nil) {
+				return
+			}
+		}
+	}
+}
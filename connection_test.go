@@ -1,7 +1,12 @@
 package memcache
 
 import (
+	"bufio"
 	"context"
+	"net"
+	"strconv"
+	"strings"
+	"syscall"
 	"testing"
 	"time"
 
@@ -57,6 +62,68 @@ func TestConnection_ExecuteBatch_ResponseCountMismatch(t *testing.T) {
 	assert.Len(t, resps, 1)
 }
 
+func TestConnection_ExecuteBatchSeq(t *testing.T) {
+	conn, _ := newMockConnection("VA 2\r\nv1\r\n", "EN\r\n", "MN\r\n")
+
+	var statuses []string
+	for resp, err := range conn.ExecuteBatchSeq(context.Background(), []*meta.Request{getReq("k1"), getReq("k2")}) {
+		require.NoError(t, err)
+		statuses = append(statuses, string(resp.Status))
+	}
+
+	assert.Equal(t, []string{"VA", "EN"}, statuses)
+}
+
+func TestConnection_CommandCounts(t *testing.T) {
+	conn, _ := newMockConnection(
+		"EN\r\n",
+		"MN\r\n", // quiet get, miss suppressed
+		"VA 2\r\nv1\r\n", "EN\r\n", "MN\r\n",
+		"MN\r\n", // quiet batch, both misses suppressed
+	)
+	ctx := context.Background()
+
+	_, err := conn.Execute(ctx, getReq("k1"))
+	require.NoError(t, err)
+	_, err = conn.Execute(ctx, getReq("k1").AddQuiet())
+	require.NoError(t, err)
+	_, err = conn.ExecuteBatch(ctx, []*meta.Request{getReq("k1"), getReq("k2")})
+	require.NoError(t, err)
+	_, err = conn.ExecuteBatch(ctx, []*meta.Request{getReq("k1").AddQuiet(), getReq("k2").AddQuiet()})
+	require.NoError(t, err)
+
+	written, read := conn.CommandCounts()
+	assert.Equal(t, uint64(9), written)
+	assert.Equal(t, written, read)
+}
+
+// A batch iteration stopped early leaves responses unread: the next operation
+// must fail instead of reading them as its own.
+func TestConnection_UnbalancedCountsDesync(t *testing.T) {
+	conn, mock := newMockConnection("VA 2\r\nv1\r\n", "EN\r\n", "MN\r\n")
+	ctx := context.Background()
+
+	for _, err := range conn.ExecuteBatchSeq(ctx, []*meta.Request{getReq("k1"), getReq("k2")}) {
+		require.NoError(t, err)
+		break
+	}
+	written := mock.GetWrittenRequest()
+
+	_, err := conn.Execute(ctx, getReq("k3"))
+	require.ErrorIs(t, err, ErrProtocolDesync)
+	assert.True(t, meta.ShouldCloseConnection(err))
+	for _, err := range conn.StatsSeq(ctx) {
+		require.ErrorIs(t, err, ErrProtocolDesync)
+	}
+	for _, err := range conn.Metadump(ctx) {
+		require.ErrorIs(t, err, ErrProtocolDesync)
+	}
+	for _, err := range conn.Watch(ctx) {
+		require.ErrorIs(t, err, ErrProtocolDesync)
+	}
+	assert.Equal(t, written, mock.GetWrittenRequest(), "nothing must be written on a desynchronized connection")
+}
+
 // With quiet requests, suppressed responses are legal: no count check.
 func TestConnection_ExecuteBatch_QuietSuppressedResponses(t *testing.T) {
 	conn, _ := newMockConnection("VA 2\r\nv1\r\n", "MN\r\n") // miss response suppressed
@@ -71,6 +138,202 @@ func TestConnection_ExecuteBatch_QuietSuppressedResponses(t *testing.T) {
 	assert.Equal(t, "v1", string(resps[0].Data))
 }
 
+// Quiet requests in a batch share its single NoOp terminator: the builders
+// don't add their own, as Execute does for a single quiet request.
+func TestConnection_ExecuteBatch_MixedQuietSingleTerminator(t *testing.T) {
+	conn, mock := newMockConnection("VA 2\r\nv1\r\n", "HD\r\n", "MN\r\n")
+	commands := NewCommands(nil)
+
+	reqs := []*meta.Request{
+		commands.NewSet("k1").Value([]byte("a")).Quiet().Request(),
+		getReq("k2"),
+		commands.NewSet("k3").Value([]byte("b")).Quiet().Request(),
+		meta.NewRequest(meta.CmdDelete, "k4", nil),
+	}
+	resps, err := conn.ExecuteBatch(context.Background(), reqs)
+	require.NoError(t, err)
+	require.Len(t, resps, 2)
+
+	written := mock.GetWrittenRequest()
+	assert.Equal(t, 1, strings.Count(written, "mn\r\n"), written)
+	assert.True(t, strings.HasSuffix(written, "md k4\r\nmn\r\n"), written)
+}
+
+func TestConnection_ExecuteBatch_RejectsNoOp(t *testing.T) {
+	conn, mock := newMockConnection()
+
+	reqs := []*meta.Request{getReq("k1"), meta.NewRequest(meta.CmdNoOp, "", nil), getReq("k2")}
+	_, err := conn.ExecuteBatch(context.Background(), reqs)
+
+	var unsupported *UnsupportedRequestError
+	require.ErrorAs(t, err, &unsupported)
+	assert.False(t, meta.ShouldCloseConnection(err), "nothing was written: the connection is reusable")
+	assert.Empty(t, mock.GetWrittenRequest())
+}
+
+// A quiet delete emits neither HD nor NF: only the NoOp marker ends the
+// batch, while other outcomes (e.g. EX for a CAS mismatch) are still reported.
+func TestConnection_ExecuteBatch_QuietDelete(t *testing.T) {
+	conn, mock := newMockConnection("EX\r\n", "MN\r\n") // k1 deleted, k2 missing, k3 CAS mismatch
+
+	reqs := []*meta.Request{
+		meta.NewRequest(meta.CmdDelete, "k1", nil).AddQuiet(),
+		meta.NewRequest(meta.CmdDelete, "k2", nil).AddQuiet(),
+		meta.NewRequest(meta.CmdDelete, "k3", nil).AddCAS(5).AddQuiet(),
+	}
+	resps, err := conn.ExecuteBatch(context.Background(), reqs)
+	require.NoError(t, err)
+	require.Len(t, resps, 1)
+	assert.Equal(t, string(meta.StatusEX), string(resps[0].Status))
+	assert.Equal(t, "md k1 q\r\nmd k2 q\r\nmd k3 C5 q\r\nmn\r\n", mock.GetWrittenRequest())
+}
+
+func TestConnection_Execute_QuietDelete(t *testing.T) {
+	t.Run("hit or miss", func(t *testing.T) {
+		// Neither HD nor NF on the wire: Execute must return on the marker
+		// instead of blocking for a response that never comes, without
+		// claiming the key was deleted.
+		conn, mock := newMockConnection("MN\r\n")
+
+		resp, err := conn.Execute(context.Background(), meta.NewRequest(meta.CmdDelete, "key", nil).AddQuiet())
+		require.NoError(t, err)
+		assert.Equal(t, string(meta.StatusMN), string(resp.Status))
+		assert.Equal(t, "md key q\r\nmn\r\n", mock.GetWrittenRequest())
+	})
+
+	t.Run("CAS mismatch", func(t *testing.T) {
+		conn, _ := newMockConnection("EX\r\n", "MN\r\n")
+
+		resp, err := conn.Execute(context.Background(), meta.NewRequest(meta.CmdDelete, "key", nil).AddCAS(5).AddQuiet())
+		require.NoError(t, err)
+		assert.Equal(t, string(meta.StatusEX), string(resp.Status))
+	})
+}
+
+// A batch far larger than the socket buffers must not deadlock: net.Pipe has
+// no buffering at all, so the server blocks on its first response until the
+// client reads it, and writing the whole batch before reading would stall.
+func TestConnection_ExecuteBatch_LargePipeline(t *testing.T) {
+	client, server := net.Pipe()
+	t.Cleanup(func() { _ = client.Close() })
+
+	// Answer EN to each mg and MN to the mn ending the batch.
+	go func() {
+		defer server.Close()
+		r := bufio.NewReader(server)
+		w := bufio.NewWriter(server)
+		for {
+			line, err := r.ReadString('\n')
+			if err != nil {
+				return
+			}
+			if strings.HasPrefix(line, "mn") {
+				_, _ = w.WriteString("MN\r\n")
+			} else {
+				_, _ = w.WriteString("EN\r\n")
+			}
+			if r.Buffered() == 0 {
+				if err := w.Flush(); err != nil {
+					return
+				}
+			}
+		}
+	}()
+
+	const n = 100_000
+	reqs := make([]*meta.Request, n)
+	for i := range reqs {
+		reqs[i] = getReq("key:" + strconv.Itoa(i))
+	}
+
+	conn := NewConnection(client, 10*time.Second)
+	resps, err := conn.ExecuteBatch(context.Background(), reqs)
+	require.NoError(t, err)
+	require.Len(t, resps, n)
+	assert.True(t, resps[n-1].IsMiss())
+}
+
+func TestConnection_Execute_QuietArithmetic(t *testing.T) {
+	req := func() *meta.Request {
+		return meta.NewRequest(meta.CmdArithmetic, "counter", nil).AddDelta(1).AddQuiet()
+	}
+
+	t.Run("existing key", func(t *testing.T) {
+		// The server suppresses HD: Execute must return on the marker instead
+		// of waiting for the success line.
+		conn, mock := newMockConnection("MN\r\n")
+
+		resp, err := conn.Execute(context.Background(), req())
+		require.NoError(t, err)
+		assert.Equal(t, string(meta.StatusHD), string(resp.Status))
+		assert.Equal(t, "ma counter D1 q\r\nmn\r\n", mock.GetWrittenRequest())
+	})
+
+	t.Run("missing key", func(t *testing.T) {
+		conn, _ := newMockConnection("NF\r\n", "MN\r\n")
+
+		resp, err := conn.Execute(context.Background(), req())
+		require.NoError(t, err)
+		assert.Equal(t, string(meta.StatusNF), string(resp.Status))
+	})
+}
+
+// A quiet miss is silent even with an opaque: the MN marker alone means the
+// miss happened, and must not be mistaken for the response itself.
+func TestConnection_Execute_QuietGetWithOpaque(t *testing.T) {
+	req := func() *meta.Request {
+		return getReq("key").AddQuiet().AddOpaque("123")
+	}
+
+	t.Run("miss", func(t *testing.T) {
+		conn, mock := newMockConnection("MN\r\n")
+
+		resp, err := conn.Execute(context.Background(), req())
+		require.NoError(t, err)
+		assert.True(t, resp.IsMiss())
+		assert.Equal(t, "mg key v q O123\r\nmn\r\n", mock.GetWrittenRequest())
+	})
+
+	t.Run("hit", func(t *testing.T) {
+		conn, _ := newMockConnection("VA 2 O123\r\nv1\r\n", "MN\r\n")
+
+		resp, err := conn.Execute(context.Background(), req())
+		require.NoError(t, err)
+		assert.Equal(t, "v1", string(resp.Data))
+		opaque, ok := resp.Opaque()
+		require.True(t, ok)
+		assert.Equal(t, "123", string(opaque))
+	})
+}
+
+func TestConnection_Execute_OpaqueCheck(t *testing.T) {
+	tests := []struct {
+		name     string
+		response string
+		desync   bool
+	}{
+		{name: "echoed", response: "EN O123\r\n"},
+		{name: "other token", response: "EN O456\r\n", desync: true},
+		{name: "no token", response: "HD\r\n", desync: true},
+		{name: "protocol error", response: "SERVER_ERROR busy\r\n"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			conn, _ := newMockConnection(tt.response)
+
+			_, err := conn.Execute(context.Background(), getReq("key").AddOpaque("123"))
+
+			if tt.desync {
+				require.ErrorIs(t, err, ErrProtocolDesync)
+				assert.True(t, meta.ShouldCloseConnection(err))
+			} else {
+				require.NoError(t, err)
+			}
+		})
+	}
+}
+
 // An invalid key anywhere in the batch must be rejected before any write.
 func TestConnection_ExecuteBatch_InvalidKeyWritesNothing(t *testing.T) {
 	conn, mock := newMockConnection()
@@ -80,9 +343,54 @@ func TestConnection_ExecuteBatch_InvalidKeyWritesNothing(t *testing.T) {
 
 	var invalidKey *meta.InvalidKeyError
 	require.ErrorAs(t, err, &invalidKey)
+	var batchKeyErr *meta.BatchKeyError
+	require.ErrorAs(t, err, &batchKeyErr)
+	assert.Equal(t, 1, batchKeyErr.Index)
 	assert.Empty(t, mock.GetWrittenRequest(), "no bytes must reach the connection")
 }
 
+func TestConnection_MaxKeyLength(t *testing.T) {
+	longKey := strings.Repeat("a", 300)
+
+	t.Run("default limit", func(t *testing.T) {
+		conn, mock := newMockConnection()
+
+		_, err := conn.Execute(context.Background(), getReq(longKey))
+
+		var invalidKey *meta.InvalidKeyError
+		require.ErrorAs(t, err, &invalidKey)
+		assert.Empty(t, mock.GetWrittenRequest())
+	})
+
+	t.Run("raised limit", func(t *testing.T) {
+		conn, mock := newMockConnection("EN\r\n", "EN\r\n", "MN\r\n")
+		conn.MaxKeyLength = 300
+
+		_, err := conn.Execute(context.Background(), getReq(longKey))
+		require.NoError(t, err)
+
+		_, err = conn.ExecuteBatch(context.Background(), []*meta.Request{getReq(longKey)})
+		require.NoError(t, err)
+
+		assert.Contains(t, mock.GetWrittenRequest(), "mg "+longKey+" v\r\n")
+	})
+}
+
+func TestConnection_Close_DiscardsPendingWrites(t *testing.T) {
+	conn, mock := newMockConnection("HD\r\n")
+
+	_, err := conn.Execute(context.Background(), getReq("key"))
+	require.NoError(t, err)
+	assert.False(t, conn.HasPendingWrites(), "Execute must flush before returning")
+
+	_, err = conn.Writer.WriteString("mn\r\n")
+	require.NoError(t, err)
+	assert.True(t, conn.HasPendingWrites())
+
+	require.NoError(t, conn.Close())
+	assert.Equal(t, "mg key v\r\n", mock.GetWrittenRequest())
+}
+
 func TestConnection_ExecuteBatch_Empty(t *testing.T) {
 	conn, mock := newMockConnection()
 
@@ -120,6 +428,18 @@ func TestConnection_ExecuteStats(t *testing.T) {
 	})
 }
 
+func TestConnection_Quit(t *testing.T) {
+	conn, mock := newMockConnection()
+
+	require.NoError(t, conn.Quit(context.Background()))
+	assert.Equal(t, "quit\r\n", mock.GetWrittenRequest())
+	assert.True(t, conn.IsClosed())
+
+	_, err := conn.Execute(context.Background(), getReq("key"))
+	require.ErrorIs(t, err, ErrConnectionQuit)
+	assert.Equal(t, "quit\r\n", mock.GetWrittenRequest(), "nothing must be written after quit")
+}
+
 func TestConnection_Ping(t *testing.T) {
 	t.Run("success", func(t *testing.T) {
 		conn, mock := newMockConnection("MN\r\n")
@@ -137,3 +457,147 @@ func TestConnection_Ping(t *testing.T) {
 		require.Error(t, conn.Ping(context.Background()))
 	})
 }
+
+// BenchmarkConnection_Execute compares a single-key get through Execute with
+// the same get sent as a batch of one, which also allocates the response slice.
+func BenchmarkConnection_Execute(b *testing.B) {
+	b.Run("Get", func(b *testing.B) {
+		mock := testutils.NewConnectionMock("VA 5\r\nhello\r\n")
+		mock.EnableCycling()
+		conn := NewConnection(mock, time.Second)
+		req := getReq("testkey")
+		b.ReportAllocs()
+
+		for b.Loop() {
+			if _, err := conn.Execute(ctx, req); err != nil {
+				b.Fatal(err)
+			}
+		}
+	})
+
+	b.Run("Get_BatchOfOne", func(b *testing.B) {
+		mock := testutils.NewConnectionMock("VA 5\r\nhello\r\n", "MN\r\n")
+		mock.EnableCycling()
+		conn := NewConnection(mock, time.Second)
+		reqs := []*meta.Request{getReq("testkey")}
+		b.ReportAllocs()
+
+		for b.Loop() {
+			if _, err := conn.ExecuteBatch(ctx, reqs); err != nil {
+				b.Fatal(err)
+			}
+		}
+	})
+}
+
+func TestConnection_Watch(t *testing.T) {
+	t.Run("dedicates the connection to the events", func(t *testing.T) {
+		conn, mock := newMockConnection(
+			"OK\r\n",
+			"ts=1700000000.000001 gid=1 type=item_get key=foo status=found\r\n",
+			"ts=1700000000.000002 gid=2 type=item_store key=foo status=stored\r\n",
+		)
+
+		var types []string
+		for event, err := range conn.Watch(context.Background(), "fetchers", "mutations") {
+			require.NoError(t, err)
+			types = append(types, event.Type)
+			if len(types) == 2 {
+				break
+			}
+		}
+
+		assert.Equal(t, []string{"item_get", "item_store"}, types)
+		assert.Equal(t, "watch fetchers mutations\r\n", mock.GetWrittenRequest())
+
+		_, err := conn.Execute(context.Background(), getReq("key"))
+		require.ErrorIs(t, err, ErrConnectionInWatchMode)
+		for _, err := range conn.Watch(context.Background()) {
+			require.ErrorIs(t, err, ErrConnectionInWatchMode)
+		}
+	})
+
+	t.Run("invalid event is rejected before writing", func(t *testing.T) {
+		conn, mock := newMockConnection("EN\r\n")
+
+		for _, err := range conn.Watch(context.Background(), "fetchers\r\nflush_all") {
+			var keyErr *meta.InvalidKeyError
+			require.ErrorAs(t, err, &keyErr)
+			assert.False(t, meta.ShouldCloseConnection(err))
+		}
+
+		assert.Empty(t, mock.GetWrittenRequest())
+		resp, err := conn.Execute(context.Background(), getReq("key"))
+		require.NoError(t, err)
+		assert.Equal(t, meta.StatusEN, resp.Status)
+	})
+
+	t.Run("rejected watch leaves the connection usable", func(t *testing.T) {
+		conn, _ := newMockConnection("ERROR\r\n", "EN\r\n")
+
+		for _, err := range conn.Watch(context.Background()) {
+			var genericErr *meta.GenericError
+			require.ErrorAs(t, err, &genericErr)
+		}
+
+		resp, err := conn.Execute(context.Background(), getReq("key"))
+		require.NoError(t, err)
+		assert.Equal(t, meta.StatusEN, resp.Status)
+	})
+}
+
+// failingWriteConn is a mock connection whose writes fail with a connection
+// reset once limit bytes were written.
+type failingWriteConn struct {
+	*testutils.ConnectionMock
+	limit int
+}
+
+func (c *failingWriteConn) Write(p []byte) (int, error) {
+	if len(p) <= c.limit {
+		c.limit -= len(p)
+		return c.ConnectionMock.Write(p)
+	}
+	n, _ := c.ConnectionMock.Write(p[:c.limit])
+	c.limit = 0
+	return n, syscall.ECONNRESET
+}
+
+func TestConnection_Execute_PartialWrite(t *testing.T) {
+	value := []byte(strings.Repeat("x", 10000))
+	req := meta.NewRequest(meta.CmdSet, "key", value)
+
+	t.Run("reset while sending the value", func(t *testing.T) {
+		conn := NewConnection(&failingWriteConn{ConnectionMock: testutils.NewConnectionMock(), limit: 5000}, time.Second)
+
+		_, err := conn.Execute(context.Background(), req)
+
+		var partialErr *PartialWriteError
+		require.ErrorAs(t, err, &partialErr)
+		assert.Equal(t, 5000, partialErr.Written)
+		assert.Equal(t, len("ms key 10000\r\n")+10000+len("\r\n"), partialErr.Total)
+		assert.ErrorIs(t, err, syscall.ECONNRESET)
+		assert.True(t, meta.ShouldCloseConnection(err))
+	})
+
+	t.Run("nothing sent", func(t *testing.T) {
+		conn := NewConnection(&failingWriteConn{ConnectionMock: testutils.NewConnectionMock(), limit: 0}, time.Second)
+
+		_, err := conn.Execute(context.Background(), req)
+
+		require.ErrorIs(t, err, syscall.ECONNRESET)
+		var partialErr *PartialWriteError
+		assert.NotErrorAs(t, err, &partialErr)
+	})
+
+	t.Run("streamed request", func(t *testing.T) {
+		conn := NewConnection(&failingWriteConn{ConnectionMock: testutils.NewConnectionMock(), limit: 5000}, time.Second)
+
+		_, _, err := conn.ExecuteStream(context.Background(), req, 1024)
+
+		var partialErr *PartialWriteError
+		require.ErrorAs(t, err, &partialErr)
+		assert.Equal(t, 5000, partialErr.Written)
+		assert.ErrorIs(t, err, syscall.ECONNRESET)
+	})
+}
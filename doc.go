@@ -15,6 +15,23 @@
 //	_ = client.Set(ctx, memcache.Item{Key: "mykey", Value: []byte("hello")})
 //	item, _ := client.Get(ctx, "mykey")
 //
+// # Cancellation
+//
+// An operation is bounded by its socket deadline: the earlier of the context
+// deadline and Config.Timeout. A context that is already done fails the
+// operation before anything is sent, but canceling a context without a
+// deadline doesn't interrupt the I/O in flight: the operation runs until it
+// completes or Config.Timeout expires. This keeps the hot path free of a
+// per-operation cancellation watcher.
+//
+// All the client's operations are safe to abandon. An operation that fails
+// mid-flight (timeout, I/O or protocol error), like a [Client.MultiGetSeq]
+// iteration stopped early, leaves its connection in an unknown state, e.g.
+// with unread responses: the connection is closed instead of being returned
+// to the pool, so a later operation never reads a response that isn't its
+// own. A [Connection] used directly must be closed the same way after such a
+// failure (see meta.ShouldCloseConnection).
+//
 // # Building Blocks
 //
 // The client is assembled from smaller pieces that can be used on their own to
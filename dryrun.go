@@ -0,0 +1,116 @@
+package memcache
+
+import (
+	"bytes"
+	"context"
+	"sync"
+
+	"github.com/pior/memcache/meta"
+)
+
+// DryRunExecutor is a BatchExecutor that validates and serializes requests
+// without sending them, to unit-test flag usage or inspect the exact wire
+// format of commands:
+//
+//	dry := &memcache.DryRunExecutor{}
+//	_ = memcache.NewCommands(dry).NewSet("key").Value(v).TTL(ttl).Do(ctx)
+//	fmt.Printf("%q\n", dry.LastCommand()) // "ms key 5 T60\r\nhello\r\n"
+//
+// A request failing validation (e.g. an invalid key) returns the error, as a
+// connection would, and is not recorded. Each request gets the nominal
+// response of its command: a miss (EN) for mg, a counter value of 0 for ma,
+// and HD for the others.
+//
+// The zero value is ready to use and safe for concurrent use. Client options
+// (Config.DefaultTTL, Config.KeyTransformer...) don't apply: the executor
+// sees the requests of a plain Commands.
+type DryRunExecutor struct {
+	// MaxKeyLength is the longest key accepted. Zero means meta.MaxKeyLength.
+	MaxKeyLength int
+
+	mu       sync.Mutex
+	commands [][]byte
+}
+
+var _ BatchExecutor = (*DryRunExecutor)(nil)
+
+// Execute implements the Executor interface.
+func (d *DryRunExecutor) Execute(_ context.Context, req *meta.Request) (*meta.Response, error) {
+	if err := d.record(req); err != nil {
+		return nil, err
+	}
+	return dryRunResponse(req), nil
+}
+
+// ExecuteBatch implements the BatchExecutor interface. Keys are all validated
+// before any request is recorded, as by Connection.ExecuteBatch.
+func (d *DryRunExecutor) ExecuteBatch(_ context.Context, reqs []*meta.Request) ([]*meta.Response, error) {
+	if err := meta.ValidateBatchKeys(reqs, d.maxKeyLength()); err != nil {
+		return nil, err
+	}
+
+	responses := make([]*meta.Response, 0, len(reqs))
+	for _, req := range reqs {
+		if err := d.record(req); err != nil {
+			return responses, err
+		}
+		responses = append(responses, dryRunResponse(req))
+	}
+	return responses, nil
+}
+
+// Commands returns the wire bytes of the requests executed so far, in order.
+func (d *DryRunExecutor) Commands() [][]byte {
+	d.mu.Lock()
+	defer d.mu.Unlock()
+	return append([][]byte(nil), d.commands...)
+}
+
+// LastCommand returns the wire bytes of the last request executed, nil if
+// none was.
+func (d *DryRunExecutor) LastCommand() []byte {
+	d.mu.Lock()
+	defer d.mu.Unlock()
+	if len(d.commands) == 0 {
+		return nil
+	}
+	return d.commands[len(d.commands)-1]
+}
+
+// Reset forgets the requests executed so far.
+func (d *DryRunExecutor) Reset() {
+	d.mu.Lock()
+	defer d.mu.Unlock()
+	d.commands = nil
+}
+
+func (d *DryRunExecutor) record(req *meta.Request) error {
+	var buf bytes.Buffer
+	if err := meta.WriteRequestWithKeyLimit(&buf, req, d.maxKeyLength()); err != nil {
+		return err
+	}
+
+	d.mu.Lock()
+	defer d.mu.Unlock()
+	d.commands = append(d.commands, buf.Bytes())
+	return nil
+}
+
+func (d *DryRunExecutor) maxKeyLength() int {
+	if d.MaxKeyLength > 0 {
+		return d.MaxKeyLength
+	}
+	return meta.MaxKeyLength
+}
+
+// dryRunResponse returns the nominal response to req.
+func dryRunResponse(req *meta.Request) *meta.Response {
+	switch req.Command {
+	case meta.CmdGet:
+		return &meta.Response{Status: meta.StatusEN}
+	case meta.CmdArithmetic:
+		return &meta.Response{Status: meta.StatusVA, Data: []byte("0")}
+	default:
+		return &meta.Response{Status: meta.StatusHD}
+	}
+}
@@ -0,0 +1,61 @@
+package memcache
+
+import (
+	"context"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/pior/memcache/meta"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestDryRunExecutor(t *testing.T) {
+	ctx := context.Background()
+
+	t.Run("records the wire format", func(t *testing.T) {
+		dry := &DryRunExecutor{}
+		commands := NewCommands(dry)
+
+		require.NoError(t, commands.NewSet("key").Value([]byte("hello")).TTL(ExpiresIn(time.Minute)).Do(ctx))
+		assert.Equal(t, "ms key 5 T60\r\nhello\r\n", string(dry.LastCommand()))
+
+		item, err := commands.Get(ctx, "key")
+		require.NoError(t, err)
+		assert.False(t, item.Found)
+
+		n, err := commands.Increment(ctx, "hits", 1, NoTTL)
+		require.NoError(t, err)
+		assert.Equal(t, int64(0), n)
+
+		var wire []string
+		for _, command := range dry.Commands() {
+			wire = append(wire, string(command))
+		}
+		assert.Equal(t, []string{"ms key 5 T60\r\nhello\r\n", "mg key v\r\n", "ma hits v D1 J1 N0\r\n"}, wire)
+
+		dry.Reset()
+		assert.Nil(t, dry.LastCommand())
+	})
+
+	t.Run("invalid key is not recorded", func(t *testing.T) {
+		dry := &DryRunExecutor{}
+
+		_, err := NewCommands(dry).Get(ctx, strings.Repeat("k", 251))
+
+		var keyErr *meta.InvalidKeyError
+		require.ErrorAs(t, err, &keyErr)
+		assert.Empty(t, dry.Commands())
+	})
+
+	t.Run("batch", func(t *testing.T) {
+		dry := &DryRunExecutor{}
+
+		items, err := NewBatchCommands(dry).MultiGet(ctx, []string{"k1", "k2"})
+
+		require.NoError(t, err)
+		assert.Len(t, items, 2)
+		assert.Len(t, dry.Commands(), 2)
+	})
+}
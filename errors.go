@@ -1,6 +1,10 @@
 package memcache
 
-import "errors"
+import (
+	"errors"
+	"fmt"
+	"strconv"
+)
 
 // Sentinel errors returned by the client. Check them with errors.Is; they may
 // be wrapped with additional context.
@@ -9,6 +13,21 @@ var (
 	// Add on an existing key, or replace/append/prepend on a missing key.
 	ErrNotStored = errors.New("memcache: item not stored")
 
+	// ErrCASMismatch is returned when a compare-and-swap store is not applied
+	// because the item was modified since its CAS value was read.
+	ErrCASMismatch = errors.New("memcache: CAS mismatch")
+
+	// ErrStaleWrite is returned when a store with SetBuilder.Invalidate is
+	// not applied: its CAS value is neither the item's nor an older one. It
+	// wraps ErrCASMismatch.
+	ErrStaleWrite = fmt.Errorf("memcache: stale write: %w", ErrCASMismatch)
+
+	// ErrCacheMiss is returned by GetBuilder.Do on a miss when the key is
+	// required (see GetBuilder.Required). Other reads report a miss with
+	// Item.Found=false. DeleteBuilder.Do returns it for a missing key that is
+	// required (see DeleteBuilder.Required).
+	ErrCacheMiss = errors.New("memcache: cache miss")
+
 	// ErrClientClosed is returned by operations issued after Client.Close.
 	ErrClientClosed = errors.New("memcache: client is closed")
 
@@ -17,6 +36,33 @@ var (
 
 	// ErrPoolClosed is returned by Pool.Acquire after the pool has been closed.
 	ErrPoolClosed = errors.New("memcache: pool is closed")
+
+	// ErrNonNumericValue is returned by increments and decrements of an item
+	// whose value is not a number, e.g. a key holding a string rather than a
+	// counter. It wraps the server's *meta.ClientError.
+	ErrNonNumericValue = errors.New("memcache: value is not a number")
+
+	// ErrProtocolDesync is returned when a response doesn't echo the opaque
+	// token (O flag) of its request, or when an operation starts on a
+	// connection where not every request written had its response read (see
+	// Connection.CommandCounts): the next response would answer another
+	// request, so the connection is out of sync with the server, and is
+	// closed.
+	ErrProtocolDesync = errors.New("memcache: protocol desync")
+
+	// ErrAuthFailed is returned when the server rejects the credentials of
+	// Config.Username and Config.Password. It wraps the server's
+	// *meta.ClientError.
+	ErrAuthFailed = errors.New("memcache: authentication failed")
+
+	// ErrConnectionQuit is returned by the operations of a connection after
+	// Connection.Quit: the server closes it.
+	ErrConnectionQuit = errors.New("memcache: connection was quit")
+
+	// ErrConnectionInWatchMode is returned by the operations of a connection
+	// streaming watch events (see Connection.Watch): the events pushed by the
+	// server would be read as responses.
+	ErrConnectionInWatchMode = errors.New("memcache: connection is in watch mode")
 )
 
 // Operation names used in OpError.Op for operations that are not a single
@@ -42,16 +88,18 @@ const (
 //	}
 //	if errors.Is(err, context.DeadlineExceeded) { ... }
 type OpError struct {
-	// Op is the operation that failed: a meta protocol command code
-	// ("mg", "ms", ...) or one of the Op* constants (OpBatch, OpStats).
+	// Op is the operation that failed: a protocol command code
+	// ("mg", "ms", "cache_memlimit", ...) or one of the Op* constants
+	// (OpBatch, OpStats).
 	Op string
 
 	// Key is the cache key, when the operation targets a single key.
 	//
-	// The key is deliberately NOT part of the Error() message: keys often
+	// The key is NOT part of the Error() message by default: keys often
 	// carry user identifiers (PII) that don't belong in logs, and embedding
 	// them would give error messages unbounded cardinality. Read this field
-	// explicitly (via errors.As) when the key is wanted.
+	// explicitly (via errors.As) when the key is wanted, or opt in with
+	// Config.KeyInErrors.
 	Key string
 
 	// Server is the address of the server the operation was routed to.
@@ -60,10 +108,16 @@ type OpError struct {
 	// Err is the underlying cause: a connection or timeout error, a
 	// gobreaker state error, a meta protocol error, etc.
 	Err error
+
+	// keyInMessage adds Key to the Error() message (Config.KeyInErrors).
+	keyInMessage bool
 }
 
 func (e *OpError) Error() string {
 	s := "memcache: " + e.Op
+	if e.keyInMessage && e.Key != "" {
+		s += " key " + strconv.Quote(e.Key)
+	}
 	if e.Server != "" {
 		s += " on " + e.Server
 	}
@@ -73,3 +127,60 @@ func (e *OpError) Error() string {
 func (e *OpError) Unwrap() error {
 	return e.Err
 }
+
+// PartialWriteError reports a request, typically a store of a large value,
+// that was only partly sent when the connection failed: the server saw a
+// truncated request, so the connection is closed rather than reused. The
+// item is not stored, as the server drops the incomplete data block.
+type PartialWriteError struct {
+	Written int   // bytes of the request sent before the failure
+	Total   int   // size of the request in bytes
+	Err     error // underlying write error
+}
+
+func (e *PartialWriteError) Error() string {
+	return fmt.Sprintf("memcache: partial write: %d of %d bytes sent: %v", e.Written, e.Total, e.Err)
+}
+
+func (e *PartialWriteError) Unwrap() error {
+	return e.Err
+}
+
+// ShouldCloseConnection returns true: the connection is mid-request.
+func (e *PartialWriteError) ShouldCloseConnection() bool {
+	return true
+}
+
+// UnsupportedRequestError reports a request that the operation doesn't
+// support, e.g. mn in a batch, whose own mn ends it. The request is rejected
+// before anything is written: the connection is unaffected, and the failure
+// says nothing about the server's health.
+type UnsupportedRequestError struct {
+	Message string
+}
+
+func (e *UnsupportedRequestError) Error() string {
+	return "memcache: " + e.Message
+}
+
+// ShouldCloseConnection returns false: nothing was written.
+func (e *UnsupportedRequestError) ShouldCloseConnection() bool {
+	return false
+}
+
+// ChunkManifestError reports a key read with BatchCommands.GetChunked whose
+// value is not a chunk manifest, e.g. a key stored with Set. The response was
+// fully read: the connection is unaffected.
+type ChunkManifestError struct {
+	Manifest []byte // the value found under the key
+}
+
+func (e *ChunkManifestError) Error() string {
+	return fmt.Sprintf("memcache: invalid chunk manifest: %q", e.Manifest)
+}
+
+// ShouldCloseConnection returns false: the value is invalid, not the
+// connection state.
+func (e *ChunkManifestError) ShouldCloseConnection() bool {
+	return false
+}
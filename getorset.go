@@ -0,0 +1,87 @@
+package memcache
+
+import (
+	"context"
+	"sync"
+)
+
+// GetOrSet returns the item stored under key. On a miss, it computes the value
+// with fill, stores it with the given TTL and returns it.
+//
+// Concurrent misses for the same key within this Commands are coalesced: a
+// single call runs fill and stores the value, the others wait and share its
+// result (or error), Value included; that Value comes from fill, so it must
+// not be returned to Config.BufferPool. This prevents dogpiling within the
+// process; across processes, rely on the server instead (see SetBuilder.Vivify
+// and the W flag of the meta protocol).
+//
+// fill runs with the context of the call that triggered it. A waiting call
+// whose context is done returns the context error without waiting further.
+func (c *Commands) GetOrSet(ctx context.Context, key string, ttl TTL, fill func(ctx context.Context) ([]byte, error)) (Item, error) {
+	item, err := c.Get(ctx, key)
+	if err != nil || item.Found {
+		return item, err
+	}
+
+	return c.flights.do(ctx, key, func() (Item, error) {
+		value, err := fill(ctx)
+		if err != nil {
+			return Item{}, err
+		}
+
+		item := Item{Key: key, Value: value, TTL: ttl}
+		if err := c.Set(ctx, item); err != nil {
+			return Item{}, err
+		}
+
+		item.Found = true
+		return item, nil
+	})
+}
+
+// flightGroup coalesces concurrent calls for the same key into a single
+// execution. The zero value is ready to use.
+type flightGroup struct {
+	mu      sync.Mutex
+	flights map[string]*flight
+}
+
+// flight is an execution in progress; item and err are set before done is
+// closed.
+type flight struct {
+	done chan struct{}
+	item Item
+	err  error
+}
+
+// do runs fn, unless a call for the same key is already running: then it
+// waits for that call and returns its result.
+func (g *flightGroup) do(ctx context.Context, key string, fn func() (Item, error)) (Item, error) {
+	g.mu.Lock()
+	if f, ok := g.flights[key]; ok {
+		g.mu.Unlock()
+		select {
+		case <-f.done:
+			return f.item, f.err
+		case <-ctx.Done():
+			return Item{}, ctx.Err()
+		}
+	}
+
+	if g.flights == nil {
+		g.flights = make(map[string]*flight)
+	}
+	f := &flight{done: make(chan struct{})}
+	g.flights[key] = f
+	g.mu.Unlock()
+
+	defer func() {
+		g.mu.Lock()
+		delete(g.flights, key)
+		g.mu.Unlock()
+		close(f.done)
+	}()
+
+	f.item, f.err = fn()
+	return f.item, f.err
+}
@@ -0,0 +1,143 @@
+package memcache
+
+import (
+	"context"
+	"errors"
+	"sync"
+	"sync/atomic"
+	"testing"
+	"testing/synctest"
+
+	"github.com/pior/memcache/meta"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+// memoryExecutor is an in-memory Executor answering mg and ms.
+type memoryExecutor struct {
+	mu    sync.Mutex
+	items map[string][]byte
+	sets  atomic.Int32
+}
+
+func (e *memoryExecutor) Execute(_ context.Context, req *meta.Request) (*meta.Response, error) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	switch req.Command {
+	case meta.CmdGet:
+		value, ok := e.items[req.Key]
+		if !ok {
+			return &meta.Response{Status: meta.StatusEN}, nil
+		}
+		return &meta.Response{Status: meta.StatusVA, Data: value}, nil
+	case meta.CmdSet:
+		e.sets.Add(1)
+		if e.items == nil {
+			e.items = make(map[string][]byte)
+		}
+		e.items[req.Key] = req.Data
+		return &meta.Response{Status: meta.StatusHD}, nil
+	}
+	return nil, errors.New("unsupported command " + string(req.Command))
+}
+
+func TestCommands_GetOrSet(t *testing.T) {
+	t.Run("hit skips fill", func(t *testing.T) {
+		executor := &memoryExecutor{items: map[string][]byte{"key": []byte("cached")}}
+		commands := NewCommands(executor)
+
+		item, err := commands.GetOrSet(context.Background(), "key", NoTTL, func(context.Context) ([]byte, error) {
+			t.Fatal("fill must not run on a hit")
+			return nil, nil
+		})
+		require.NoError(t, err)
+		assert.Equal(t, "cached", string(item.Value))
+		assert.True(t, item.Found)
+	})
+
+	t.Run("miss fills and stores", func(t *testing.T) {
+		executor := &memoryExecutor{}
+		commands := NewCommands(executor)
+
+		item, err := commands.GetOrSet(context.Background(), "key", NoTTL, func(context.Context) ([]byte, error) {
+			return []byte("computed"), nil
+		})
+		require.NoError(t, err)
+		assert.Equal(t, "computed", string(item.Value))
+		assert.Equal(t, "computed", string(executor.items["key"]))
+	})
+
+	t.Run("fill error is returned and nothing is stored", func(t *testing.T) {
+		executor := &memoryExecutor{}
+		commands := NewCommands(executor)
+		fillErr := errors.New("backend down")
+
+		_, err := commands.GetOrSet(context.Background(), "key", NoTTL, func(context.Context) ([]byte, error) {
+			return nil, fillErr
+		})
+		require.ErrorIs(t, err, fillErr)
+		assert.Zero(t, executor.sets.Load())
+	})
+
+	t.Run("concurrent misses share one fill", func(t *testing.T) {
+		synctest.Test(t, func(t *testing.T) {
+			executor := &memoryExecutor{}
+			commands := NewCommands(executor)
+
+			var fills atomic.Int32
+			release := make(chan struct{})
+			fill := func(context.Context) ([]byte, error) {
+				fills.Add(1)
+				<-release
+				return []byte("computed"), nil
+			}
+
+			const callers = 10
+			items := make([]Item, callers)
+			var wg sync.WaitGroup
+			wg.Add(callers)
+			for i := range callers {
+				go func() {
+					defer wg.Done()
+					item, err := commands.GetOrSet(context.Background(), "key", NoTTL, fill)
+					assert.NoError(t, err)
+					items[i] = item
+				}()
+			}
+
+			// Every caller has missed and is now blocked: in fill or waiting.
+			synctest.Wait()
+			close(release)
+			wg.Wait()
+
+			assert.Equal(t, int32(1), fills.Load())
+			assert.Equal(t, int32(1), executor.sets.Load())
+			for _, item := range items {
+				assert.Equal(t, "computed", string(item.Value))
+			}
+		})
+	})
+
+	t.Run("waiter stops when its context is done", func(t *testing.T) {
+		synctest.Test(t, func(t *testing.T) {
+			commands := NewCommands(&memoryExecutor{})
+
+			release := make(chan struct{})
+			defer close(release)
+			go commands.GetOrSet(context.Background(), "key", NoTTL, func(context.Context) ([]byte, error) {
+				<-release
+				return []byte("computed"), nil
+			})
+			synctest.Wait()
+
+			ctx, cancel := context.WithCancel(context.Background())
+			cancel()
+			_, err := commands.GetOrSet(ctx, "key", NoTTL, func(context.Context) ([]byte, error) {
+				t.Fatal("fill must run once")
+				return nil, nil
+			})
+			require.ErrorIs(t, err, context.Canceled)
+		})
+	})
+}
@@ -0,0 +1,29 @@
+package memcache
+
+import "time"
+
+// Hooks are optional callbacks observing the client's connection lifecycle
+// and slow operations, e.g. to export metrics or traces. Connection churn is a
+// common root cause of latency spikes in pooled deployments.
+//
+// Nil callbacks are skipped. Callbacks run synchronously on the goroutine
+// establishing the connection or running the operation, so they must be fast
+// and safe for concurrent use.
+type Hooks struct {
+	// OnConnect is called when a new connection to addr is established.
+	OnConnect func(addr string)
+
+	// OnConnectError is called when establishing a connection to addr fails.
+	OnConnectError func(addr string, err error)
+
+	// OnReconnect is called, after OnConnect, when the new connection replaces
+	// one that was closed because of a connection or protocol error.
+	OnReconnect func(addr string)
+
+	// OnSlowOp is called when an operation on addr takes longer than
+	// Config.SlowOpThreshold, including the wait for a pooled connection.
+	// Op is the meta command (e.g. "mg") or OpBatch, keyLen is the length of
+	// the key (zero for a batch). Surfaces the individual slow requests behind
+	// tail latency, to correlate them with server pauses or network blips.
+	OnSlowOp func(addr, op string, keyLen int, elapsed time.Duration)
+}
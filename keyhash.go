@@ -0,0 +1,42 @@
+package memcache
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+
+	"github.com/pior/memcache/meta"
+)
+
+// hashedKeyPrefix marks the keys replaced by their digest in HashLongKeys.
+const hashedKeyPrefix = "sha256:"
+
+// HashLongKeys returns a Config.KeyTransformer for applications whose keys
+// can exceed the server's key limit, e.g. full URLs: keys of up to threshold
+// bytes are sent verbatim, longer ones are replaced by "sha256:" followed by
+// the hex SHA-256 digest of the key (71 bytes). A threshold <= 0 means
+// meta.MaxKeyLength.
+//
+// As for any KeyTransformer, the digest is computed for every request, so
+// items stored under a long key are read back with the same key. The
+// original key can't be recovered from the stored one (e.g. in a metadump),
+// and two long keys with the same digest would share an item: with SHA-256,
+// the probability of a collision is negligible.
+//
+// It can't be combined with base64 keys (b flag): KeyTransformer receives
+// them encoded, and the digest that replaces a long one is not valid base64,
+// so the server rejects the request. Hash such keys before encoding them.
+//
+// To combine it with another transformation, e.g. a namespace, call it from
+// your own KeyTransformer.
+func HashLongKeys(threshold int) func(key string) string {
+	if threshold <= 0 {
+		threshold = meta.MaxKeyLength
+	}
+	return func(key string) string {
+		if len(key) <= threshold {
+			return key
+		}
+		sum := sha256.Sum256([]byte(key))
+		return hashedKeyPrefix + hex.EncodeToString(sum[:])
+	}
+}
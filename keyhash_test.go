@@ -0,0 +1,39 @@
+package memcache
+
+import (
+	"context"
+	"strings"
+	"testing"
+
+	"github.com/pior/memcache/internal/testutils"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestHashLongKeys(t *testing.T) {
+	hash := HashLongKeys(10)
+
+	assert.Equal(t, "short", hash("short"))
+	assert.Equal(t, "0123456789", hash("0123456789"))
+	assert.Equal(t, "sha256:ee29eb4a8725678278ac439cf7abfd2a849cdc7378a6b6316017b81c51d720e7", hash("01234567890"))
+	assert.NotEqual(t, hash("01234567890"), hash("01234567891"))
+
+	long := strings.Repeat("k", 1000)
+	assert.Len(t, HashLongKeys(0)(long), 71)
+	assert.Equal(t, strings.Repeat("k", 250), HashLongKeys(0)(strings.Repeat("k", 250)))
+}
+
+func TestClient_HashLongKeys(t *testing.T) {
+	mockConn := testutils.NewConnectionMock("HD\r\n", "VA 1\r\nv\r\n")
+	client := newTestClientWithConfig(t, mockConn, Config{KeyTransformer: HashLongKeys(0)})
+	key := "https://example.com/" + strings.Repeat("path/", 60)
+	stored := HashLongKeys(0)(key)
+
+	require.NoError(t, client.Set(context.Background(), Item{Key: key, Value: []byte("v")}))
+	item, err := client.Get(context.Background(), key)
+	require.NoError(t, err)
+
+	assert.True(t, item.Found)
+	assert.Equal(t, key, item.Key)
+	assertRequest(t, mockConn, "ms "+stored+" 1\r\nv\r\nmg "+stored+" v\r\n")
+}
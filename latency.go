@@ -0,0 +1,105 @@
+package memcache
+
+import (
+	"math/bits"
+	"sync/atomic"
+	"time"
+)
+
+// latencySubBits is the number of bits of precision kept by the latency
+// histogram: each power-of-two range of durations is split into
+// 2^latencySubBits buckets, bounding the error of a percentile to 1/16th
+// (6.25%) of its value.
+const latencySubBits = 4
+
+const (
+	latencySubBuckets = 1 << latencySubBits
+	latencyBuckets    = (64 - latencySubBits + 1) * latencySubBuckets
+)
+
+// LatencySnapshot summarizes the latencies of the operations recorded by the
+// client, see Config.LatencyHistogram. Percentiles are the upper bound of the
+// histogram bucket they fall in, within 6.25% of the actual value.
+type LatencySnapshot struct {
+	Count uint64 // number of operations recorded
+	P50   time.Duration
+	P90   time.Duration
+	P99   time.Duration
+	Max   time.Duration // exact
+}
+
+// latencyHistogram is a log-linear histogram of operation latencies, in the
+// spirit of HdrHistogram: recording is a couple of atomic adds, cheap enough
+// to stay always on, and takes no lock.
+type latencyHistogram struct {
+	counts [latencyBuckets]atomic.Uint64
+	max    atomic.Int64
+}
+
+// record adds an operation that took d.
+func (h *latencyHistogram) record(d time.Duration) {
+	d = max(d, 0)
+	h.counts[latencyBucket(uint64(d))].Add(1)
+	for {
+		current := h.max.Load()
+		if int64(d) <= current || h.max.CompareAndSwap(current, int64(d)) {
+			return
+		}
+	}
+}
+
+// addTo adds the bucket counts of h to counts, and returns its maximum.
+func (h *latencyHistogram) addTo(counts *[latencyBuckets]uint64) time.Duration {
+	for i := range h.counts {
+		counts[i] += h.counts[i].Load()
+	}
+	return time.Duration(h.max.Load())
+}
+
+// latencySnapshot computes the snapshot of the merged bucket counts.
+func latencySnapshot(counts *[latencyBuckets]uint64, maxLatency time.Duration) LatencySnapshot {
+	snapshot := LatencySnapshot{Max: maxLatency}
+	for _, n := range counts {
+		snapshot.Count += n
+	}
+	if snapshot.Count == 0 {
+		return snapshot
+	}
+
+	percentile := func(q float64) time.Duration {
+		rank := max(uint64(q*float64(snapshot.Count)+0.5), 1)
+		var seen uint64
+		for i, n := range counts {
+			seen += n
+			if seen >= rank {
+				return min(time.Duration(latencyBucketMax(i)), maxLatency)
+			}
+		}
+		return maxLatency
+	}
+	snapshot.P50 = percentile(0.50)
+	snapshot.P90 = percentile(0.90)
+	snapshot.P99 = percentile(0.99)
+	return snapshot
+}
+
+// latencyBucket returns the index of the bucket of v: values below
+// latencySubBuckets have a bucket each, larger ones share a bucket with the
+// values having the same latencySubBits+1 most significant bits.
+func latencyBucket(v uint64) int {
+	if v < latencySubBuckets {
+		return int(v)
+	}
+	shift := bits.Len64(v) - latencySubBits - 1
+	return (shift+1)*latencySubBuckets + int(v>>shift) - latencySubBuckets
+}
+
+// latencyBucketMax returns the largest value of bucket i.
+func latencyBucketMax(i int) uint64 {
+	if i < latencySubBuckets {
+		return uint64(i)
+	}
+	shift := i/latencySubBuckets - 1
+	mantissa := uint64(i%latencySubBuckets + latencySubBuckets)
+	return (mantissa+1)<<shift - 1
+}
@@ -0,0 +1,72 @@
+package memcache
+
+import (
+	"context"
+	"testing"
+	"time"
+
+	"github.com/pior/memcache/internal/testutils"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestLatencyHistogram(t *testing.T) {
+	t.Run("buckets cover their values", func(t *testing.T) {
+		for _, v := range []uint64{0, 1, 15, 16, 17, 31, 32, 33, 1000, 123456789, 1<<63 - 1, 1<<64 - 1} {
+			i := latencyBucket(v)
+			require.Less(t, i, latencyBuckets, v)
+			assert.GreaterOrEqual(t, latencyBucketMax(i), v)
+			if i > 0 {
+				assert.Less(t, latencyBucketMax(i-1), v)
+			}
+		}
+	})
+
+	t.Run("percentiles", func(t *testing.T) {
+		var h latencyHistogram
+		for i := 1; i <= 100; i++ {
+			h.record(time.Duration(i) * time.Millisecond)
+		}
+
+		var counts [latencyBuckets]uint64
+		snapshot := latencySnapshot(&counts, h.addTo(&counts))
+
+		assert.Equal(t, uint64(100), snapshot.Count)
+		assert.Equal(t, 100*time.Millisecond, snapshot.Max)
+		assert.InEpsilon(t, 50*time.Millisecond, snapshot.P50, 1.0/16)
+		assert.InEpsilon(t, 90*time.Millisecond, snapshot.P90, 1.0/16)
+		assert.InEpsilon(t, 99*time.Millisecond, snapshot.P99, 1.0/16)
+	})
+
+	t.Run("empty", func(t *testing.T) {
+		var counts [latencyBuckets]uint64
+		assert.Equal(t, LatencySnapshot{}, latencySnapshot(&counts, 0))
+	})
+}
+
+func TestClient_LatencySnapshot(t *testing.T) {
+	newClient := func(t *testing.T, enabled bool) *Client {
+		return newTestClientWithConfig(t, testutils.NewConnectionMock("EN\r\n", "HD\r\n", "MN\r\n"), Config{LatencyHistogram: enabled})
+	}
+
+	t.Run("records operations and batches", func(t *testing.T) {
+		client := newClient(t, true)
+
+		_, err := client.Get(context.Background(), "key")
+		require.NoError(t, err)
+		require.NoError(t, NewBatchCommands(client).MultiSet(context.Background(), []Item{{Key: "key", Value: []byte("v")}}))
+
+		snapshot := client.LatencySnapshot()
+		assert.Equal(t, uint64(2), snapshot.Count)
+		assert.LessOrEqual(t, snapshot.P99, snapshot.Max)
+	})
+
+	t.Run("disabled by default", func(t *testing.T) {
+		client := newClient(t, false)
+
+		_, err := client.Get(context.Background(), "key")
+		require.NoError(t, err)
+
+		assert.Equal(t, LatencySnapshot{}, client.LatencySnapshot())
+	})
+}
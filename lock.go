@@ -0,0 +1,70 @@
+package memcache
+
+import (
+	"bytes"
+	"context"
+	"errors"
+
+	"github.com/pior/memcache/meta"
+)
+
+// TryLock acquires the distributed lock key, unless another holder has it. It
+// stores token, a value unique to this holder (e.g. a random ID), with add
+// semantics: the store only succeeds if the key doesn't exist. It returns
+// true if the lock was acquired.
+//
+// The lock expires after ttl, so a crashed holder doesn't keep it forever:
+// ttl must exceed the time the lock is needed for. It is used as given,
+// without the client's DefaultTTL and TTLJitter. Release the lock with Unlock,
+// passing the same token.
+func (c *Commands) TryLock(ctx context.Context, key string, ttl TTL, token []byte) (bool, error) {
+	req := meta.NewRequest(meta.CmdSet, key, token).AddModeAdd()
+	if exptime := ttl.Expiration(); exptime != 0 {
+		req.AddTTL(exptime)
+	}
+
+	resp, err := c.executor.Execute(ctx, req)
+	if err != nil {
+		return false, err
+	}
+
+	if resp.HasError() {
+		return false, c.keyErr(req, resp.Error)
+	}
+
+	switch {
+	case resp.IsSuccess():
+		return true, nil
+	case resp.IsNotStored():
+		return false, nil
+	default:
+		return false, c.keyErr(req, statusErr(req, "lock", resp.Status))
+	}
+}
+
+// Unlock releases the lock key acquired by TryLock with token. The lock is
+// only deleted if it still holds token: a holder whose lock expired, and was
+// since acquired by another one, doesn't release the other holder's lock. It
+// returns true if the lock was released.
+//
+// The check and the delete are not a single command: the delete is
+// conditional on the CAS value read with the token, so it fails if the lock
+// changed hands in between.
+func (c *Commands) Unlock(ctx context.Context, key string, token []byte) (bool, error) {
+	item, err := c.NewGet(key).ReturnCAS().Do(ctx)
+	if err != nil {
+		return false, err
+	}
+	if !item.Found || !bytes.Equal(item.Value, token) {
+		return false, nil
+	}
+
+	result, err := c.NewDelete(key).CAS(item.CAS).Do(ctx)
+	if errors.Is(err, ErrCASMismatch) {
+		return false, nil
+	}
+	if err != nil {
+		return false, err
+	}
+	return result == DeleteResultDeleted, nil
+}
@@ -0,0 +1,75 @@
+package memcache
+
+import (
+	"context"
+	"testing"
+	"time"
+
+	"github.com/pior/memcache/internal/testutils"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestCommands_TryLock(t *testing.T) {
+	t.Run("acquired", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("HD\r\n")
+		client := newTestClient(t, mockConn)
+
+		acquired, err := client.TryLock(context.Background(), "lock", ExpiresIn(30*time.Second), []byte("token1"))
+
+		require.NoError(t, err)
+		assert.True(t, acquired)
+		assertRequest(t, mockConn, "ms lock 6 ME T30\r\ntoken1\r\n")
+	})
+
+	t.Run("held by another", func(t *testing.T) {
+		client := newTestClient(t, testutils.NewConnectionMock("NS\r\n"))
+
+		acquired, err := client.TryLock(context.Background(), "lock", ExpiresIn(30*time.Second), []byte("token1"))
+
+		require.NoError(t, err)
+		assert.False(t, acquired)
+	})
+}
+
+func TestCommands_Unlock(t *testing.T) {
+	t.Run("released", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("VA 6 c42\r\ntoken1\r\n", "HD\r\n")
+		client := newTestClient(t, mockConn)
+
+		released, err := client.Unlock(context.Background(), "lock", []byte("token1"))
+
+		require.NoError(t, err)
+		assert.True(t, released)
+		assertRequest(t, mockConn, "mg lock v c\r\nmd lock C42\r\n")
+	})
+
+	t.Run("held by another", func(t *testing.T) {
+		mockConn := testutils.NewConnectionMock("VA 6 c42\r\ntoken2\r\n")
+		client := newTestClient(t, mockConn)
+
+		released, err := client.Unlock(context.Background(), "lock", []byte("token1"))
+
+		require.NoError(t, err)
+		assert.False(t, released)
+		assertRequest(t, mockConn, "mg lock v c\r\n")
+	})
+
+	t.Run("changed hands before the delete", func(t *testing.T) {
+		client := newTestClient(t, testutils.NewConnectionMock("VA 6 c42\r\ntoken1\r\n", "EX\r\n"))
+
+		released, err := client.Unlock(context.Background(), "lock", []byte("token1"))
+
+		require.NoError(t, err)
+		assert.False(t, released)
+	})
+
+	t.Run("expired", func(t *testing.T) {
+		client := newTestClient(t, testutils.NewConnectionMock("EN\r\n"))
+
+		released, err := client.Unlock(context.Background(), "lock", []byte("token1"))
+
+		require.NoError(t, err)
+		assert.False(t, released)
+	})
+}
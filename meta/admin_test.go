@@ -0,0 +1,77 @@
+package meta
+
+import (
+	"bufio"
+	"errors"
+	"io"
+	"strings"
+	"testing"
+)
+
+func readOK(input string) error {
+	return ReadOKResponse(bufio.NewReader(strings.NewReader(input)))
+}
+
+func TestReadOKResponse(t *testing.T) {
+	if err := readOK("OK\r\n"); err != nil {
+		t.Errorf("unexpected error: %v", err)
+	}
+
+	var clientErr *ClientError
+	if err := readOK("CLIENT_ERROR bad value\r\n"); !errors.As(err, &clientErr) {
+		t.Errorf("error = %v (%T), want ClientError", err, err)
+	} else if clientErr.Message != "bad value" {
+		t.Errorf("message = %q, want %q", clientErr.Message, "bad value")
+	}
+
+	var serverErr *ServerError
+	if err := readOK("SERVER_ERROR busy\r\n"); !errors.As(err, &serverErr) {
+		t.Errorf("error = %v (%T), want ServerError", err, err)
+	}
+
+	var genericErr *GenericError
+	if err := readOK("ERROR\r\n"); !errors.As(err, &genericErr) {
+		t.Errorf("error = %v (%T), want GenericError", err, err)
+	}
+
+	var parseErr *ParseError
+	if err := readOK("STORED\r\n"); !errors.As(err, &parseErr) {
+		t.Errorf("error = %v (%T), want ParseError", err, err)
+	}
+
+	if err := readOK("OK"); !errors.Is(err, io.EOF) {
+		t.Errorf("error = %v, want io.EOF", err)
+	}
+}
+
+func TestReadOKResponse_AdminStatus(t *testing.T) {
+	tests := []struct {
+		input   string
+		status  string
+		message string
+		busy    bool
+	}{
+		{input: "BUSY currently processing crawler request\r\n", status: BusyPrefix, message: "currently processing crawler request", busy: true},
+		{input: "BADCLASS invalid class id\r\n", status: BadClassPrefix, message: "invalid class id"},
+		{input: "NOTFOUND\r\n", status: NotFoundPrefix},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.status, func(t *testing.T) {
+			var adminErr *AdminError
+			err := readOK(tt.input)
+			if !errors.As(err, &adminErr) {
+				t.Fatalf("error = %v (%T), want AdminError", err, err)
+			}
+			if adminErr.Status != tt.status || adminErr.Message != tt.message {
+				t.Errorf("AdminError = %+v, want status %q message %q", adminErr, tt.status, tt.message)
+			}
+			if adminErr.Busy() != tt.busy {
+				t.Errorf("Busy() = %v, want %v", adminErr.Busy(), tt.busy)
+			}
+			if ShouldCloseConnection(err) {
+				t.Error("admin status must not close the connection")
+			}
+		})
+	}
+}
@@ -0,0 +1,59 @@
+package meta
+
+import (
+	"bufio"
+	"fmt"
+	"io"
+	"strings"
+)
+
+// authKey is the key of the authentication set command: the server ignores it.
+const authKey = "auth"
+
+// WriteAuthRequest writes the authentication command of memcached's text
+// protocol (server started with -Y): a set whose value is
+// "<username> <password>".
+//
+// Wire format: set <key> 0 0 <bytes>\r\n<username> <password>\r\n
+//
+// The username must not contain whitespace, and neither the username nor the
+// password may contain a line break: such credentials are rejected with a
+// *ClientError before anything is written.
+func WriteAuthRequest(w io.Writer, username, password string) error {
+	if username == "" || strings.ContainsAny(username, " \t\r\n") {
+		return &ClientError{Message: "invalid authentication username"}
+	}
+	if strings.ContainsAny(password, "\r\n") {
+		return &ClientError{Message: "invalid authentication password"}
+	}
+
+	token := username + Space + password
+	_, err := fmt.Fprintf(w, "set %s 0 0 %d%s%s%s", authKey, len(token), CRLF, token, CRLF)
+	return err
+}
+
+// ReadAuthResponse reads the response of the authentication command: nil for
+// "STORED", or the error line of a rejected authentication, a *ClientError.
+func ReadAuthResponse(r *bufio.Reader) error {
+	line, err := r.ReadString('\n')
+	if err != nil {
+		return err
+	}
+
+	line = strings.TrimSuffix(line, CRLF)
+	line = strings.TrimSuffix(line, "\n")
+
+	if line == StoredMarker {
+		return nil
+	}
+	if msg, ok := strings.CutPrefix(line, ErrorClientPrefix+" "); ok {
+		return &ClientError{Message: msg}
+	}
+	if msg, ok := strings.CutPrefix(line, ErrorServerPrefix+" "); ok {
+		return &ServerError{Message: msg}
+	}
+	if line == ErrorGeneric {
+		return &GenericError{Message: "ERROR"}
+	}
+	return &ParseError{Message: "unexpected authentication response: " + line}
+}
@@ -0,0 +1,48 @@
+package meta
+
+import (
+	"bufio"
+	"bytes"
+	"errors"
+	"strings"
+	"testing"
+)
+
+func TestWriteAuthRequest(t *testing.T) {
+	var buf bytes.Buffer
+	if err := WriteAuthRequest(&buf, "user", "secret pass"); err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if got, want := buf.String(), "set auth 0 0 16\r\nuser secret pass\r\n"; got != want {
+		t.Errorf("request = %q, want %q", got, want)
+	}
+
+	for _, creds := range [][2]string{{"", "pass"}, {"us er", "pass"}, {"user", "pa\r\nss"}} {
+		buf.Reset()
+		var clientErr *ClientError
+		if err := WriteAuthRequest(&buf, creds[0], creds[1]); !errors.As(err, &clientErr) {
+			t.Errorf("WriteAuthRequest(%q, %q) error = %v, want a ClientError", creds[0], creds[1], err)
+		}
+		if buf.Len() != 0 {
+			t.Errorf("WriteAuthRequest(%q, %q) wrote %q", creds[0], creds[1], buf.String())
+		}
+	}
+}
+
+func TestReadAuthResponse(t *testing.T) {
+	if err := ReadAuthResponse(bufio.NewReader(strings.NewReader("STORED\r\n"))); err != nil {
+		t.Errorf("unexpected error: %v", err)
+	}
+
+	err := ReadAuthResponse(bufio.NewReader(strings.NewReader("CLIENT_ERROR authentication failure\r\n")))
+	var clientErr *ClientError
+	if !errors.As(err, &clientErr) || clientErr.Message != "authentication failure" {
+		t.Errorf("error = %v, want a ClientError", err)
+	}
+
+	err = ReadAuthResponse(bufio.NewReader(strings.NewReader("HD\r\n")))
+	var parseErr *ParseError
+	if !errors.As(err, &parseErr) {
+		t.Errorf("error = %v, want a ParseError", err)
+	}
+}
@@ -4,11 +4,15 @@ import (
 	"bufio"
 	"bytes"
 	"io"
+	"math"
+	"strconv"
 	"testing"
 )
 
 var sinkRequest *Request
 
+var sinkUint uint64
+
 // goos: darwin
 // goarch: arm64
 // pkg: github.com/pior/memcache/meta
@@ -38,6 +42,14 @@ func BenchmarkBuildRequest(b *testing.B) {
 		}
 	})
 
+	b.Run("GetWithPreparedFlags", func(b *testing.B) {
+		flags := PrepareFlags(NewRequest(CmdGet, "", nil).
+			AddReturnValue().AddReturnCAS().AddReturnTTL().AddReturnClientFlags().AddOpaque("token123").Flags)
+		for b.Loop() {
+			sinkRequest = NewPreparedRequest(CmdGet, "mykey", nil, flags)
+		}
+	})
+
 	b.Run("SetWithTTL", func(b *testing.B) {
 		data := bytes.Repeat([]byte("x"), 100)
 		for b.Loop() {
@@ -321,3 +333,20 @@ func BenchmarkRoundTrip_Set(b *testing.B) {
 
 // 	return conn
 // }
+
+// Benchmark flag token parsing against strconv, as used by the typed getters
+func BenchmarkParseUint(b *testing.B) {
+	token := []byte("1234567890123")
+
+	b.Run("parseUint", func(b *testing.B) {
+		for b.Loop() {
+			sinkUint, _ = parseUint(token, math.MaxUint64)
+		}
+	})
+
+	b.Run("strconv", func(b *testing.B) {
+		for b.Loop() {
+			sinkUint, _ = strconv.ParseUint(string(token), 10, 64)
+		}
+	})
+}
@@ -0,0 +1,38 @@
+package meta
+
+import "sync"
+
+// BufferPool provides the buffers that value data is read into (see
+// ReadResponseWithPool), for callers recycling them to reduce allocations.
+// Implementations must be safe for concurrent use.
+type BufferPool interface {
+	// Get returns a buffer of length n.
+	Get(n int) []byte
+
+	// Put returns a buffer obtained from Get (or a reslice of it, such as
+	// Response.Data) to the pool. The caller must not use it afterwards.
+	Put(buf []byte)
+}
+
+// NewBufferPool returns a BufferPool backed by a sync.Pool: buffers of any
+// size are recycled, and a pooled buffer too small for a request is replaced
+// by a new allocation.
+func NewBufferPool() BufferPool {
+	return &syncBufferPool{}
+}
+
+type syncBufferPool struct {
+	pool sync.Pool
+}
+
+func (p *syncBufferPool) Get(n int) []byte {
+	if bufp, ok := p.pool.Get().(*[]byte); ok && cap(*bufp) >= n {
+		return (*bufp)[:n]
+	}
+	return make([]byte, n)
+}
+
+func (p *syncBufferPool) Put(buf []byte) {
+	buf = buf[:0]
+	p.pool.Put(&buf)
+}
@@ -0,0 +1,56 @@
+package meta
+
+import (
+	"bufio"
+	"strings"
+	"testing"
+)
+
+// countingPool records the buffer sizes requested from a BufferPool.
+type countingPool struct {
+	BufferPool
+	sizes []int
+}
+
+func (p *countingPool) Get(n int) []byte {
+	p.sizes = append(p.sizes, n)
+	return p.BufferPool.Get(n)
+}
+
+func TestReadResponseWithPool(t *testing.T) {
+	pool := &countingPool{BufferPool: NewBufferPool()}
+	r := bufio.NewReader(strings.NewReader("VA 5 f3\r\nhello\r\nHD\r\nEN\r\n"))
+
+	var resp Response
+	if err := ReadResponseWithPool(r, &resp, pool); err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if string(resp.Data) != "hello" {
+		t.Errorf("Data = %q, want %q", resp.Data, "hello")
+	}
+	pool.Put(resp.Data)
+
+	for range 2 {
+		if err := ReadResponseWithPool(r, &resp, pool); err != nil {
+			t.Fatalf("unexpected error: %v", err)
+		}
+	}
+
+	if len(pool.sizes) != 1 || pool.sizes[0] != 7 {
+		t.Errorf("pool sizes = %v, want one buffer for the value and its CRLF", pool.sizes)
+	}
+}
+
+func TestNewBufferPool(t *testing.T) {
+	pool := NewBufferPool()
+
+	buf := pool.Get(10)
+	if len(buf) != 10 {
+		t.Fatalf("len = %d, want 10", len(buf))
+	}
+	pool.Put(buf)
+
+	if buf := pool.Get(100); len(buf) != 100 {
+		t.Errorf("len = %d, want 100 even when the pooled buffer is smaller", len(buf))
+	}
+}
@@ -0,0 +1,84 @@
+package meta
+
+import (
+	"bufio"
+	"strconv"
+	"strings"
+)
+
+// CachedumpEntry is one item listed by "stats cachedump <class> <limit>".
+//
+// Wire format: ITEM <key> [<size> b; <exptime> s]\r\n
+type CachedumpEntry struct {
+	Key        string // item key
+	Size       int    // value size in bytes
+	Expiration int64  // absolute expiration (unix seconds), 0 if the item never expires
+}
+
+// ReadCachedumpResponse reads the response of "stats cachedump": ITEM lines
+// until the END marker.
+func ReadCachedumpResponse(r *bufio.Reader) ([]CachedumpEntry, error) {
+	var entries []CachedumpEntry
+
+	for {
+		line, err := r.ReadString('\n')
+		if err != nil {
+			return entries, err
+		}
+
+		line = strings.TrimSuffix(line, CRLF)
+		line = strings.TrimSuffix(line, "\n")
+
+		if line == EndMarker {
+			return entries, nil
+		}
+
+		if msg, ok := strings.CutPrefix(line, ErrorClientPrefix+" "); ok {
+			return entries, &ClientError{Message: msg}
+		}
+		if msg, ok := strings.CutPrefix(line, ErrorServerPrefix+" "); ok {
+			return entries, &ServerError{Message: msg}
+		}
+		if line == ErrorGeneric {
+			return entries, &GenericError{Message: "ERROR"}
+		}
+
+		entry, ok := parseCachedumpLine(line)
+		if !ok {
+			return entries, &ParseError{Message: "invalid cachedump line: " + line}
+		}
+		entries = append(entries, entry)
+	}
+}
+
+// parseCachedumpLine parses "ITEM <key> [<size> b; <exptime> s]".
+func parseCachedumpLine(line string) (CachedumpEntry, bool) {
+	rest, ok := strings.CutPrefix(line, ItemPrefix+" ")
+	if !ok {
+		return CachedumpEntry{}, false
+	}
+
+	key, rest, ok := strings.Cut(rest, " [")
+	if !ok || key == "" {
+		return CachedumpEntry{}, false
+	}
+
+	rest, ok = strings.CutSuffix(rest, " s]")
+	if !ok {
+		return CachedumpEntry{}, false
+	}
+	size, exptime, ok := strings.Cut(rest, " b; ")
+	if !ok {
+		return CachedumpEntry{}, false
+	}
+
+	entry := CachedumpEntry{Key: key}
+	var err error
+	if entry.Size, err = strconv.Atoi(size); err != nil {
+		return CachedumpEntry{}, false
+	}
+	if entry.Expiration, err = strconv.ParseInt(exptime, 10, 64); err != nil {
+		return CachedumpEntry{}, false
+	}
+	return entry, true
+}
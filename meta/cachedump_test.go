@@ -0,0 +1,61 @@
+package meta
+
+import (
+	"bufio"
+	"errors"
+	"strings"
+	"testing"
+)
+
+func TestReadCachedumpResponse(t *testing.T) {
+	r := bufio.NewReader(strings.NewReader(
+		"ITEM user:42 [68 b; 1700000000 s]\r\n" +
+			"ITEM forever [5 b; 0 s]\r\n" +
+			"END\r\n"))
+
+	entries, err := ReadCachedumpResponse(r)
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	want := []CachedumpEntry{
+		{Key: "user:42", Size: 68, Expiration: 1700000000},
+		{Key: "forever", Size: 5},
+	}
+	if len(entries) != len(want) {
+		t.Fatalf("entries = %+v, want %+v", entries, want)
+	}
+	for i := range want {
+		if entries[i] != want[i] {
+			t.Errorf("entries[%d] = %+v, want %+v", i, entries[i], want[i])
+		}
+	}
+}
+
+func TestReadCachedumpResponse_Empty(t *testing.T) {
+	entries, err := ReadCachedumpResponse(bufio.NewReader(strings.NewReader("END\r\n")))
+	if err != nil || len(entries) != 0 {
+		t.Errorf("ReadCachedumpResponse = %+v/%v, want no entries", entries, err)
+	}
+}
+
+func TestReadCachedumpResponse_Errors(t *testing.T) {
+	tests := []struct {
+		name  string
+		input string
+		check func(error) bool
+	}{
+		{name: "malformed line", input: "ITEM key 68 b\r\n", check: func(err error) bool { var e *ParseError; return errors.As(err, &e) }},
+		{name: "bad size", input: "ITEM key [x b; 0 s]\r\n", check: func(err error) bool { var e *ParseError; return errors.As(err, &e) }},
+		{name: "client error", input: "CLIENT_ERROR bad command line\r\n", check: func(err error) bool { var e *ClientError; return errors.As(err, &e) }},
+		{name: "generic error", input: "ERROR\r\n", check: func(err error) bool { var e *GenericError; return errors.As(err, &e) }},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			_, err := ReadCachedumpResponse(bufio.NewReader(strings.NewReader(tt.input)))
+			if !tt.check(err) {
+				t.Errorf("error = %v (%T)", err, err)
+			}
+		})
+	}
+}
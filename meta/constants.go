@@ -77,7 +77,8 @@ const (
 	//   - FlagQuiet (q): Suppress success response (HD)
 	//   - FlagOpaque (O): Set opaque token for request matching
 	//   - FlagBase64Key (b): Key is base64-encoded
-	//   - FlagInvalidate (I): Mark as stale instead of storing
+	//   - FlagInvalidate (I): With C, store a value whose CAS is older than the item's, marked stale
+	//   - FlagVivify (N): Auto-create on miss with given TTL (append/prepend modes only)
 	//
 	// Storage modes (with FlagMode):
 	//   - ModeSet (S): Store unconditionally (default)
@@ -105,6 +106,16 @@ const (
 	//
 	//   Set with client flags:
 	//     NewRequest(CmdSet, "mykey", []byte("value")).AddTTL(3600).AddClientFlags(123)
+	//
+	//   Append, creating the item on miss:
+	//     NewRequest(CmdSet, "mykey", []byte("value")).AddModeAppend().AddVivify(3600)
+	//
+	//   Store a recomputed value after winning a vivified get (mg with N):
+	//     NewRequest(CmdSet, "mykey", []byte("value")).AddTTL(3600) // ModeSet, not ModeAdd
+	//
+	//   The vivify stub exists as a regular item: ModeAdd returns NS, the recompute
+	//   is lost and other callers keep seeing the stub (and recomputing) until it
+	//   expires.
 	CmdSet CmdType = "ms"
 
 	// CmdDelete deletes or invalidates items.
@@ -116,7 +127,7 @@ const (
 	//   - FlagTTL (T): Set TTL for invalidation (with I flag)
 	//   - FlagInvalidate (I): Mark stale instead of deleting
 	//   - FlagReturnKey (k): Return key in response
-	//   - FlagQuiet (q): Suppress success response
+	//   - FlagQuiet (q): Suppress success and miss responses (HD, NF)
 	//   - FlagOpaque (O): Set opaque token for request matching
 	//   - FlagBase64Key (b): Key is base64-encoded
 	//
@@ -125,6 +136,11 @@ const (
 	//   - NF: Key not found
 	//   - EX: CAS mismatch
 	//
+	// md has no return flags besides k and O: it cannot report the CAS of the
+	// deleted item. To record it (e.g. for an audit trail), read it with mg and
+	// the c flag, then delete with that CAS so the record matches what was
+	// deleted.
+	//
 	// Typical patterns:
 	//
 	//   Basic delete:
@@ -227,6 +243,73 @@ const (
 	// Typical pattern:
 	//     &Request{Command: CmdStats, Key: "items"} // Key carries the optional argument
 	CmdStats CmdType = "stats"
+
+	// CmdCacheMemlimit changes the server memory limit (standard text protocol).
+	//
+	// Wire format: cache_memlimit <megabytes>\r\n
+	//
+	// This is not part of the meta protocol but an admin command of the
+	// standard text protocol. The response is "OK\r\n" (see ReadOKResponse).
+	//
+	// Typical pattern:
+	//     &Request{Command: CmdCacheMemlimit, Key: "1024"} // Key carries the argument
+	CmdCacheMemlimit CmdType = "cache_memlimit"
+
+	// CmdVerbosity changes the server logging level (standard text protocol).
+	//
+	// Wire format: verbosity <level>\r\n
+	//
+	// This is not part of the meta protocol but an admin command of the
+	// standard text protocol. The response is "OK\r\n" (see ReadOKResponse).
+	//
+	// Typical pattern:
+	//     &Request{Command: CmdVerbosity, Key: "1"} // Key carries the argument
+	CmdVerbosity CmdType = "verbosity"
+
+	// CmdLRUCrawler controls the LRU crawler (standard text protocol).
+	//
+	// Wire format: lru_crawler <subcommand> [args]\r\n
+	//
+	// The "metadump all" subcommand streams the metadata of every item, one
+	// line per item, followed by "END\r\n" (see ReadMetadumpEntry). The server
+	// answers "BUSY ..." while another crawl is running (see AdminError).
+	//
+	// Typical pattern:
+	//     &Request{Command: CmdLRUCrawler, Key: "metadump all"} // Key carries the arguments
+	CmdLRUCrawler CmdType = "lru_crawler"
+
+	// CmdVersion returns the server version (standard text protocol).
+	//
+	// Wire format: version\r\n
+	//
+	// The response is "VERSION <version>\r\n" (see ReadVersionResponse).
+	//
+	// Typical pattern:
+	//     &Request{Command: CmdVersion}
+	CmdVersion CmdType = "version"
+
+	// CmdWatch streams the server's log events (standard text protocol).
+	//
+	// Wire format: watch [<event class>]*\r\n
+	//
+	// The server answers "OK\r\n" (see ReadOKResponse), then pushes one line
+	// per event (see ReadWatchEvent) until the connection is closed: the
+	// connection can't carry any other command after it.
+	//
+	// Typical pattern:
+	//     &Request{Command: CmdWatch, Key: "fetchers mutations"} // Key carries the arguments
+	CmdWatch CmdType = "watch"
+
+	// CmdQuit asks the server to close the connection (standard text
+	// protocol).
+	//
+	// Wire format: quit\r\n
+	//
+	// There is no response: the server closes the connection.
+	//
+	// Typical pattern:
+	//     &Request{Command: CmdQuit}
+	CmdQuit CmdType = "quit"
 )
 
 // Response status codes (2 characters)
@@ -271,7 +354,7 @@ const (
 	ErrorServerPrefix = "SERVER_ERROR"
 )
 
-// Stats command response prefixes (standard text protocol)
+// Stats and admin command responses (standard text protocol)
 const (
 	// StatPrefix is the prefix for each statistics line
 	// Format: STAT <name> <value>\r\n
@@ -279,6 +362,29 @@ const (
 
 	// EndMarker indicates the end of a stats response
 	EndMarker = "END"
+
+	// ItemPrefix is the prefix for each line of a "stats cachedump" response
+	// Format: ITEM <key> [<size> b; <exptime> s]\r\n
+	ItemPrefix = "ITEM"
+
+	// OKMarker is the success response of admin commands (e.g. cache_memlimit)
+	OKMarker = "OK"
+
+	// StoredMarker is the success response of the authentication command
+	StoredMarker = "STORED"
+
+	// VersionPrefix is the prefix of the version command response
+	// Format: VERSION <version>\r\n
+	VersionPrefix = "VERSION"
+
+	// BusyPrefix is returned by lru_crawler while a crawl is already running
+	BusyPrefix = "BUSY"
+
+	// BadClassPrefix is returned by lru_crawler for an invalid slab class ID
+	BadClassPrefix = "BADCLASS"
+
+	// NotFoundPrefix is returned by admin commands with nothing to act on
+	NotFoundPrefix = "NOTFOUND"
 )
 
 // Request flags (single character, optionally followed by token)
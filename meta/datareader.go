@@ -0,0 +1,79 @@
+package meta
+
+import (
+	"bufio"
+	"io"
+)
+
+// DataReader reads the data block of a VA response that was left unread (see
+// ReadOptions.StreamThreshold), without buffering it whole.
+//
+// The terminator following the value is consumed and checked along with the
+// last bytes of the value, so the reader is at the next response as soon as
+// size bytes were read: Done then reports true. An invalid terminator or a
+// truncated value is a ParseError.
+type DataReader struct {
+	r         *bufio.Reader
+	remaining int
+	lenient   bool
+	done      bool
+}
+
+// NewDataReader returns a reader of the size bytes of value data at the head
+// of r. lenient accepts a bare LF terminator, like
+// ReadOptions.LenientLineEndings.
+func NewDataReader(r *bufio.Reader, size int, lenient bool) *DataReader {
+	return &DataReader{r: r, remaining: size, lenient: lenient}
+}
+
+// Read implements io.Reader. It returns io.EOF once the whole value was read.
+func (d *DataReader) Read(p []byte) (int, error) {
+	if d.done {
+		return 0, io.EOF
+	}
+
+	var n int
+	if d.remaining > 0 {
+		if len(p) > d.remaining {
+			p = p[:d.remaining]
+		}
+		var err error
+		n, err = d.r.Read(p)
+		d.remaining -= n
+		if err == io.EOF && d.remaining > 0 {
+			return n, &ParseError{Message: "failed to read data block", Err: io.ErrUnexpectedEOF}
+		}
+		if err != nil && err != io.EOF {
+			return n, err
+		}
+	}
+
+	if d.remaining == 0 {
+		if err := d.readTerminator(); err != nil {
+			return n, err
+		}
+		d.done = true
+	}
+	return n, nil
+}
+
+// Done reports whether the value and its terminator were read entirely.
+func (d *DataReader) Done() bool {
+	return d.done
+}
+
+func (d *DataReader) readTerminator() error {
+	b, err := d.r.ReadByte()
+	if err == nil && b == '\r' {
+		b, err = d.r.ReadByte()
+	} else if err == nil && !d.lenient {
+		return &ParseError{Message: "invalid data block terminator"}
+	}
+	if err != nil {
+		return &ParseError{Message: "failed to read data block", Err: err}
+	}
+	if b != '\n' {
+		return &ParseError{Message: "invalid data block terminator"}
+	}
+	return nil
+}
@@ -3,6 +3,8 @@ package meta
 import (
 	"errors"
 	"fmt"
+	"strconv"
+	"strings"
 )
 
 // Error types for meta protocol operations.
@@ -59,6 +61,51 @@ func (e *ServerError) ShouldCloseConnection() bool {
 	return false
 }
 
+// AdminError represents a status word other than OK returned by an admin
+// command such as lru_crawler: BUSY, BADCLASS or NOTFOUND.
+//
+// Common causes:
+//   - BUSY: a crawl is already running, back off and retry
+//   - BADCLASS: invalid slab class ID
+//   - NOTFOUND: nothing to act on
+//
+// Connection handling: Connection can be REUSED
+type AdminError struct {
+	Status  string // status word, e.g. BusyPrefix
+	Message string // text following the status word, if any
+}
+
+func (e *AdminError) Error() string {
+	if e.Message == "" {
+		return e.Status
+	}
+	return e.Status + ": " + e.Message
+}
+
+// Busy reports whether the server was busy and the command can be retried.
+func (e *AdminError) Busy() bool {
+	return e.Status == BusyPrefix
+}
+
+// ShouldCloseConnection returns false - the response line was fully read
+func (e *AdminError) ShouldCloseConnection() bool {
+	return false
+}
+
+// parseAdminError returns the AdminError for an admin status line, or nil if
+// the line doesn't start with an admin status word.
+func parseAdminError(line string) error {
+	for _, status := range []string{BusyPrefix, BadClassPrefix, NotFoundPrefix} {
+		if line == status {
+			return &AdminError{Status: status}
+		}
+		if msg, ok := strings.CutPrefix(line, status+" "); ok {
+			return &AdminError{Status: status, Message: msg}
+		}
+	}
+	return nil
+}
+
 // GenericError represents a generic ERROR response from memcached.
 // Typically indicates unknown command or protocol violation.
 //
@@ -81,6 +128,10 @@ func (e *GenericError) ShouldCloseConnection() bool {
 	return true
 }
 
+// ErrEmptyKey is wrapped by the InvalidKeyError of an empty key, e.g. a key
+// computed from input that turned out to be empty.
+var ErrEmptyKey = errors.New("key is empty")
+
 // InvalidKeyError is returned when a key fails validation.
 // Indicates the key violates memcache protocol constraints before sending to server.
 //
@@ -92,18 +143,73 @@ func (e *GenericError) ShouldCloseConnection() bool {
 // Connection handling: Connection is still valid, operation was rejected client-side
 type InvalidKeyError struct {
 	Message string
+	Err     error // the failed rule, if it has a sentinel (ErrEmptyKey)
 }
 
 func (e *InvalidKeyError) Error() string {
 	return e.Message
 }
 
+// Unwrap returns the failed rule for error chain inspection
+func (e *InvalidKeyError) Unwrap() error {
+	return e.Err
+}
+
 // ShouldCloseConnection returns false - the key was rejected client-side,
 // before any byte was written: the connection is untouched and reusable.
 func (e *InvalidKeyError) ShouldCloseConnection() bool {
 	return false
 }
 
+// BatchKeyError is returned when a request of a batch has an invalid key. The
+// whole batch is rejected before any byte is written, so the error carries
+// the position of the offending request.
+//
+// Connection handling: Connection is still valid, batch was rejected client-side
+type BatchKeyError struct {
+	Index int   // position of the request in the batch
+	Err   error // the key validation error, an *InvalidKeyError
+}
+
+func (e *BatchKeyError) Error() string {
+	return "batch request " + strconv.Itoa(e.Index) + ": " + e.Err.Error()
+}
+
+// Unwrap returns the underlying error for error chain inspection
+func (e *BatchKeyError) Unwrap() error {
+	return e.Err
+}
+
+// ShouldCloseConnection returns false - the batch was rejected before any
+// byte was written
+func (e *BatchKeyError) ShouldCloseConnection() bool {
+	return false
+}
+
+// KeyDecodeError is returned by Response.DecodedKey when the key echoed with
+// the b flag is not valid base64.
+//
+// Connection handling: Connection is still valid, the response was fully read
+type KeyDecodeError struct {
+	Key []byte // the key as returned by the server
+	Err error  // the base64 decoding error
+}
+
+func (e *KeyDecodeError) Error() string {
+	return "invalid base64 key " + strconv.Quote(string(e.Key)) + ": " + e.Err.Error()
+}
+
+// Unwrap returns the decoding error for error chain inspection
+func (e *KeyDecodeError) Unwrap() error {
+	return e.Err
+}
+
+// ShouldCloseConnection returns false - decoding happens after the response
+// was fully read
+func (e *KeyDecodeError) ShouldCloseConnection() bool {
+	return false
+}
+
 // ParseError represents a client-side parsing error.
 // Indicates the client failed to parse the server response, which suggests
 // either a protocol violation by the server or a bug in the client parser.
@@ -185,6 +291,8 @@ type ErrorWithConnectionState interface {
 //
 // Returns false for:
 //   - ServerError
+//   - AdminError
+//   - BatchKeyError
 //   - InvalidKeyError
 //   - nil
 //
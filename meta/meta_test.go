@@ -4,8 +4,11 @@ import (
 	"bufio"
 	"bytes"
 	"errors"
+	"io"
+	"strconv"
 	"strings"
 	"testing"
+	"testing/iotest"
 )
 
 // Test request serialization
@@ -322,6 +325,207 @@ func TestReadResponse_VA(t *testing.T) {
 	}
 }
 
+// Responses arriving in several reads, split anywhere (notably between the
+// \r and \n of a line or data block terminator), must parse as if they
+// arrived at once.
+func TestReadResponse_SplitReads(t *testing.T) {
+	// Two pipelined responses: the second one must not be disturbed by the
+	// split in the first one.
+	inputs := []string{
+		"HD c1\r\nVA 2\r\nv2\r\n",
+		"VA 5 t60\r\nh\r\nlo\r\nEN\r\n", // data block containing CRLF
+		"VA 0\r\n\r\nMN\r\n",
+	}
+
+	readAll := func(t *testing.T, r *bufio.Reader) []Response {
+		t.Helper()
+		var resps []Response
+		for range 2 {
+			var resp Response
+			if err := ReadResponse(r, &resp); err != nil {
+				t.Fatalf("ReadResponse failed: %v", err)
+			}
+			resps = append(resps, resp)
+		}
+		return resps
+	}
+
+	for _, input := range inputs {
+		want := readAll(t, bufio.NewReader(strings.NewReader(input)))
+
+		for split := 1; split < len(input); split++ {
+			r := bufio.NewReader(io.MultiReader(strings.NewReader(input[:split]), strings.NewReader(input[split:])))
+			got := readAll(t, r)
+			for i := range want {
+				if got[i].Status != want[i].Status || !bytes.Equal(got[i].Data, want[i].Data) || !bytes.Equal(got[i].Flags, want[i].Flags) {
+					t.Errorf("input %q split at %d: response %d = %+v, want %+v", input, split, i, got[i], want[i])
+				}
+			}
+		}
+
+		got := readAll(t, bufio.NewReader(iotest.OneByteReader(strings.NewReader(input))))
+		for i := range want {
+			if got[i].Status != want[i].Status || !bytes.Equal(got[i].Data, want[i].Data) {
+				t.Errorf("input %q read byte by byte: response %d = %+v, want %+v", input, i, got[i], want[i])
+			}
+		}
+	}
+}
+
+// A stream ending between the \r and \n of a terminator is truncated, not
+// complete.
+func TestReadResponse_TruncatedAtCR(t *testing.T) {
+	for _, input := range []string{"HD\r", "VA 2\r", "VA 2\r\nv2\r"} {
+		var resp Response
+		if err := ReadResponse(bufio.NewReader(strings.NewReader(input)), &resp); err == nil {
+			t.Errorf("ReadResponse(%q) succeeded, want an error", input)
+		}
+	}
+}
+
+func TestReadResponse_LineEndings(t *testing.T) {
+	tests := []struct {
+		input   string
+		strict  bool // accepted in the default, strict mode
+		lenient bool // accepted with LenientLineEndings
+	}{
+		{"HD c1\r\nVA 2\r\nv2\r\n", true, true},
+		{"HD c1\nVA 2\r\nv2\r\n", false, true},
+		{"HD c1\r\nVA 2\nv2\n", false, true},
+		{"HD c1\r\nVA 2\r\nv2\n", false, true},
+		{"HD c1\r\nVA 2\r\nv2\rX", false, false},
+		{"HD c1\r\nVA 2\r\nv2X", false, false},
+	}
+
+	read := func(input string, opts ReadOptions) error {
+		r := bufio.NewReader(strings.NewReader(input))
+		var hd, va Response
+		if err := ReadResponseWithOptions(r, &hd, opts); err != nil {
+			return err
+		}
+		if err := ReadResponseWithOptions(r, &va, opts); err != nil {
+			return err
+		}
+		if string(hd.Status) != "HD" || string(va.Data) != "v2" || r.Buffered() != 0 {
+			t.Errorf("read %q as %s and %s %q, %d bytes left", input, hd.Status, va.Status, va.Data, r.Buffered())
+		}
+		return nil
+	}
+
+	for _, tt := range tests {
+		if err := read(tt.input, ReadOptions{}); (err == nil) != tt.strict {
+			t.Errorf("strict read of %q: err = %v, want accepted=%v", tt.input, err, tt.strict)
+		}
+		if err := read(tt.input, ReadOptions{LenientLineEndings: true}); (err == nil) != tt.lenient {
+			t.Errorf("lenient read of %q: err = %v, want accepted=%v", tt.input, err, tt.lenient)
+		}
+	}
+}
+
+func TestReadResponse_TrailingWhitespace(t *testing.T) {
+	tests := []struct {
+		input  string
+		status StatusType
+		flags  string
+		data   string
+	}{
+		{input: "HD \r\n", status: StatusHD},
+		{input: "HD c1 \t\r\n", status: StatusHD, flags: " c1"},
+		{input: "VA 5 \r\nhello\r\n", status: StatusVA, data: "hello"},
+		{input: "VA 5 f3  \r\nhello\r\n", status: StatusVA, flags: " f3", data: "hello"},
+		{input: "EN\t\r\n", status: StatusEN},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			r := bufio.NewReader(strings.NewReader(tt.input))
+			var resp Response
+			if err := ReadResponse(r, &resp); err != nil {
+				t.Fatalf("ReadResponse failed: %v", err)
+			}
+			if resp.Status != tt.status || string(resp.Flags) != tt.flags || string(resp.Data) != tt.data {
+				t.Errorf("read %s %q %q, want %s %q %q", resp.Status, resp.Flags, resp.Data, tt.status, tt.flags, tt.data)
+			}
+		})
+	}
+
+	var resp Response
+	if err := ReadResponse(bufio.NewReader(strings.NewReader("ERROR \r\n")), &resp); err != nil {
+		t.Fatalf("ReadResponse failed: %v", err)
+	}
+	var genericErr *GenericError
+	if !errors.As(resp.Error, &genericErr) {
+		t.Errorf("Error = %v, want a GenericError", resp.Error)
+	}
+}
+
+func TestReadResponse_StreamThreshold(t *testing.T) {
+	opts := ReadOptions{StreamThreshold: 4}
+
+	t.Run("small value is buffered", func(t *testing.T) {
+		r := bufio.NewReader(strings.NewReader("VA 4 c1\r\nabcd\r\n"))
+		var resp Response
+		if err := ReadResponseWithOptions(r, &resp, opts); err != nil {
+			t.Fatal(err)
+		}
+		if string(resp.Data) != "abcd" || resp.UnreadSize != 0 {
+			t.Errorf("Data = %q, UnreadSize = %d", resp.Data, resp.UnreadSize)
+		}
+	})
+
+	t.Run("large value is left unread", func(t *testing.T) {
+		r := bufio.NewReader(iotest.OneByteReader(strings.NewReader("VA 10 c1\r\n0123456789\r\nHD\r\n")))
+		var resp Response
+		if err := ReadResponseWithOptions(r, &resp, opts); err != nil {
+			t.Fatal(err)
+		}
+		if cas, _ := resp.CAS(); resp.Data != nil || resp.UnreadSize != 10 || cas != 1 {
+			t.Fatalf("Data = %q, UnreadSize = %d, CAS = %d", resp.Data, resp.UnreadSize, cas)
+		}
+
+		data := NewDataReader(r, resp.UnreadSize, false)
+		value := make([]byte, 10)
+		if _, err := io.ReadFull(data, value); err != nil {
+			t.Fatal(err)
+		}
+		if string(value) != "0123456789" || !data.Done() {
+			t.Errorf("value = %q, Done = %v", value, data.Done())
+		}
+		if n, err := data.Read(value); n != 0 || err != io.EOF {
+			t.Errorf("Read after the value = %d, %v, want 0, EOF", n, err)
+		}
+
+		if err := ReadResponseWithOptions(r, &resp, opts); err != nil || resp.Status != StatusHD {
+			t.Errorf("next response: %s, %v", resp.Status, err)
+		}
+	})
+
+	t.Run("invalid terminator", func(t *testing.T) {
+		r := bufio.NewReader(strings.NewReader("0123456789XX"))
+		_, err := io.ReadAll(NewDataReader(r, 10, false))
+		var parseErr *ParseError
+		if !errors.As(err, &parseErr) {
+			t.Errorf("err = %v, want ParseError", err)
+		}
+	})
+
+	t.Run("truncated value", func(t *testing.T) {
+		r := bufio.NewReader(strings.NewReader("01234"))
+		_, err := io.ReadAll(NewDataReader(r, 10, false))
+		if !errors.Is(err, io.ErrUnexpectedEOF) {
+			t.Errorf("err = %v, want ErrUnexpectedEOF", err)
+		}
+	})
+
+	t.Run("lenient terminator", func(t *testing.T) {
+		r := bufio.NewReader(strings.NewReader("0123456789\n"))
+		value, err := io.ReadAll(NewDataReader(r, 10, true))
+		if err != nil || string(value) != "0123456789" {
+			t.Errorf("value = %q, err = %v", value, err)
+		}
+	})
+}
+
 func TestReadResponse_InvalidVASize(t *testing.T) {
 	tests := []struct {
 		name          string
@@ -462,6 +666,51 @@ func TestReadResponse_OtherStatuses(t *testing.T) {
 	}
 }
 
+// Flags the client didn't ask for, including flag types unknown to this
+// package, are kept rather than rejected: newer servers may add some.
+func TestReadResponse_UnexpectedFlags(t *testing.T) {
+	tests := []struct {
+		input  string
+		status StatusType
+		flags  string
+	}{
+		{input: "HD c123 Oabc\r\n", status: StatusHD, flags: " c123 Oabc"},
+		{input: "NF Oabc Zfuture\r\n", status: StatusNF, flags: " Oabc Zfuture"},
+		{input: "EN Y\r\n", status: StatusEN, flags: " Y"},
+		{input: "NS c7 Z1\r\n", status: StatusNS, flags: " c7 Z1"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			r := bufio.NewReader(strings.NewReader(tt.input))
+			var resp Response
+			if err := ReadResponse(r, &resp); err != nil {
+				t.Fatalf("ReadResponse failed: %v", err)
+			}
+			if resp.Status != tt.status {
+				t.Errorf("Status = %q, want %q", resp.Status, tt.status)
+			}
+			if got := string(resp.Flags); got != tt.flags {
+				t.Errorf("Flags = %q, want %q", got, tt.flags)
+			}
+			if resp.HasError() {
+				t.Errorf("unexpected error: %v", resp.Error)
+			}
+		})
+	}
+
+	var resp Response
+	if err := ReadResponse(bufio.NewReader(strings.NewReader("HD c123 Oabc\r\n")), &resp); err != nil {
+		t.Fatalf("ReadResponse failed: %v", err)
+	}
+	if cas, ok := resp.CAS(); !ok || cas != 123 {
+		t.Errorf("CAS() = %d, %v, want 123, true", cas, ok)
+	}
+	if opaque, ok := resp.Opaque(); !ok || string(opaque) != "abc" {
+		t.Errorf("Opaque() = %q, %v, want \"abc\", true", opaque, ok)
+	}
+}
+
 // Test batch operations
 
 func TestWriteMultipleRequests(t *testing.T) {
@@ -487,6 +736,27 @@ func TestWriteMultipleRequests(t *testing.T) {
 	}
 }
 
+func TestReadMultipleResponses(t *testing.T) {
+	r := bufio.NewReader(strings.NewReader("VA 2\r\nv1\r\nEN\r\nVA 2\r\nv3\r\nMN\r\n"))
+
+	var got []string
+	for {
+		var resp Response
+		if err := ReadResponse(r, &resp); err != nil {
+			t.Fatalf("ReadResponse failed: %v", err)
+		}
+		if resp.Status == StatusMN {
+			break
+		}
+		got = append(got, string(resp.Status)+":"+string(resp.Data))
+	}
+
+	expected := "VA:v1 EN: VA:v3"
+	if s := strings.Join(got, " "); s != expected {
+		t.Errorf("responses = %q, want %q", s, expected)
+	}
+}
+
 // Test helper methods
 
 func TestResponse_HelperMethods(t *testing.T) {
@@ -594,6 +864,19 @@ func TestRequest_HelperMethods(t *testing.T) {
 	})
 }
 
+func TestValidateKeyWithLimit(t *testing.T) {
+	key := strings.Repeat("a", 300)
+
+	if err := ValidateKeyWithLimit(key, false, 300); err != nil {
+		t.Errorf("ValidateKeyWithLimit() unexpected error: %v", err)
+	}
+
+	err := ValidateKeyWithLimit(key, false, 299)
+	if err == nil || err.Error() != "key exceeds maximum length of 299 bytes" {
+		t.Errorf("ValidateKeyWithLimit() error = %v, want maximum length of 299 bytes", err)
+	}
+}
+
 func TestValidateKey(t *testing.T) {
 	tests := []struct {
 		name          string
@@ -917,3 +1200,72 @@ func TestReadResponse_VASizeTooLarge(t *testing.T) {
 		t.Fatalf("ReadResponse error = %v, want ParseError", err)
 	}
 }
+
+func TestValidateBatchKeys(t *testing.T) {
+	reqs := []*Request{
+		NewRequest(CmdGet, "key1", nil),
+		NewRequest(CmdNoOp, "", nil),
+		NewRequest(CmdGet, "bad key", nil),
+	}
+
+	err := ValidateBatchKeys(reqs, MaxKeyLength)
+	var batchKeyErr *BatchKeyError
+	if !errors.As(err, &batchKeyErr) {
+		t.Fatalf("error = %v (%T), want BatchKeyError", err, err)
+	}
+	if batchKeyErr.Index != 2 {
+		t.Errorf("Index = %d, want 2", batchKeyErr.Index)
+	}
+	var invalidKey *InvalidKeyError
+	if !errors.As(err, &invalidKey) {
+		t.Errorf("error must unwrap to InvalidKeyError")
+	}
+
+	if err := ValidateBatchKeys(reqs[:2], MaxKeyLength); err != nil {
+		t.Errorf("valid batch: unexpected error %v", err)
+	}
+}
+
+// Values are length-prefixed: CRLF, NUL bytes or a literal "mn\r\n" inside a
+// value must never be taken for a delimiter, on write or on read.
+func TestValueFraming_EmbeddedDelimiters(t *testing.T) {
+	values := map[string]string{
+		"CRLF":          "a\r\nb",
+		"trailing CRLF": "ab\r\n",
+		"NUL bytes":     "a\x00b\x00",
+		"noop command":  "mn\r\n",
+		"fake response": "HD\r\nEN\r\n",
+	}
+
+	for name, value := range values {
+		t.Run(name, func(t *testing.T) {
+			var buf bytes.Buffer
+			if err := WriteRequest(&buf, NewRequest(CmdSet, "key", []byte(value))); err != nil {
+				t.Fatalf("WriteRequest failed: %v", err)
+			}
+			wantWire := "ms key " + strconv.Itoa(len(value)) + "\r\n" + value + "\r\n"
+			if got := buf.String(); got != wantWire {
+				t.Errorf("wire = %q, want %q", got, wantWire)
+			}
+
+			// The value is followed by a real NoOp marker, as in a batch.
+			wire := "VA " + strconv.Itoa(len(value)) + "\r\n" + value + "\r\n" + "MN\r\n"
+			r := bufio.NewReader(strings.NewReader(wire))
+
+			var resp Response
+			if err := ReadResponse(r, &resp); err != nil {
+				t.Fatalf("ReadResponse failed: %v", err)
+			}
+			if string(resp.Data) != value {
+				t.Errorf("Data = %q, want %q", resp.Data, value)
+			}
+
+			if err := ReadResponse(r, &resp); err != nil {
+				t.Fatalf("ReadResponse of the marker failed: %v", err)
+			}
+			if resp.Status != StatusMN {
+				t.Errorf("next status = %q, want MN: the stream is desynchronized", resp.Status)
+			}
+		})
+	}
+}
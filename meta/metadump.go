@@ -0,0 +1,72 @@
+package meta
+
+import (
+	"bufio"
+	"net/url"
+	"strconv"
+	"strings"
+)
+
+// MetadumpEntry is the metadata of one item, as streamed by
+// "lru_crawler metadump".
+//
+// Wire format: key=<key> exp=<exptime> la=<last access> cas=<cas> fetch=<yes|no> cls=<class> size=<size>\r\n
+type MetadumpEntry struct {
+	Key        string // URL-decoded key
+	Expiration int64  // absolute expiration (unix seconds), -1 if the item never expires
+	LastAccess int64  // last access time (unix seconds)
+	CAS        uint64 // CAS value
+	Fetched    bool   // whether the item was fetched since it was stored
+	SlabClass  int    // slab class ID
+	Size       int    // total item size in bytes
+}
+
+// ReadMetadumpEntry reads the next entry of a metadump stream.
+// It returns ok=false, with a nil error, on the terminating END line.
+//
+// Fields missing from the line are left zero, so that entries from servers
+// reporting fewer fields still parse; a line without a key is a ParseError.
+// A busy crawler ("BUSY ...") is reported as an AdminError.
+func ReadMetadumpEntry(r *bufio.Reader) (entry MetadumpEntry, ok bool, err error) {
+	line, err := r.ReadString('\n')
+	if err != nil {
+		return entry, false, err
+	}
+
+	line = strings.TrimSuffix(line, CRLF)
+	line = strings.TrimSuffix(line, "\n")
+
+	switch {
+	case line == EndMarker:
+		return entry, false, nil
+	case strings.HasPrefix(line, ErrorClientPrefix+" "):
+		return entry, false, &ClientError{Message: strings.TrimPrefix(line, ErrorClientPrefix+" ")}
+	case strings.HasPrefix(line, ErrorServerPrefix+" "):
+		return entry, false, &ServerError{Message: strings.TrimPrefix(line, ErrorServerPrefix+" ")}
+	case line == ErrorGeneric || strings.HasPrefix(line, ErrorGeneric+" "):
+		return entry, false, &GenericError{Message: line}
+	}
+
+	if err := parseAdminError(line); err != nil {
+		return entry, false, err
+	}
+
+	params := ParseDebugParams([]byte(line))
+
+	rawKey, found := params["key"]
+	if !found {
+		return entry, false, &ParseError{Message: "invalid metadump line: " + line}
+	}
+	if entry.Key, err = url.PathUnescape(rawKey); err != nil {
+		return entry, false, &ParseError{Message: "invalid metadump key: " + rawKey, Err: err}
+	}
+
+	entry.Expiration, _ = strconv.ParseInt(params["exp"], 10, 64)
+	entry.LastAccess, _ = strconv.ParseInt(params["la"], 10, 64)
+	entry.CAS, _ = strconv.ParseUint(params["cas"], 10, 64)
+	entry.Fetched = params["fetch"] == "yes"
+	entry.SlabClass, _ = strconv.Atoi(params["cls"])
+	entry.Size, _ = strconv.Atoi(params["size"])
+
+	return entry, true, nil
+}
@@ -0,0 +1,70 @@
+package meta
+
+import (
+	"bufio"
+	"errors"
+	"strings"
+	"testing"
+)
+
+func TestReadMetadumpEntry(t *testing.T) {
+	r := bufio.NewReader(strings.NewReader(
+		"key=user%3A42%20a exp=-1 la=1700000000 cas=7 fetch=yes cls=1 size=68\r\n" +
+			"key=short\r\n" +
+			"END\r\n"))
+
+	entry, ok, err := ReadMetadumpEntry(r)
+	if err != nil || !ok {
+		t.Fatalf("ReadMetadumpEntry = %v/%v, want entry", ok, err)
+	}
+	want := MetadumpEntry{
+		Key:        "user:42 a",
+		Expiration: -1,
+		LastAccess: 1700000000,
+		CAS:        7,
+		Fetched:    true,
+		SlabClass:  1,
+		Size:       68,
+	}
+	if entry != want {
+		t.Errorf("entry = %+v, want %+v", entry, want)
+	}
+
+	entry, ok, err = ReadMetadumpEntry(r)
+	if err != nil || !ok {
+		t.Fatalf("ReadMetadumpEntry = %v/%v, want entry", ok, err)
+	}
+	if entry != (MetadumpEntry{Key: "short"}) {
+		t.Errorf("entry with missing fields = %+v, want only the key", entry)
+	}
+
+	_, ok, err = ReadMetadumpEntry(r)
+	if err != nil || ok {
+		t.Errorf("ReadMetadumpEntry at END = %v/%v, want false/nil", ok, err)
+	}
+}
+
+func TestReadMetadumpEntry_Errors(t *testing.T) {
+	read := func(input string) error {
+		_, _, err := ReadMetadumpEntry(bufio.NewReader(strings.NewReader(input)))
+		return err
+	}
+
+	var adminErr *AdminError
+	if err := read("BUSY currently processing crawler request\r\n"); !errors.As(err, &adminErr) || !adminErr.Busy() {
+		t.Errorf("BUSY error = %v (%T), want busy AdminError", err, err)
+	}
+
+	var genericErr *GenericError
+	if err := read("ERROR locked try again later\r\n"); !errors.As(err, &genericErr) {
+		t.Errorf("ERROR error = %v (%T), want GenericError", err, err)
+	}
+
+	var parseErr *ParseError
+	if err := read("exp=-1 la=1\r\n"); !errors.As(err, &parseErr) {
+		t.Errorf("line without key error = %v (%T), want ParseError", err, err)
+	}
+	if err := read("key=bad%zz\r\n"); !errors.As(err, &parseErr) {
+		t.Errorf("undecodable key error = %v (%T), want ParseError", err, err)
+	}
+}
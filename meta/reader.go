@@ -17,6 +17,10 @@ const MaxDataSize = 1 << 30
 // ReadResponse reads and parses a single response from r into resp.
 // Response format: <status> [<flags>*]\r\n[<data>\r\n]
 //
+// A meta response carries at most one value. Several values, e.g. of a
+// multi-get, come from pipelined requests: one response each, read in the
+// order of the requests by successive calls.
+//
 // The caller provides the Response; it will be reset before parsing.
 // This allows callers to reuse Response objects (e.g., via sync.Pool).
 //
@@ -24,6 +28,10 @@ const MaxDataSize = 1 << 30
 // stored in resp.Error (not returned as Go error). The caller should check
 // resp.HasError() and use ShouldCloseConnection() to determine connection handling.
 //
+// Every flag of the status line is kept in resp.Flags, whatever the status and
+// whether or not it was requested: flags unknown to this package, e.g. ones
+// added by a newer server, are retained as is rather than rejected.
+//
 // Go errors returned indicate I/O or parsing failures:
 //   - io.EOF: Connection closed
 //   - ParseError: Malformed response, connection should be closed
@@ -32,10 +40,47 @@ const MaxDataSize = 1 << 30
 // Performance considerations:
 //   - Uses bufio.Reader for efficient line reading
 //   - Minimizes allocations for flag parsing
+//   - Parses all the flags in a single left-to-right pass; the typed getters
+//     (CAS, ClientFlags...) then scan the few parsed flags
 //   - Reads data block in single read operation when possible
 func ReadResponse(r *bufio.Reader, resp *Response) error {
+	return ReadResponseWithPool(r, resp, nil)
+}
+
+// ReadResponseWithPool is ReadResponse reading the value data into a buffer
+// drawn from pool rather than a new allocation, so that a steady read
+// workload recycles its buffers. The caller owns resp.Data and returns it to
+// the pool with Put once done with it. A nil pool allocates like
+// ReadResponse.
+func ReadResponseWithPool(r *bufio.Reader, resp *Response, pool BufferPool) error {
+	return ReadResponseWithOptions(r, resp, ReadOptions{Pool: pool})
+}
+
+// ReadOptions tunes how ReadResponseWithOptions reads a response.
+type ReadOptions struct {
+	// Pool, if not nil, provides the buffers values are read into (see
+	// ReadResponseWithPool).
+	Pool BufferPool
+
+	// LenientLineEndings accepts a bare LF as the terminator of the response
+	// line and of the data block, for proxies or emulators that don't send
+	// the CRLF the protocol mandates. By default, any other terminator than
+	// CRLF is a ParseError. Requests are always written with CRLF.
+	LenientLineEndings bool
+
+	// StreamThreshold, if positive, leaves the data block of a VA response
+	// larger than StreamThreshold bytes unread, to avoid buffering a large
+	// value in a single allocation: Response.Data is then nil, and
+	// Response.UnreadSize is the size of the value, which the caller must
+	// read with NewDataReader before reading the next response.
+	StreamThreshold int
+}
+
+// ReadResponseWithOptions is ReadResponse with options.
+func ReadResponseWithOptions(r *bufio.Reader, resp *Response, opts ReadOptions) error {
 	// Reset response for reuse
 	*resp = Response{}
+	pool := opts.Pool
 
 	// Read response line
 	line, err := r.ReadString('\n')
@@ -44,8 +89,17 @@ func ReadResponse(r *bufio.Reader, resp *Response) error {
 	}
 
 	// Trim CRLF
-	line = strings.TrimSuffix(line, CRLF)
-	line = strings.TrimSuffix(line, "\n") // Handle LF-only (lenient)
+	if trimmed, ok := strings.CutSuffix(line, CRLF); ok {
+		line = trimmed
+	} else if opts.LenientLineEndings {
+		line = strings.TrimSuffix(line, "\n")
+	} else {
+		return &ParseError{Message: "response line not terminated by CRLF"}
+	}
+
+	// Trailing whitespace before the terminator, sent by some servers and
+	// proxies, is insignificant: drop it so it doesn't end up in a field.
+	line = strings.TrimRight(line, " \t")
 
 	// Check for protocol errors first
 	if msg, ok := strings.CutPrefix(line, ErrorClientPrefix+" "); ok {
@@ -140,9 +194,21 @@ func ReadResponse(r *bufio.Reader, resp *Response) error {
 	}
 
 	// Read data block for VA responses
+	if resp.Status == StatusVA && opts.StreamThreshold > 0 && dataSize > opts.StreamThreshold {
+		resp.UnreadSize = dataSize
+		return nil
+	}
+	if resp.Status == StatusVA && opts.LenientLineEndings {
+		return readDataLenient(r, resp, dataSize, pool)
+	}
 	if resp.Status == StatusVA {
 		// Read data + CRLF together in single read
-		data := make([]byte, dataSize+2)
+		var data []byte
+		if pool != nil {
+			data = pool.Get(dataSize + 2)
+		} else {
+			data = make([]byte, dataSize+2)
+		}
 		_, err = io.ReadFull(r, data)
 		if err != nil {
 			return &ParseError{Message: "failed to read data block", Err: err}
@@ -160,6 +226,36 @@ func ReadResponse(r *bufio.Reader, resp *Response) error {
 	return nil
 }
 
+// readDataLenient reads the data block of a VA response terminated by either
+// CRLF or a bare LF.
+func readDataLenient(r *bufio.Reader, resp *Response, dataSize int, pool BufferPool) error {
+	var data []byte
+	if pool != nil {
+		data = pool.Get(dataSize + 2)
+	} else {
+		data = make([]byte, dataSize+2)
+	}
+
+	// Read data + the first byte of the terminator, then the LF of a CRLF.
+	data = data[:dataSize+1]
+	if _, err := io.ReadFull(r, data); err != nil {
+		return &ParseError{Message: "failed to read data block", Err: err}
+	}
+	last := data[dataSize]
+	if last == '\r' {
+		var err error
+		if last, err = r.ReadByte(); err != nil {
+			return &ParseError{Message: "failed to read data block", Err: err}
+		}
+	}
+	if last != '\n' {
+		return &ParseError{Message: "invalid data block terminator"}
+	}
+
+	resp.Data = data[:dataSize]
+	return nil
+}
+
 // lineScanner walks a response line field by field, in place. It avoids the
 // per-response []string that strings.Fields would allocate.
 type lineScanner struct {
@@ -198,7 +294,8 @@ func (s *lineScanner) remaining() int {
 // Stats responses consist of multiple "STAT <name> <value>\r\n" lines
 // followed by "END\r\n".
 //
-// Returns a map of stat names to values and any error encountered.
+// Returns a map of stat names to values and any error encountered. Use
+// ReadStat to go through a large response without building the map.
 //
 // Example response:
 //
@@ -210,45 +307,88 @@ func ReadStatsResponse(r *bufio.Reader) (map[string]string, error) {
 	stats := make(map[string]string)
 
 	for {
-		line, err := r.ReadString('\n')
-		if err != nil {
+		stat, ok, err := ReadStat(r)
+		if err != nil || !ok {
 			return stats, err
 		}
+		stats[stat.Name] = stat.Value
+	}
+}
 
-		// Trim CRLF
-		line = strings.TrimSuffix(line, CRLF)
-		line = strings.TrimSuffix(line, "\n")
+// Stat is one "STAT <name> <value>" line of a stats response.
+type Stat struct {
+	Name  string
+	Value string // may contain spaces
+}
 
-		// Check for END marker
-		if line == EndMarker {
-			return stats, nil
-		}
+// ReadStat reads the next line of a stats response.
+// It returns ok=false, with a nil error, on the terminating END line.
+func ReadStat(r *bufio.Reader) (stat Stat, ok bool, err error) {
+	line, err := r.ReadString('\n')
+	if err != nil {
+		return stat, false, err
+	}
 
-		// Check for errors
-		if msg, ok := strings.CutPrefix(line, ErrorClientPrefix+" "); ok {
-			return stats, &ClientError{Message: msg}
-		}
-		if msg, ok := strings.CutPrefix(line, ErrorServerPrefix+" "); ok {
-			return stats, &ServerError{Message: msg}
-		}
-		if line == ErrorGeneric {
-			return stats, &GenericError{Message: "ERROR"}
-		}
+	// Trim CRLF
+	line = strings.TrimSuffix(line, CRLF)
+	line = strings.TrimSuffix(line, "\n")
 
-		// Parse STAT line: STAT <name> <value>
-		if !strings.HasPrefix(line, StatPrefix+" ") {
-			return stats, &ParseError{Message: "invalid stats response line: " + line}
-		}
+	// Check for END marker
+	if line == EndMarker {
+		return stat, false, nil
+	}
 
-		// Remove "STAT " prefix
-		statLine := strings.TrimPrefix(line, StatPrefix+" ")
+	// Check for errors
+	if msg, ok := strings.CutPrefix(line, ErrorClientPrefix+" "); ok {
+		return stat, false, &ClientError{Message: msg}
+	}
+	if msg, ok := strings.CutPrefix(line, ErrorServerPrefix+" "); ok {
+		return stat, false, &ServerError{Message: msg}
+	}
+	if line == ErrorGeneric {
+		return stat, false, &GenericError{Message: "ERROR"}
+	}
 
-		// Split into name and value (value may contain spaces)
-		parts := strings.SplitN(statLine, " ", 2)
-		if len(parts) != 2 {
-			return stats, &ParseError{Message: "invalid STAT line format: " + line}
-		}
+	// Parse STAT line: STAT <name> <value>
+	statLine, found := strings.CutPrefix(line, StatPrefix+" ")
+	if !found {
+		return stat, false, &ParseError{Message: "invalid stats response line: " + line}
+	}
+
+	// Split into name and value (value may contain spaces)
+	name, value, found := strings.Cut(statLine, " ")
+	if !found {
+		return stat, false, &ParseError{Message: "invalid STAT line format: " + line}
+	}
+
+	return Stat{Name: name, Value: value}, true, nil
+}
+
+// ReadOKResponse reads the response of an admin command (e.g. cache_memlimit):
+// a single "OK\r\n" line on success, or an error line. Admin status words
+// (BUSY, BADCLASS, NOTFOUND) are reported as an AdminError.
+func ReadOKResponse(r *bufio.Reader) error {
+	line, err := r.ReadString('\n')
+	if err != nil {
+		return err
+	}
+
+	line = strings.TrimSuffix(line, CRLF)
+	line = strings.TrimSuffix(line, "\n")
 
-		stats[parts[0]] = parts[1]
+	switch {
+	case line == OKMarker:
+		return nil
+	case strings.HasPrefix(line, ErrorClientPrefix+" "):
+		return &ClientError{Message: strings.TrimPrefix(line, ErrorClientPrefix+" ")}
+	case strings.HasPrefix(line, ErrorServerPrefix+" "):
+		return &ServerError{Message: strings.TrimPrefix(line, ErrorServerPrefix+" ")}
+	case line == ErrorGeneric:
+		return &GenericError{Message: "ERROR"}
+	}
+
+	if err := parseAdminError(line); err != nil {
+		return err
 	}
+	return &ParseError{Message: "unexpected admin command response: " + line}
 }
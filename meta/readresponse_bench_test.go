@@ -40,6 +40,22 @@ func benchReadResponse(b *testing.B, input []byte) {
 	}
 }
 
+// benchReadResponsePooled reads values into buffers recycled through a
+// BufferPool, returning each one once read, as a steady workload would.
+func benchReadResponsePooled(b *testing.B, input []byte) {
+	b.Helper()
+	r := bufio.NewReader(&loopReader{data: input})
+	pool := NewBufferPool()
+	var resp Response
+	b.ReportAllocs()
+	for b.Loop() {
+		if err := ReadResponseWithPool(r, &resp, pool); err != nil {
+			b.Fatal(err)
+		}
+		pool.Put(resp.Data)
+	}
+}
+
 func makeVA(size int, flags string) []byte {
 	var buf bytes.Buffer
 	buf.WriteString("VA ")
@@ -88,6 +104,30 @@ func BenchmarkReadResponseReuse_SmallValueWithFlags(b *testing.B) {
 	benchReadResponse(b, makeVA(100, "c12345 t3600 f30"))
 }
 
+// A get returning its metadata: the flags are parsed in one pass, then the
+// typed getters scan the parsed flags.
+func BenchmarkReadResponseReuse_SmallValueWithSixFlags(b *testing.B) {
+	r := bufio.NewReader(&loopReader{data: makeVA(100, "c12345 f30 t3600 s100 h1 l12")})
+	var resp Response
+	b.ReportAllocs()
+	for b.Loop() {
+		if err := ReadResponse(r, &resp); err != nil {
+			b.Fatal(err)
+		}
+		cas, _ := resp.CAS()
+		flags, _ := resp.ClientFlags()
+		sinkUint = cas + uint64(flags)
+	}
+}
+
 func BenchmarkReadResponseReuse_LargeValue(b *testing.B) {
 	benchReadResponse(b, makeVA(10*1024, ""))
 }
+
+func BenchmarkReadResponsePooled_SmallValue(b *testing.B) {
+	benchReadResponsePooled(b, makeVA(100, ""))
+}
+
+func BenchmarkReadResponsePooled_LargeValue(b *testing.B) {
+	benchReadResponsePooled(b, makeVA(10*1024, ""))
+}
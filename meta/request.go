@@ -1,6 +1,10 @@
 package meta
 
-import "strconv"
+import (
+	"iter"
+	"slices"
+	"strconv"
+)
 
 // Request represents a meta protocol request.
 // This is a low-level container for request data without serialization logic.
@@ -41,8 +45,10 @@ func (f Flags) IsEmpty() bool {
 	return len(f) == 0
 }
 
+// Reset empties the flags. The bytes are dropped rather than reused: they
+// may be shared, e.g. with the requests of a PreparedFlags.
 func (f *Flags) Reset() {
-	*f = (*f)[:0]
+	*f = nil
 }
 
 func (f Flags) Clone() Flags {
@@ -63,6 +69,34 @@ func (f *Flags) AddTokenString(flagType FlagType, token string) {
 	*f = append(*f, token...)
 }
 
+// Remove deletes every flag of the given type, keeping the others in order.
+// The remaining flags are copied to a new slice, as the bytes may be shared
+// (see PreparedFlags).
+func (f *Flags) Remove(flagType FlagType) {
+	if !f.Has(flagType) {
+		return
+	}
+
+	out := make(Flags, 0, len(*f))
+	for i := 0; i < len(*f); {
+		i = flagsSkipSpaces(*f, i)
+		if i >= len(*f) {
+			break
+		}
+
+		start := i
+		for i < len(*f) && (*f)[i] != ' ' {
+			i++
+		}
+
+		if FlagType((*f)[start]) != flagType {
+			out = append(out, ' ')
+			out = append(out, (*f)[start:i]...)
+		}
+	}
+	*f = out
+}
+
 // Common TTL values cached to reduce allocations.
 // Note: strconv.Itoa already caches 0-100, so we only cache larger values that are
 // common in memcached usage.
@@ -129,6 +163,53 @@ func (f Flags) Get(flagType FlagType) (token []byte, ok bool) {
 	return nil, false
 }
 
+// All iterates over the flags in order, yielding each flag type with its
+// token (nil if the flag has no token). Tokens alias f.
+//
+// It is a generic, lower-level view of the flags: every flag is visited,
+// including those without a typed accessor on Response.
+func (f Flags) All() iter.Seq2[FlagType, []byte] {
+	return func(yield func(FlagType, []byte) bool) {
+		for i := 0; i < len(f); {
+			i = flagsSkipSpaces(f, i)
+			if i >= len(f) {
+				return
+			}
+
+			t := FlagType(f[i])
+			i++
+
+			start := i
+			for i < len(f) && f[i] != ' ' {
+				i++
+			}
+
+			var token []byte
+			if start < i {
+				token = f[start:i]
+			}
+			if !yield(t, token) {
+				return
+			}
+		}
+	}
+}
+
+// Matches reports whether every flag of expected is present in f. An expected
+// flag with a token must carry that exact token; one without a token matches
+// the flag with any token. Flags of f absent from expected are ignored.
+//
+// Typical use: assertions in tests, e.g. f.Matches(Flags(" W t60")).
+func (f Flags) Matches(expected Flags) bool {
+	for flag, want := range expected.All() {
+		got, ok := f.Get(flag)
+		if !ok || (want != nil && string(got) != string(want)) {
+			return false
+		}
+	}
+	return true
+}
+
 func flagsSkipSpaces(b []byte, idx int) int {
 	for idx < len(b) && b[idx] == ' ' {
 		idx++
@@ -169,6 +250,38 @@ func NewRequest(cmd CmdType, key string, data []byte) *Request {
 	}
 }
 
+// PreparedFlags is a set of flags serialized once and shared by many
+// requests, for hot loops sending the same flags over and over: requests
+// created with NewPreparedRequest reuse its bytes instead of serializing
+// the flags again.
+//
+// The zero value is an empty flag set.
+type PreparedFlags struct {
+	flags Flags
+}
+
+// PrepareFlags returns the prepared form of flags, which are copied: flags
+// can be reused afterwards. Build them with the Flags methods, or take the
+// Flags of a request built with the typed Add* methods:
+//
+//	prepared := PrepareFlags(NewRequest(CmdGet, "", nil).AddReturnValue().AddReturnCAS().Flags)
+func PrepareFlags(flags Flags) PreparedFlags {
+	return PreparedFlags{flags: slices.Clip(flags.Clone())}
+}
+
+// NewPreparedRequest creates a request carrying the prepared flags, without
+// copying them. Changing the flags of the request is safe: the shared bytes
+// have no spare capacity, so the first append copies them, Reset drops them,
+// and Remove copies them.
+func NewPreparedRequest(cmd CmdType, key string, data []byte, flags PreparedFlags) *Request {
+	return &Request{
+		Command: cmd,
+		Key:     key,
+		Data:    data,
+		Flags:   flags.flags,
+	}
+}
+
 // HasFlag checks if the request contains a flag of the given type.
 func (r *Request) HasFlag(flagType FlagType) bool {
 	return r.Flags.Has(flagType)
@@ -200,7 +313,18 @@ func (r *Request) AddOpaque(token string) *Request {
 	return r
 }
 
-// AddQuiet adds the 'q' flag to suppress nominal responses (HD, EN, NF).
+// SetOpaque sets the 'O' flag, replacing any opaque token already present.
+// Supported by: mg, ms, md, ma.
+// Typical use: tag a request built elsewhere (e.g. by a builder's Request
+// method) for correlation, without risking two conflicting tokens.
+func (r *Request) SetOpaque(token string) *Request {
+	r.Flags.Remove(FlagOpaque)
+	r.Flags.AddTokenString(FlagOpaque, token)
+	return r
+}
+
+// AddQuiet adds the 'q' flag to suppress nominal responses: EN for mg, HD for
+// ms and ma, HD and NF for md. A miss of ma (NF) is still reported.
 // Supported by: mg, ms, md, ma.
 // Typical use: pipelining multiple requests and using mn (noop) to detect end.
 // Error responses are still returned even with quiet mode.
@@ -210,6 +334,7 @@ func (r *Request) AddQuiet() *Request { r.Flags.Add(FlagQuiet); return r }
 // AddBase64Key adds the 'b' flag indicating the key is base64-encoded.
 // Supported by: mg, ms, md, ma, me.
 // Typical use: keys containing whitespace or binary data.
+// A key returned with the k flag is base64-encoded too: see Response.DecodedKey.
 // The flag is unconditionally added, even if already present.
 func (r *Request) AddBase64Key() *Request { r.Flags.Add(FlagBase64Key); return r }
 
@@ -324,10 +449,11 @@ func (r *Request) AddNoLRUBump() *Request { r.Flags.Add(FlagNoLRUBump); return r
 func (r *Request) AddRecache(seconds int) *Request { r.Flags.AddInt(FlagRecache, seconds); return r }
 
 // AddVivify adds the 'N' flag to auto-create a stub item on cache miss.
-// Supported by: mg, ma.
+// Supported by: mg, ms (append/prepend modes), ma.
 // Typical use: cache-aside pattern with built-in locking to prevent thundering herd.
 // Token: TTL for the stub item in seconds, e.g. 30, 60.
 // On miss, creates stub and returns 'W' flag; subsequent requests get stale stub.
+// With ms, the appended/prepended data is stored as a new item instead of NF.
 // The flag is unconditionally added, even if already present.
 func (r *Request) AddVivify(seconds int) *Request { r.Flags.AddInt(FlagVivify, seconds); return r }
 
@@ -2,6 +2,7 @@ package meta
 
 import (
 	"bytes"
+	"strings"
 	"testing"
 )
 
@@ -71,6 +72,61 @@ func TestRequest_FlagMethods_Chaining(t *testing.T) {
 	}
 }
 
+func TestRequest_SetOpaque(t *testing.T) {
+	req := NewRequest(CmdGet, "key", nil).AddOpaque("first").AddReturnValue()
+	req.SetOpaque("second").SetOpaque("third")
+
+	var buf bytes.Buffer
+	if err := WriteRequest(&buf, req); err != nil {
+		t.Fatalf("WriteRequest failed: %v", err)
+	}
+	if got := buf.String(); got != "mg key v Othird\r\n" {
+		t.Errorf("wire = %q, want a single opaque token %q", got, "mg key v Othird\r\n")
+	}
+}
+
+func TestNewPreparedRequest(t *testing.T) {
+	flags := NewRequest(CmdGet, "", nil).AddReturnValue().AddOpaque("abc").Flags
+	prepared := PrepareFlags(flags)
+	flags.Reset()
+	flags.Add(FlagQuiet)
+
+	req1 := NewPreparedRequest(CmdGet, "k1", nil, prepared).AddReturnCAS()
+	req2 := NewPreparedRequest(CmdGet, "k2", nil, prepared).SetOpaque("xyz")
+	req3 := NewPreparedRequest(CmdGet, "k3", nil, prepared)
+
+	var buf bytes.Buffer
+	for _, req := range []*Request{req1, req2, req3} {
+		if err := WriteRequest(&buf, req); err != nil {
+			t.Fatalf("WriteRequest failed: %v", err)
+		}
+	}
+	want := "mg k1 v Oabc c\r\nmg k2 v Oxyz\r\nmg k3 v Oabc\r\n"
+	if got := buf.String(); got != want {
+		t.Errorf("wire = %q, want %q: the prepared flags must not change", got, want)
+	}
+}
+
+func TestNewPreparedRequest_Reset(t *testing.T) {
+	prepared := PrepareFlags(NewRequest(CmdGet, "", nil).AddReturnValue().AddReturnTTL().Flags)
+
+	req := NewPreparedRequest(CmdGet, "k1", nil, prepared)
+	req.Flags.Reset()
+	req.AddReturnCAS()
+	other := NewPreparedRequest(CmdGet, "k2", nil, prepared)
+
+	var buf bytes.Buffer
+	for _, r := range []*Request{req, other} {
+		if err := WriteRequest(&buf, r); err != nil {
+			t.Fatalf("WriteRequest failed: %v", err)
+		}
+	}
+	want := "mg k1 c\r\nmg k2 v t\r\n"
+	if got := buf.String(); got != want {
+		t.Errorf("wire = %q, want %q: Reset must not write into the prepared flags", got, want)
+	}
+}
+
 func TestFlags_Methods(t *testing.T) {
 	t.Run("IsEmpty and Reset", func(t *testing.T) {
 		var f Flags
@@ -114,6 +170,42 @@ func TestFlags_Methods(t *testing.T) {
 		}
 	})
 
+	t.Run("All yields every flag in order", func(t *testing.T) {
+		f := Flags(" s5 t-1  W k c42 ")
+		var got []string
+		for flag, token := range f.All() {
+			if token == nil {
+				got = append(got, string(flag)+"<nil>")
+			} else {
+				got = append(got, string(flag)+"="+string(token))
+			}
+		}
+		want := "s=5 t=-1 W<nil> k<nil> c=42"
+		if strings.Join(got, " ") != want {
+			t.Errorf("All = %q, want %q", strings.Join(got, " "), want)
+		}
+	})
+
+	t.Run("All stops when the consumer breaks", func(t *testing.T) {
+		f := Flags(" t1 c2")
+		var count int
+		for range f.All() {
+			count++
+			break
+		}
+		if count != 1 {
+			t.Errorf("iterations = %d, want 1", count)
+		}
+	})
+
+	t.Run("Remove drops every flag of the type", func(t *testing.T) {
+		f := Flags(" Oa v  Ob t")
+		f.Remove(FlagOpaque)
+		if got := string(f); got != " v t" {
+			t.Errorf("flags = %q, want %q", got, " v t")
+		}
+	})
+
 	t.Run("Get returns first match", func(t *testing.T) {
 		var f Flags
 		f.AddInt(FlagTTL, 1)
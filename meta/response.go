@@ -1,8 +1,13 @@
 package meta
 
 import (
+	"bytes"
+	"encoding/base64"
+	"math"
+	"slices"
 	"strconv"
 	"strings"
+	"time"
 )
 
 // Response represents a parsed meta protocol response.
@@ -18,9 +23,14 @@ type Response struct {
 	Data []byte
 
 	// Flags contains all flags returned in the response.
-	// Order matches the response wire order.
+	// Order matches the response wire order. Use the typed accessors below, or
+	// Flags.All to visit every flag generically.
 	Flags Flags
 
+	// UnreadSize is the size of a VA value left unread on the reader (see
+	// ReadOptions.StreamThreshold), in which case Data is nil.
+	UnreadSize int
+
 	// Error is set for non-meta error responses: ERROR, CLIENT_ERROR, SERVER_ERROR
 	// When Error is set, other fields may be empty or invalid
 	Error error
@@ -28,6 +38,9 @@ type Response struct {
 
 // IsSuccess returns true if the response indicates a successful operation.
 // Success statuses: HD, VA, MN, ME
+//
+// For a get, both HD and VA are hits: HD when no value was requested (no v
+// flag), e.g. a get reading only metadata.
 func (r *Response) IsSuccess() bool {
 	switch r.Status {
 	case StatusHD, StatusVA, StatusMN, StatusME:
@@ -79,6 +92,47 @@ func (r *Response) GetFlagToken(flagType FlagType) (token []byte, ok bool) {
 	return r.Flags.Get(flagType)
 }
 
+// MatchesFlags reports whether the response carries every flag of expected,
+// with the same token when expected gives one (see Flags.Matches).
+func (r *Response) MatchesFlags(expected Flags) bool {
+	return r.Flags.Matches(expected)
+}
+
+// DataEqual reports whether r and other carry the same value: both values
+// with the same bytes, or both without a value (e.g. two misses). The
+// metadata (TTL, CAS, last access...), which legitimately differs between
+// the replicas of an item, is ignored: see DiffFlags.
+func (r *Response) DataEqual(other *Response) bool {
+	return r.HasValue() == other.HasValue() && bytes.Equal(r.Data, other.Data)
+}
+
+// DiffFlags returns the types of the flags that differ between r and other:
+// present in only one of them, or with different tokens. Each type is listed
+// once, in the order of r's flags, then other's. Meant for cache-consistency
+// tooling comparing the replicas of an item, e.g. to report that their CAS
+// (c) and remaining TTL (t) differ.
+func (r *Response) DiffFlags(other *Response) []FlagType {
+	var diff []FlagType
+	check := func(flagType FlagType) {
+		if slices.Contains(diff, flagType) {
+			return
+		}
+		token, ok := r.Flags.Get(flagType)
+		otherToken, otherOk := other.Flags.Get(flagType)
+		if ok != otherOk || !bytes.Equal(token, otherToken) {
+			diff = append(diff, flagType)
+		}
+	}
+
+	for flagType := range r.Flags.All() {
+		check(flagType)
+	}
+	for flagType := range other.Flags.All() {
+		check(flagType)
+	}
+	return diff
+}
+
 // --- Typed flag getters ---
 
 // Boolean flags (presence check)
@@ -103,17 +157,50 @@ func (r *Response) AlreadyWon() bool {
 
 // Typed getters (parse flag tokens)
 
+// parseUint parses an unsigned decimal flag token no larger than limit. It is a
+// faster strconv.ParseUint for the hot response path: no string conversion
+// and no error value, just whether the token is valid.
+func parseUint(token []byte, limit uint64) (uint64, bool) {
+	if len(token) == 0 {
+		return 0, false
+	}
+	var v uint64
+	for _, c := range token {
+		d := c - '0'
+		if d > 9 {
+			return 0, false
+		}
+		if v > (limit-uint64(d))/10 {
+			return 0, false // overflow
+		}
+		v = v*10 + uint64(d)
+	}
+	return v, true
+}
+
+// parseInt parses a signed decimal flag token into an int (e.g. a TTL of -1).
+func parseInt(token []byte) (int, bool) {
+	neg := len(token) > 0 && token[0] == '-'
+	if neg {
+		token = token[1:]
+	}
+	v, ok := parseUint(token, math.MaxInt)
+	if !ok {
+		return 0, false
+	}
+	if neg {
+		return -int(v), true
+	}
+	return int(v), true
+}
+
 // CAS returns the CAS token value from the response.
 func (r *Response) CAS() (uint64, bool) {
 	token, ok := r.Flags.Get(FlagReturnCAS)
 	if !ok {
 		return 0, false
 	}
-	v, err := strconv.ParseUint(string(token), 10, 64)
-	if err != nil {
-		return 0, false
-	}
-	return v, true
+	return parseUint(token, math.MaxUint64)
 }
 
 // TTL returns the remaining TTL in seconds from the response.
@@ -123,11 +210,18 @@ func (r *Response) TTL() (int, bool) {
 	if !ok {
 		return 0, false
 	}
-	v, err := strconv.Atoi(string(token))
-	if err != nil {
-		return 0, false
+	return parseInt(token)
+}
+
+// ExpiresAt returns the absolute expiration time of the item, computed from
+// the remaining TTL (t flag) and now, the time the response was received.
+// Returns false if the flag is missing or the item never expires.
+func (r *Response) ExpiresAt(now time.Time) (time.Time, bool) {
+	ttl, ok := r.TTL()
+	if !ok || ttl < 0 {
+		return time.Time{}, false
 	}
-	return v, true
+	return now.Add(time.Duration(ttl) * time.Second), true
 }
 
 // ClientFlags returns the client flags value from the response.
@@ -136,11 +230,8 @@ func (r *Response) ClientFlags() (uint32, bool) {
 	if !ok {
 		return 0, false
 	}
-	v, err := strconv.ParseUint(string(token), 10, 32)
-	if err != nil {
-		return 0, false
-	}
-	return uint32(v), true
+	v, ok := parseUint(token, math.MaxUint32)
+	return uint32(v), ok
 }
 
 // Size returns the value size in bytes from the response.
@@ -149,11 +240,7 @@ func (r *Response) Size() (int, bool) {
 	if !ok {
 		return 0, false
 	}
-	v, err := strconv.Atoi(string(token))
-	if err != nil {
-		return 0, false
-	}
-	return v, true
+	return parseInt(token)
 }
 
 // Hit returns the hit status from the response (true if item was hit before).
@@ -171,23 +258,56 @@ func (r *Response) LastAccess() (int, bool) {
 	if !ok {
 		return 0, false
 	}
-	v, err := strconv.Atoi(string(token))
-	if err != nil {
-		return 0, false
-	}
-	return v, true
+	return parseInt(token)
 }
 
-// Key returns the key from the response (when k flag was requested).
+// Key returns the key from the response (when k flag was requested), as sent
+// on the wire: base64-encoded if the response echoes the b flag (see
+// DecodedKey).
 func (r *Response) Key() ([]byte, bool) {
 	return r.Flags.Get(FlagReturnKey)
 }
 
+// DecodedKey returns the key from the response (when k flag was requested),
+// decoded from base64 if the response echoes the b flag. It returns nil
+// without error when the response has no key, and a KeyDecodeError for a
+// malformed base64 key.
+//
+// Only keys are base64-encoded: the b flag doesn't apply to values, which
+// Data always holds as raw bytes.
+func (r *Response) DecodedKey() ([]byte, error) {
+	key, ok := r.Flags.Get(FlagReturnKey)
+	if !ok || !r.Flags.Has(FlagBase64Key) {
+		return key, nil
+	}
+	decoded := make([]byte, base64.StdEncoding.DecodedLen(len(key)))
+	n, err := base64.StdEncoding.Decode(decoded, key)
+	if err != nil {
+		return nil, &KeyDecodeError{Key: key, Err: err}
+	}
+	return decoded[:n], nil
+}
+
 // Opaque returns the opaque token from the response.
 func (r *Response) Opaque() ([]byte, bool) {
 	return r.Flags.Get(FlagOpaque)
 }
 
+// CounterValue parses the value of an arithmetic response (ma with the v
+// flag) as an unsigned 64-bit counter.
+// Returns a ParseError if the response carries no value, or if the value is not
+// a valid counter, e.g. when the key holds a non-numeric value.
+func (r *Response) CounterValue() (uint64, error) {
+	if !r.HasValue() {
+		return 0, &ParseError{Message: "response has no counter value"}
+	}
+	v, err := strconv.ParseUint(string(r.Data), 10, 64)
+	if err != nil {
+		return 0, &ParseError{Message: "invalid counter value", Err: err}
+	}
+	return v, nil
+}
+
 // ParseDebugParams parses debug key=value pairs from ME response Data.
 // ME responses contain debug information in the format: key=value key2=value2 ...
 //
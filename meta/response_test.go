@@ -1,7 +1,11 @@
 package meta
 
 import (
+	"errors"
+	"math"
+	"slices"
 	"testing"
+	"time"
 )
 
 // responseWithFlags builds a Response carrying the given raw flags string.
@@ -43,6 +47,20 @@ func TestResponse_TypedGetters(t *testing.T) {
 		}
 	})
 
+	t.Run("ExpiresAt", func(t *testing.T) {
+		now := time.Date(2026, 6, 12, 12, 0, 0, 0, time.UTC)
+		v, ok := responseWithFlags(" t3600").ExpiresAt(now)
+		if want := now.Add(time.Hour); !ok || !v.Equal(want) {
+			t.Errorf("ExpiresAt = %v/%v, want %v/true", v, ok, want)
+		}
+	})
+
+	t.Run("ExpiresAt never expires", func(t *testing.T) {
+		if _, ok := responseWithFlags(" t-1").ExpiresAt(time.Now()); ok {
+			t.Error("ExpiresAt for an infinite TTL must return false")
+		}
+	})
+
 	t.Run("ClientFlags", func(t *testing.T) {
 		v, ok := responseWithFlags(" f123").ClientFlags()
 		if !ok || v != 123 {
@@ -91,6 +109,35 @@ func TestResponse_TypedGetters(t *testing.T) {
 		}
 	})
 
+	t.Run("DecodedKey", func(t *testing.T) {
+		tests := []struct {
+			flags string
+			want  string
+		}{
+			{flags: " kmykey", want: "mykey"},
+			{flags: " kaGVsbG8gd29ybGQ= b", want: "hello world"},
+			{flags: " b kaGVsbG8gd29ybGQ=", want: "hello world"},
+			{flags: " W", want: ""},
+		}
+		for _, tt := range tests {
+			v, err := responseWithFlags(tt.flags).DecodedKey()
+			if err != nil || string(v) != tt.want {
+				t.Errorf("DecodedKey(%q) = %q/%v, want %q", tt.flags, v, err, tt.want)
+			}
+		}
+	})
+
+	t.Run("DecodedKey invalid base64", func(t *testing.T) {
+		var decodeErr *KeyDecodeError
+		_, err := responseWithFlags(" knot!base64 b").DecodedKey()
+		if !errors.As(err, &decodeErr) {
+			t.Errorf("error = %v (%T), want KeyDecodeError", err, err)
+		}
+		if ShouldCloseConnection(err) {
+			t.Error("a bad base64 key must not close the connection")
+		}
+	})
+
 	t.Run("Opaque", func(t *testing.T) {
 		v, ok := responseWithFlags(" Otok").Opaque()
 		if !ok || string(v) != "tok" {
@@ -112,6 +159,151 @@ func TestResponse_TypedGetters(t *testing.T) {
 	})
 }
 
+func TestResponse_MatchesFlags(t *testing.T) {
+	resp := responseWithFlags(" W c42 t60 k")
+
+	tests := []struct {
+		expected string
+		want     bool
+	}{
+		{expected: "", want: true},
+		{expected: " W", want: true},
+		{expected: " t60 c42", want: true},
+		{expected: " c", want: true},
+		{expected: " k", want: true},
+		{expected: " t61", want: false},
+		{expected: " kfoo", want: false},
+		{expected: " X", want: false},
+		{expected: " W Z", want: false},
+	}
+
+	for _, tt := range tests {
+		if got := resp.MatchesFlags(Flags(tt.expected)); got != tt.want {
+			t.Errorf("MatchesFlags(%q) = %v, want %v", tt.expected, got, tt.want)
+		}
+	}
+}
+
+func TestResponse_DataEqual(t *testing.T) {
+	value := func(data string) *Response { return &Response{Status: StatusVA, Data: []byte(data)} }
+	miss := &Response{Status: StatusEN}
+
+	tests := []struct {
+		name string
+		a, b *Response
+		want bool
+	}{
+		{name: "same value", a: value("v1"), b: value("v1"), want: true},
+		{name: "other value", a: value("v1"), b: value("v2")},
+		{name: "empty value and miss", a: value(""), b: miss},
+		{name: "two misses", a: miss, b: miss, want: true},
+		{name: "metadata ignored", a: &Response{Status: StatusVA, Data: []byte("v"), Flags: Flags(" c1 t60")}, b: value("v"), want: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := tt.a.DataEqual(tt.b); got != tt.want {
+				t.Errorf("DataEqual = %v, want %v", got, tt.want)
+			}
+			if got := tt.b.DataEqual(tt.a); got != tt.want {
+				t.Errorf("reversed DataEqual = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestResponse_DiffFlags(t *testing.T) {
+	a := responseWithFlags(" c1 t60 f3 k")
+	b := responseWithFlags(" f3 c2 s10 k")
+
+	got := a.DiffFlags(b)
+	want := []FlagType{FlagReturnCAS, FlagReturnTTL, FlagReturnSize}
+	if !slices.Equal(got, want) {
+		t.Errorf("DiffFlags = %q, want %q", got, want)
+	}
+
+	if got := a.DiffFlags(responseWithFlags(" k f3 t60 c1")); len(got) != 0 {
+		t.Errorf("DiffFlags of the same flags = %q, want none", got)
+	}
+}
+
+func TestParseUint(t *testing.T) {
+	tests := []struct {
+		token  string
+		limit  uint64
+		want   uint64
+		wantOK bool
+	}{
+		{token: "0", limit: math.MaxUint64, want: 0, wantOK: true},
+		{token: "12345", limit: math.MaxUint64, want: 12345, wantOK: true},
+		{token: "18446744073709551615", limit: math.MaxUint64, want: math.MaxUint64, wantOK: true},
+		{token: "18446744073709551616", limit: math.MaxUint64},
+		{token: "4294967295", limit: math.MaxUint32, want: math.MaxUint32, wantOK: true},
+		{token: "4294967296", limit: math.MaxUint32},
+		{token: "", limit: math.MaxUint64},
+		{token: "12a", limit: math.MaxUint64},
+		{token: "-1", limit: math.MaxUint64},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.token, func(t *testing.T) {
+			got, ok := parseUint([]byte(tt.token), tt.limit)
+			if got != tt.want || ok != tt.wantOK {
+				t.Errorf("parseUint(%q) = %d/%v, want %d/%v", tt.token, got, ok, tt.want, tt.wantOK)
+			}
+		})
+	}
+}
+
+func TestParseInt(t *testing.T) {
+	tests := []struct {
+		token  string
+		want   int
+		wantOK bool
+	}{
+		{token: "3600", want: 3600, wantOK: true},
+		{token: "-1", want: -1, wantOK: true},
+		{token: "-", wantOK: false},
+		{token: "--1", wantOK: false},
+		{token: "99999999999999999999", wantOK: false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.token, func(t *testing.T) {
+			got, ok := parseInt([]byte(tt.token))
+			if got != tt.want || ok != tt.wantOK {
+				t.Errorf("parseInt(%q) = %d/%v, want %d/%v", tt.token, got, ok, tt.want, tt.wantOK)
+			}
+		})
+	}
+}
+
+func TestResponse_CounterValue(t *testing.T) {
+	t.Run("valid", func(t *testing.T) {
+		resp := &Response{Status: StatusVA, Data: []byte("18446744073709551615")}
+		v, err := resp.CounterValue()
+		if err != nil || v != 18446744073709551615 {
+			t.Errorf("CounterValue = %d/%v, want max uint64/nil", v, err)
+		}
+	})
+
+	t.Run("non-numeric value", func(t *testing.T) {
+		resp := &Response{Status: StatusVA, Data: []byte("abc")}
+		var parseErr *ParseError
+		if _, err := resp.CounterValue(); !errors.As(err, &parseErr) {
+			t.Errorf("CounterValue error = %v, want ParseError", err)
+		}
+	})
+
+	t.Run("no value", func(t *testing.T) {
+		resp := &Response{Status: StatusHD}
+		var parseErr *ParseError
+		if _, err := resp.CounterValue(); !errors.As(err, &parseErr) {
+			t.Errorf("CounterValue error = %v, want ParseError", err)
+		}
+	})
+}
+
 func TestParseDebugParams_Malformed(t *testing.T) {
 	params := ParseDebugParams([]byte("exp=3600 garbage la=12 ="))
 	if got := params["exp"]; got != "3600" {
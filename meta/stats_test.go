@@ -61,6 +61,30 @@ func TestReadStatsResponse(t *testing.T) {
 	})
 }
 
+func TestReadStat(t *testing.T) {
+	r := bufio.NewReader(strings.NewReader("STAT pid 1\r\nSTAT slab_global_page_pool 0 0\r\nEND\r\n"))
+
+	var stats []Stat
+	for {
+		stat, ok, err := ReadStat(r)
+		if err != nil {
+			t.Fatalf("unexpected error: %v", err)
+		}
+		if !ok {
+			break
+		}
+		stats = append(stats, stat)
+	}
+
+	want := []Stat{{Name: "pid", Value: "1"}, {Name: "slab_global_page_pool", Value: "0 0"}}
+	if len(stats) != len(want) || stats[0] != want[0] || stats[1] != want[1] {
+		t.Errorf("stats = %+v, want %+v", stats, want)
+	}
+	if r.Buffered() != 0 {
+		t.Errorf("%d bytes left after END", r.Buffered())
+	}
+}
+
 func TestReadStatsResponse_Errors(t *testing.T) {
 	tests := []struct {
 		name    string
@@ -0,0 +1,90 @@
+package meta
+
+import (
+	"bufio"
+	"fmt"
+	"strconv"
+	"strings"
+)
+
+// Version is a parsed memcached version, e.g. "1.6.21-1" is
+// {Major: 1, Minor: 6, Patch: 21, Suffix: "-1"}.
+type Version struct {
+	Major  int
+	Minor  int
+	Patch  int
+	Suffix string // anything after the patch number, e.g. "-1" or "-rc1"
+}
+
+// AtLeast reports whether v is major.minor.patch or later, e.g. to check
+// that a server supports a feature. The suffix is ignored.
+func (v Version) AtLeast(major, minor, patch int) bool {
+	if v.Major != major {
+		return v.Major > major
+	}
+	if v.Minor != minor {
+		return v.Minor > minor
+	}
+	return v.Patch >= patch
+}
+
+func (v Version) String() string {
+	return fmt.Sprintf("%d.%d.%d%s", v.Major, v.Minor, v.Patch, v.Suffix)
+}
+
+// ParseVersion parses a "<major>.<minor>.<patch>[<suffix>]" version, as
+// returned by the version command. It returns a *ParseError on any other
+// format.
+func ParseVersion(s string) (Version, error) {
+	var v Version
+	rest := s
+	for i, field := range []*int{&v.Major, &v.Minor, &v.Patch} {
+		end := 0
+		for end < len(rest) && rest[end] >= '0' && rest[end] <= '9' {
+			end++
+		}
+		n, err := strconv.Atoi(rest[:end])
+		if err != nil {
+			return Version{}, &ParseError{Message: "invalid version: " + s, Err: err}
+		}
+		*field = n
+		rest = rest[end:]
+
+		if i < 2 {
+			var ok bool
+			if rest, ok = strings.CutPrefix(rest, "."); !ok {
+				return Version{}, &ParseError{Message: "invalid version: " + s}
+			}
+		}
+	}
+	v.Suffix = rest
+	return v, nil
+}
+
+// ReadVersionResponse reads the response of the version command:
+// "VERSION <version>".
+func ReadVersionResponse(r *bufio.Reader) (Version, error) {
+	line, err := r.ReadString('\n')
+	if err != nil {
+		return Version{}, err
+	}
+
+	line = strings.TrimSuffix(line, CRLF)
+	line = strings.TrimSuffix(line, "\n")
+
+	if msg, ok := strings.CutPrefix(line, ErrorClientPrefix+" "); ok {
+		return Version{}, &ClientError{Message: msg}
+	}
+	if msg, ok := strings.CutPrefix(line, ErrorServerPrefix+" "); ok {
+		return Version{}, &ServerError{Message: msg}
+	}
+	if line == ErrorGeneric {
+		return Version{}, &GenericError{Message: "ERROR"}
+	}
+
+	version, ok := strings.CutPrefix(line, VersionPrefix+" ")
+	if !ok {
+		return Version{}, &ParseError{Message: "unexpected version response: " + line}
+	}
+	return ParseVersion(version)
+}
@@ -0,0 +1,85 @@
+package meta
+
+import (
+	"bufio"
+	"errors"
+	"strings"
+	"testing"
+)
+
+func TestParseVersion(t *testing.T) {
+	tests := []struct {
+		input string
+		want  Version
+	}{
+		{"1.6.21", Version{Major: 1, Minor: 6, Patch: 21}},
+		{"1.6.21-1", Version{Major: 1, Minor: 6, Patch: 21, Suffix: "-1"}},
+		{"1.6.0-rc1", Version{Major: 1, Minor: 6, Patch: 0, Suffix: "-rc1"}},
+	}
+	for _, tt := range tests {
+		got, err := ParseVersion(tt.input)
+		if err != nil {
+			t.Errorf("ParseVersion(%q) error: %v", tt.input, err)
+			continue
+		}
+		if got != tt.want {
+			t.Errorf("ParseVersion(%q) = %+v, want %+v", tt.input, got, tt.want)
+		}
+		if got.String() != tt.input {
+			t.Errorf("ParseVersion(%q).String() = %q", tt.input, got.String())
+		}
+	}
+}
+
+func TestParseVersion_Invalid(t *testing.T) {
+	for _, input := range []string{"", "1", "1.6", "1.6.", "v1.6.21", "1.x.21", "1..21"} {
+		_, err := ParseVersion(input)
+		var parseErr *ParseError
+		if !errors.As(err, &parseErr) {
+			t.Errorf("ParseVersion(%q) error = %v, want a ParseError", input, err)
+		}
+	}
+}
+
+func TestVersion_AtLeast(t *testing.T) {
+	v := Version{Major: 1, Minor: 6, Patch: 21}
+	tests := []struct {
+		major, minor, patch int
+		want                bool
+	}{
+		{1, 6, 21, true},
+		{1, 6, 0, true},
+		{1, 5, 99, true},
+		{0, 9, 0, true},
+		{1, 6, 22, false},
+		{1, 7, 0, false},
+		{2, 0, 0, false},
+	}
+	for _, tt := range tests {
+		if got := v.AtLeast(tt.major, tt.minor, tt.patch); got != tt.want {
+			t.Errorf("%s.AtLeast(%d, %d, %d) = %v, want %v", v, tt.major, tt.minor, tt.patch, got, tt.want)
+		}
+	}
+}
+
+func TestReadVersionResponse(t *testing.T) {
+	v, err := ReadVersionResponse(bufio.NewReader(strings.NewReader("VERSION 1.6.21\r\n")))
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if v != (Version{Major: 1, Minor: 6, Patch: 21}) {
+		t.Errorf("version = %+v", v)
+	}
+
+	_, err = ReadVersionResponse(bufio.NewReader(strings.NewReader("ERROR\r\n")))
+	var genericErr *GenericError
+	if !errors.As(err, &genericErr) {
+		t.Errorf("error = %v, want a GenericError", err)
+	}
+
+	_, err = ReadVersionResponse(bufio.NewReader(strings.NewReader("VERSION unknown\r\n")))
+	var parseErr *ParseError
+	if !errors.As(err, &parseErr) {
+		t.Errorf("error = %v, want a ParseError", err)
+	}
+}
@@ -0,0 +1,33 @@
+package meta
+
+import (
+	"bufio"
+	"strings"
+)
+
+// WatchEvent is one log event pushed by the server after a "watch" command.
+//
+// Wire format: ts=<time> gid=<id> type=<event type> [<name>=<value>]*\r\n
+type WatchEvent struct {
+	Type   string            // event type, e.g. "item_get" or "item_store"
+	Fields map[string]string // every name=value field of the line, including ts, gid and type
+}
+
+// ReadWatchEvent reads the next event of a watch stream. A line without an
+// event type is a ParseError.
+func ReadWatchEvent(r *bufio.Reader) (WatchEvent, error) {
+	line, err := r.ReadString('\n')
+	if err != nil {
+		return WatchEvent{}, err
+	}
+
+	line = strings.TrimSuffix(line, CRLF)
+	line = strings.TrimSuffix(line, "\n")
+
+	fields := ParseDebugParams([]byte(line))
+	eventType, ok := fields["type"]
+	if !ok {
+		return WatchEvent{}, &ParseError{Message: "invalid watch event: " + line}
+	}
+	return WatchEvent{Type: eventType, Fields: fields}, nil
+}
@@ -0,0 +1,39 @@
+package meta
+
+import (
+	"bufio"
+	"bytes"
+	"errors"
+	"strings"
+	"testing"
+)
+
+func TestReadWatchEvent(t *testing.T) {
+	r := bufio.NewReader(strings.NewReader(
+		"ts=1700000000.123456 gid=7 type=item_get key=foo status=found clsid=1 cfd=20 size=3\r\n" +
+			"garbage\r\n"))
+
+	event, err := ReadWatchEvent(r)
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if event.Type != "item_get" || event.Fields["key"] != "foo" || event.Fields["gid"] != "7" {
+		t.Errorf("event = %+v", event)
+	}
+
+	_, err = ReadWatchEvent(r)
+	var parseErr *ParseError
+	if !errors.As(err, &parseErr) {
+		t.Errorf("error = %v, want a ParseError", err)
+	}
+}
+
+func TestWriteWatchRequest(t *testing.T) {
+	var buf bytes.Buffer
+	if err := WriteRequest(&buf, &Request{Command: CmdWatch, Key: "fetchers mutations"}); err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if buf.String() != "watch fetchers mutations\r\n" {
+		t.Errorf("request = %q", buf.String())
+	}
+}
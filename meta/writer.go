@@ -34,14 +34,20 @@ func putBuffer(buf *bytes.Buffer) {
 // Keys must be 1-250 bytes and contain no whitespace (unless base64-encoded).
 // Returns an error describing the validation failure.
 func ValidateKey(key string, hasBase64Flag bool) error {
+	return ValidateKeyWithLimit(key, hasBase64Flag, MaxKeyLength)
+}
+
+// ValidateKeyWithLimit is ValidateKey with a custom maximum key length, for
+// servers built with a key limit other than MaxKeyLength.
+func ValidateKeyWithLimit(key string, hasBase64Flag bool, maxKeyLength int) error {
 	keyLen := len(key)
 
 	if keyLen < MinKeyLength {
-		return &InvalidKeyError{Message: "key is empty"}
+		return &InvalidKeyError{Message: ErrEmptyKey.Error(), Err: ErrEmptyKey}
 	}
 
-	if keyLen > MaxKeyLength {
-		return &InvalidKeyError{Message: "key exceeds maximum length of 250 bytes"}
+	if keyLen > maxKeyLength {
+		return &InvalidKeyError{Message: "key exceeds maximum length of " + strconv.Itoa(maxKeyLength) + " bytes"}
 	}
 
 	// Whitespace is only allowed if key is base64-encoded
@@ -52,6 +58,23 @@ func ValidateKey(key string, hasBase64Flag bool) error {
 	return nil
 }
 
+// ValidateBatchKeys validates the key of every request of a batch, so that an
+// invalid key is caught before any request is written. Commands without a key
+// (mn, stats and admin commands) are skipped. It returns a *BatchKeyError for
+// the first invalid key.
+func ValidateBatchKeys(reqs []*Request, maxKeyLength int) error {
+	for i, req := range reqs {
+		switch req.Command {
+		case CmdNoOp, CmdStats, CmdCacheMemlimit, CmdVerbosity, CmdLRUCrawler, CmdVersion, CmdWatch, CmdQuit:
+			continue
+		}
+		if err := ValidateKeyWithLimit(req.Key, req.HasFlag(FlagBase64Key), maxKeyLength); err != nil {
+			return &BatchKeyError{Index: i, Err: err}
+		}
+	}
+	return nil
+}
+
 // WriteRequest serializes a Request to wire format and writes it to w.
 // Format: <command> <key> [<size>] <flags>*\r\n[<data>\r\n]
 //
@@ -67,6 +90,12 @@ func ValidateKey(key string, hasBase64Flag bool) error {
 //   - Single write call for header reduces syscalls
 //   - Data block written directly (no buffering for large values)
 func WriteRequest(w io.Writer, req *Request) error {
+	return WriteRequestWithKeyLimit(w, req, MaxKeyLength)
+}
+
+// WriteRequestWithKeyLimit is WriteRequest with a custom maximum key length,
+// for servers built with a key limit other than MaxKeyLength.
+func WriteRequestWithKeyLimit(w io.Writer, req *Request, maxKeyLength int) error {
 	// Get buffer from pool
 	buf := getBuffer()
 	defer putBuffer(buf)
@@ -79,8 +108,9 @@ func WriteRequest(w io.Writer, req *Request) error {
 		return err
 	}
 
-	// stats command has optional args but no key or flags
-	if req.Command == CmdStats {
+	// stats and admin commands have optional args but no key or flags
+	switch req.Command {
+	case CmdStats, CmdCacheMemlimit, CmdVerbosity, CmdLRUCrawler, CmdVersion, CmdWatch, CmdQuit:
 		buf.WriteString(string(req.Command))
 		if req.Key != "" {
 			buf.WriteString(Space)
@@ -93,7 +123,7 @@ func WriteRequest(w io.Writer, req *Request) error {
 
 	// Validate key before writing
 	hasBase64Flag := req.HasFlag(FlagBase64Key)
-	if err := ValidateKey(req.Key, hasBase64Flag); err != nil {
+	if err := ValidateKeyWithLimit(req.Key, hasBase64Flag, maxKeyLength); err != nil {
 		return err
 	}
 
@@ -48,6 +48,17 @@ func TestWriteRequest_Stats(t *testing.T) {
 	})
 }
 
+func TestWriteRequest_CacheMemlimit(t *testing.T) {
+	var buf bytes.Buffer
+	err := WriteRequest(&buf, &Request{Command: CmdCacheMemlimit, Key: "1024"})
+	if err != nil {
+		t.Fatalf("WriteRequest failed: %v", err)
+	}
+	if got := buf.String(); got != "cache_memlimit 1024\r\n" {
+		t.Errorf("wire = %q, want %q", got, "cache_memlimit 1024\r\n")
+	}
+}
+
 func TestWriteRequest_SetWithEmptyData(t *testing.T) {
 	var buf bytes.Buffer
 	err := WriteRequest(&buf, NewRequest(CmdSet, "key", nil))
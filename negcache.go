@@ -0,0 +1,216 @@
+package memcache
+
+import (
+	"container/list"
+	"context"
+	"encoding/base64"
+	"sync"
+	"time"
+
+	"github.com/pior/memcache/meta"
+)
+
+// DefaultNegativeCacheTTL is how long a miss is remembered when
+// Config.NegativeCacheSize is set without Config.NegativeCacheTTL.
+const DefaultNegativeCacheTTL = time.Second
+
+// negativeCacheTTLKey is the context key of the TTL set by
+// WithNegativeCacheTTL.
+type negativeCacheTTLKey struct{}
+
+// WithNegativeCacheTTL returns a copy of ctx that shortens how old a
+// remembered miss may be to answer the gets run with it, e.g. a tight TTL for
+// a key expected to be stored soon by another client. The TTL is capped at
+// Config.NegativeCacheTTL, which remains how long misses are kept. A zero TTL
+// makes the gets reach the server. Without a negative cache, it has no
+// effect.
+func WithNegativeCacheTTL(ctx context.Context, ttl time.Duration) context.Context {
+	return context.WithValue(ctx, negativeCacheTTLKey{}, ttl)
+}
+
+// negativeCache remembers the keys of recent misses, to answer gets for them
+// without a round trip. It is a bounded LRU: when full, the least recently
+// used miss is forgotten.
+//
+// A miss is only remembered if no write was reported since its get started
+// (see begin): a get racing with a write to the same key could otherwise
+// record a miss that the write already made stale. A get that finds the item,
+// e.g. one with a TTL shorter than the age of the miss, forgets the miss the
+// same way.
+type negativeCache struct {
+	size int
+	ttl  time.Duration // how long a miss is kept, the maximum TTL of a get
+
+	mu      sync.Mutex
+	entries map[string]*list.Element // of *negativeEntry
+	lru     list.List                // most recently used first
+	epoch   uint64                   // incremented by every invalidation
+}
+
+type negativeEntry struct {
+	key    string
+	missed time.Time
+}
+
+func newNegativeCache(size int, ttl time.Duration) *negativeCache {
+	if ttl <= 0 {
+		ttl = DefaultNegativeCacheTTL
+	}
+	return &negativeCache{size: size, ttl: ttl, entries: make(map[string]*list.Element)}
+}
+
+// execute runs exec, the execution of req, through the cache: a cacheable get
+// of a key that missed less than the TTL of ctx ago is answered with a miss
+// without calling exec, a request that may write its key invalidates it, and
+// so does a get that finds the item.
+func (nc *negativeCache) execute(ctx context.Context, req *meta.Request, exec func() (*meta.Response, error)) (*meta.Response, error) {
+	if !nc.cacheable(req) {
+		if writes(req) {
+			defer nc.invalidate(req)
+			return exec()
+		}
+		resp, err := exec()
+		if err == nil && found(req, resp) {
+			nc.invalidate(req)
+		}
+		return resp, err
+	}
+
+	if nc.contains(req.Key, nc.ttlFor(ctx)) {
+		return &meta.Response{Status: meta.StatusEN}, nil
+	}
+
+	epoch := nc.begin()
+	resp, err := exec()
+	if err == nil {
+		if resp.Status == meta.StatusEN {
+			nc.add(req.Key, epoch)
+		} else if found(req, resp) {
+			nc.invalidate(req)
+		}
+	}
+	return resp, err
+}
+
+// invalidateBatch invalidates the keys written by a batch and those its gets
+// found, once it completed. resps holds the response of each request, nil
+// for those of a failed batch.
+func (nc *negativeCache) invalidateBatch(reqs []*meta.Request, resps []*meta.Response) {
+	for i, req := range reqs {
+		if writes(req) || (resps[i] != nil && found(req, resps[i])) {
+			nc.invalidate(req)
+		}
+	}
+}
+
+// ttlFor returns how old a miss may be to answer a get run with ctx: the TTL
+// set with WithNegativeCacheTTL, capped at the cache TTL.
+func (nc *negativeCache) ttlFor(ctx context.Context) time.Duration {
+	if ttl, ok := ctx.Value(negativeCacheTTLKey{}).(time.Duration); ok {
+		return min(ttl, nc.ttl)
+	}
+	return nc.ttl
+}
+
+// found reports whether resp is a get of req finding the item.
+func found(req *meta.Request, resp *meta.Response) bool {
+	return req.Command == meta.CmdGet && (resp.Status == meta.StatusHD || resp.Status == meta.StatusVA)
+}
+
+// writes reports whether req may store, modify or delete its item.
+func writes(req *meta.Request) bool {
+	return req.Command != meta.CmdGet || req.HasFlag(meta.FlagVivify)
+}
+
+// cacheable reports whether req is a get that the cache can answer: the
+// synthesized miss has no flags to echo, and a get that creates the item on a
+// miss (N flag) is a write.
+func (nc *negativeCache) cacheable(req *meta.Request) bool {
+	return req.Command == meta.CmdGet &&
+		!req.HasFlag(meta.FlagVivify) &&
+		!req.HasFlag(meta.FlagQuiet) &&
+		!req.HasFlag(meta.FlagReturnKey) &&
+		!req.HasFlag(meta.FlagOpaque) &&
+		!req.HasFlag(meta.FlagBase64Key)
+}
+
+// contains reports whether key missed at most maxAge ago. A miss older than
+// the cache TTL is forgotten.
+func (nc *negativeCache) contains(key string, maxAge time.Duration) bool {
+	if maxAge <= 0 {
+		return false
+	}
+
+	nc.mu.Lock()
+	defer nc.mu.Unlock()
+
+	elem, ok := nc.entries[key]
+	if !ok {
+		return false
+	}
+	age := time.Since(elem.Value.(*negativeEntry).missed)
+	if age > nc.ttl {
+		nc.remove(elem)
+		return false
+	}
+	if age > maxAge {
+		return false
+	}
+	nc.lru.MoveToFront(elem)
+	return true
+}
+
+// begin returns the token to pass to add once the get has missed.
+func (nc *negativeCache) begin() uint64 {
+	nc.mu.Lock()
+	defer nc.mu.Unlock()
+	return nc.epoch
+}
+
+// add remembers a miss of key, unless a write was reported since begin
+// returned epoch.
+func (nc *negativeCache) add(key string, epoch uint64) {
+	nc.mu.Lock()
+	defer nc.mu.Unlock()
+
+	if epoch != nc.epoch {
+		return
+	}
+
+	missed := time.Now()
+	if elem, ok := nc.entries[key]; ok {
+		elem.Value.(*negativeEntry).missed = missed
+		nc.lru.MoveToFront(elem)
+		return
+	}
+
+	nc.entries[key] = nc.lru.PushFront(&negativeEntry{key: key, missed: missed})
+	if nc.lru.Len() > nc.size {
+		nc.remove(nc.lru.Back())
+	}
+}
+
+// invalidate forgets the miss of the key of req, a request that may have
+// written it or that found it. It must be called once the request completed,
+// successfully or not.
+func (nc *negativeCache) invalidate(req *meta.Request) {
+	key := req.Key
+	if req.HasFlag(meta.FlagBase64Key) {
+		if decoded, err := base64.StdEncoding.DecodeString(key); err == nil {
+			key = string(decoded)
+		}
+	}
+
+	nc.mu.Lock()
+	defer nc.mu.Unlock()
+
+	nc.epoch++
+	if elem, ok := nc.entries[key]; ok {
+		nc.remove(elem)
+	}
+}
+
+func (nc *negativeCache) remove(elem *list.Element) {
+	delete(nc.entries, elem.Value.(*negativeEntry).key)
+	nc.lru.Remove(elem)
+}
@@ -0,0 +1,186 @@
+package memcache
+
+import (
+	"context"
+	"testing"
+	"testing/synctest"
+	"time"
+
+	"github.com/pior/memcache/internal/testutils"
+	"github.com/pior/memcache/meta"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func newNegativeCacheTestClient(t *testing.T, responses ...string) (*Client, *testutils.ConnectionMock) {
+	mock := testutils.NewConnectionMock(responses...)
+	client := newTestClientWithConfig(t, mock, Config{
+		NegativeCacheSize: 10,
+		NegativeCacheTTL:  time.Minute,
+	})
+	return client, mock
+}
+
+func TestClient_NegativeCache(t *testing.T) {
+	ctx := context.Background()
+
+	t.Run("repeated miss skips the round trip", func(t *testing.T) {
+		client, mock := newNegativeCacheTestClient(t, "EN\r\n")
+
+		for range 3 {
+			item, err := client.Get(ctx, "key")
+			require.NoError(t, err)
+			assert.False(t, item.Found)
+		}
+		assertRequest(t, mock, "mg key v\r\n")
+	})
+
+	t.Run("set after miss invalidates", func(t *testing.T) {
+		client, mock := newNegativeCacheTestClient(t, "EN\r\n", "HD\r\n", "VA 1\r\nv\r\n")
+
+		item, err := client.Get(ctx, "key")
+		require.NoError(t, err)
+		assert.False(t, item.Found)
+
+		require.NoError(t, client.Set(ctx, Item{Key: "key", Value: []byte("v")}))
+
+		item, err = client.Get(ctx, "key")
+		require.NoError(t, err)
+		assert.Equal(t, "v", string(item.Value))
+		assertRequest(t, mock, "mg key v\r\nms key 1\r\nv\r\nmg key v\r\n")
+	})
+
+	t.Run("failed write invalidates", func(t *testing.T) {
+		client, mock := newNegativeCacheTestClient(t, "EN\r\n", "SERVER_ERROR out of memory\r\n", "VA 1\r\nv\r\n")
+
+		_, err := client.Get(ctx, "key")
+		require.NoError(t, err)
+
+		// The store may have been applied before the server reported the
+		// error: the miss is forgotten anyway.
+		require.Error(t, client.Set(ctx, Item{Key: "key", Value: []byte("v")}))
+
+		item, err := client.Get(ctx, "key")
+		require.NoError(t, err)
+		assert.True(t, item.Found)
+		assertRequest(t, mock, "mg key v\r\nms key 1\r\nv\r\nmg key v\r\n")
+	})
+
+	t.Run("batch write invalidates", func(t *testing.T) {
+		client, mock := newNegativeCacheTestClient(t, "EN\r\n", "HD\r\n", "MN\r\n", "VA 1\r\nv\r\n")
+
+		_, err := client.Get(ctx, "key")
+		require.NoError(t, err)
+
+		require.NoError(t, NewBatchCommands(client).MultiSet(ctx, []Item{{Key: "key", Value: []byte("v")}}))
+
+		item, err := client.Get(ctx, "key")
+		require.NoError(t, err)
+		assert.True(t, item.Found)
+		assertRequest(t, mock, "mg key v\r\nms key 1\r\nv\r\nmn\r\nmg key v\r\n")
+	})
+
+	t.Run("context TTL shorter than the miss age reaches the server", func(t *testing.T) {
+		client, mock := newNegativeCacheTestClient(t, "EN\r\n", "VA 1\r\nv\r\n", "VA 1\r\nv\r\n")
+
+		_, err := client.Get(ctx, "key")
+		require.NoError(t, err)
+
+		// The item stored elsewhere is found, which forgets the miss for
+		// the gets without a TTL too.
+		item, err := client.Get(WithNegativeCacheTTL(ctx, 0), "key")
+		require.NoError(t, err)
+		assert.True(t, item.Found)
+
+		item, err = client.Get(ctx, "key")
+		require.NoError(t, err)
+		assert.True(t, item.Found)
+		assertRequest(t, mock, "mg key v\r\nmg key v\r\nmg key v\r\n")
+	})
+
+	t.Run("batch get finding the item invalidates", func(t *testing.T) {
+		client, mock := newNegativeCacheTestClient(t, "EN\r\n", "VA 1\r\nv\r\n", "MN\r\n", "VA 1\r\nv\r\n")
+
+		_, err := client.Get(ctx, "key")
+		require.NoError(t, err)
+
+		items, err := NewBatchCommands(client).MultiGet(ctx, []string{"key"})
+		require.NoError(t, err)
+		require.Len(t, items, 1)
+		assert.True(t, items[0].Found)
+
+		item, err := client.Get(ctx, "key")
+		require.NoError(t, err)
+		assert.True(t, item.Found)
+		assertRequest(t, mock, "mg key v\r\nmg key v\r\nmn\r\nmg key v\r\n")
+	})
+
+	t.Run("gets with flags to echo reach the server", func(t *testing.T) {
+		client, mock := newNegativeCacheTestClient(t, "EN\r\n", "EN\r\n")
+
+		_, err := client.Get(ctx, "key")
+		require.NoError(t, err)
+
+		resp, err := client.Execute(ctx, meta.NewRequest(meta.CmdGet, "key", nil).AddReturnKey())
+		require.NoError(t, err)
+		assert.Equal(t, meta.StatusEN, resp.Status)
+		assertRequest(t, mock, "mg key v\r\nmg key k\r\n")
+	})
+}
+
+func TestNegativeCache(t *testing.T) {
+	t.Run("miss expires after the TTL", func(t *testing.T) {
+		synctest.Test(t, func(t *testing.T) {
+			nc := newNegativeCache(10, time.Second)
+			nc.add("key", nc.begin())
+			assert.True(t, nc.contains("key", nc.ttl))
+
+			time.Sleep(time.Second + time.Nanosecond)
+			assert.False(t, nc.contains("key", nc.ttl))
+		})
+	})
+
+	t.Run("context TTL is capped at the cache TTL", func(t *testing.T) {
+		synctest.Test(t, func(t *testing.T) {
+			ctx := context.Background()
+			nc := newNegativeCache(10, time.Second)
+			nc.add("key", nc.begin())
+
+			time.Sleep(500 * time.Millisecond)
+			assert.False(t, nc.contains("key", nc.ttlFor(WithNegativeCacheTTL(ctx, 100*time.Millisecond))))
+			assert.True(t, nc.contains("key", nc.ttlFor(ctx)))
+
+			time.Sleep(time.Second)
+			assert.False(t, nc.contains("key", nc.ttlFor(WithNegativeCacheTTL(ctx, time.Hour))))
+		})
+	})
+
+	t.Run("least recently used miss is evicted", func(t *testing.T) {
+		nc := newNegativeCache(2, time.Minute)
+		nc.add("k1", nc.begin())
+		nc.add("k2", nc.begin())
+		assert.True(t, nc.contains("k1", nc.ttl))
+		nc.add("k3", nc.begin())
+
+		assert.True(t, nc.contains("k1", nc.ttl))
+		assert.False(t, nc.contains("k2", nc.ttl))
+		assert.True(t, nc.contains("k3", nc.ttl))
+	})
+
+	t.Run("miss racing with a write is not remembered", func(t *testing.T) {
+		nc := newNegativeCache(10, time.Minute)
+		epoch := nc.begin()
+		nc.invalidate(meta.NewRequest(meta.CmdSet, "key", []byte("v")))
+		nc.add("key", epoch)
+
+		assert.False(t, nc.contains("key", nc.ttl))
+	})
+
+	t.Run("base64 key invalidates the decoded key", func(t *testing.T) {
+		nc := newNegativeCache(10, time.Minute)
+		nc.add("key", nc.begin())
+		nc.invalidate(meta.NewRequest(meta.CmdDelete, "a2V5", nil).AddBase64Key())
+
+		assert.False(t, nc.contains("key", nc.ttl))
+	})
+}
@@ -3,6 +3,11 @@ package memcache
 import (
 	"context"
 	"errors"
+	"fmt"
+	"iter"
+	"net"
+	"os"
+	"sync/atomic"
 	"time"
 
 	"github.com/pior/memcache/meta"
@@ -10,6 +15,20 @@ import (
 )
 
 func NewServerPool(addr string, config Config) (*ServerPool, error) {
+	sp := &ServerPool{
+		addr:            addr,
+		maxConnLifetime: config.MaxConnLifetime,
+		hooks:           config.Hooks,
+		keyInErrors:     config.KeyInErrors,
+		closedCheckIdle: closedCheckIdle,
+	}
+	if config.Hooks.OnSlowOp != nil {
+		sp.slowOpThreshold = config.SlowOpThreshold
+	}
+	if config.LatencyHistogram {
+		sp.latency = &latencyHistogram{}
+	}
+
 	constructor := func(ctx context.Context) (*Connection, error) {
 		// Apply ConnectTimeout for connection establishment
 		dialCtx := ctx
@@ -19,33 +38,72 @@ func NewServerPool(addr string, config Config) (*ServerPool, error) {
 			defer cancel()
 		}
 
-		netConn, err := config.Dialer.DialContext(dialCtx, "tcp", addr)
+		netConn, err := dial(dialCtx, addr, config)
 		if err != nil {
+			if sp.hooks.OnConnectError != nil {
+				sp.hooks.OnConnectError(addr, err)
+			}
 			return nil, err
 		}
 
-		return NewConnection(netConn, config.Timeout), nil
+		if config.WireLogger != nil {
+			netConn = newWireLogConn(netConn, config.WireLogger, addr, config.WireLogMaxBytes)
+		}
+
+		conn := NewConnection(netConn, config.Timeout)
+		conn.MaxKeyLength = config.MaxKeyLength
+		conn.BufferPool = config.BufferPool
+		conn.LenientLineEndings = config.LenientLineEndings
+
+		if config.Username != "" {
+			if err := conn.Authenticate(dialCtx, config.Username, config.Password); err != nil {
+				conn.Close()
+				if sp.hooks.OnConnectError != nil {
+					sp.hooks.OnConnectError(addr, err)
+				}
+				return nil, err
+			}
+		}
+		sp.connected()
+		return conn, nil
 	}
 
 	pool, err := config.NewPool(constructor, config.MaxSize)
 	if err != nil {
 		return nil, err
 	}
+	sp.pool = pool
 
-	var breaker *gobreaker.CircuitBreaker[bool]
 	if config.CircuitBreakerSettings != nil {
 		settings := *config.CircuitBreakerSettings
 		settings.Name = addr
 
-		breaker = gobreaker.NewCircuitBreaker[bool](settings)
+		sp.circuitBreaker = gobreaker.NewCircuitBreaker[bool](settings)
 	}
 
-	return &ServerPool{
-		addr:            addr,
-		pool:            pool,
-		circuitBreaker:  breaker,
-		maxConnLifetime: config.MaxConnLifetime,
-	}, nil
+	return sp, nil
+}
+
+// dial connects to addr and applies the configured socket options.
+func dial(ctx context.Context, addr string, config Config) (net.Conn, error) {
+	netConn, err := config.Dialer.DialContext(ctx, "tcp", addr)
+	if err != nil {
+		return nil, err
+	}
+
+	if config.TCPQuickAck {
+		if err := setQuickAck(netConn); err != nil {
+			netConn.Close()
+			return nil, err
+		}
+	}
+
+	if err := setBufferSizes(netConn, config.SocketReadBuffer, config.SocketWriteBuffer); err != nil {
+		netConn.Close()
+		return nil, err
+	}
+
+	return netConn, nil
 }
 
 // ServerPool wraps a pool, a circuit breaker with its server address.
@@ -54,20 +112,122 @@ type ServerPool struct {
 	pool            Pool
 	circuitBreaker  *gobreaker.CircuitBreaker[bool]
 	maxConnLifetime time.Duration
+	hooks           Hooks
+	keyInErrors     bool
+	slowOpThreshold time.Duration     // zero when slow operations are not reported
+	latency         *latencyHistogram // nil when latencies are not recorded
+	closedCheckIdle time.Duration     // idle time after which acquire checks the socket
+
+	// lostConns counts connections closed on error that were not replaced
+	// yet, to report their replacements as reconnects.
+	lostConns atomic.Int64
+}
+
+// connected reports a newly established connection to the hooks.
+func (sp *ServerPool) connected() {
+	if sp.hooks.OnConnect != nil {
+		sp.hooks.OnConnect(sp.addr)
+	}
+
+	for {
+		lost := sp.lostConns.Load()
+		if lost <= 0 {
+			return
+		}
+		if sp.lostConns.CompareAndSwap(lost, lost-1) {
+			if sp.hooks.OnReconnect != nil {
+				sp.hooks.OnReconnect(sp.addr)
+			}
+			return
+		}
+	}
+}
+
+// destroy closes a connection that failed with a connection or protocol
+// error. The next connection established to the server is a reconnect.
+func (sp *ServerPool) destroy(resource Resource) {
+	sp.lostConns.Add(1)
+	resource.Destroy()
+}
+
+// closedCheckIdle is how long a connection sits idle in the pool before
+// acquire checks whether the server closed it: the check costs a syscall,
+// not worth paying on every checkout of a busy pool, whose connections are
+// rarely closed while in use.
+const closedCheckIdle = time.Second
+
+// acquire gets a connection from the pool. An idle connection that exceeded
+// MaxConnLifetime while waiting in the pool (health checks disabled, or not
+// run yet) is replaced by another one instead of being used past its lifetime,
+// as is one that the server closed meanwhile (see Connection.IsClosed), which
+// is only checked for a connection idle for closedCheckIdle.
+// Only one is replaced per checkout, so a lifetime shorter than the dial time
+// cannot make the checkout spin: the rest are recycled by later checkouts or
+// on release.
+func (sp *ServerPool) acquire(ctx context.Context) (Resource, error) {
+	resource, err := sp.pool.Acquire(ctx)
+	if err != nil {
+		return resource, err
+	}
+
+	switch {
+	case sp.expired(resource):
+		resource.Destroy()
+	case resource.IdleDuration() >= sp.closedCheckIdle && resource.Value().IsClosed():
+		sp.destroy(resource)
+	default:
+		return resource, nil
+	}
+	return sp.pool.Acquire(ctx)
+}
+
+// expired reports whether the connection has exceeded MaxConnLifetime.
+func (sp *ServerPool) expired(resource Resource) bool {
+	return sp.maxConnLifetime > 0 && time.Since(resource.CreationTime()) > sp.maxConnLifetime
 }
 
 // release returns a connection to the pool, or destroys it if it has
 // exceeded MaxConnLifetime. Enforcing the lifetime here (and not only in the
 // health check loop) matters under sustained load: a saturated pool never has
 // idle connections, so the health check alone would never recycle them.
+//
+// A connection with pending writes is destroyed too: the next request would
+// otherwise flush those stale bytes ahead of its own. So is a connection that
+// was quit, which the server closes.
 func (sp *ServerPool) release(resource Resource) {
-	if sp.maxConnLifetime > 0 && time.Since(resource.CreationTime()) > sp.maxConnLifetime {
+	if sp.expired(resource) || resource.Value().quit {
 		resource.Destroy()
 		return
 	}
+	if resource.Value().HasPendingWrites() {
+		sp.destroy(resource)
+		return
+	}
 	resource.Release()
 }
 
+// withConn runs fn on a connection acquired from the pool, for operations
+// that are not a meta request (stats, admin commands). The connection is
+// destroyed if fn fails with a connection-corrupting error.
+func (sp *ServerPool) withConn(ctx context.Context, op string, fn func(conn *Connection) error) error {
+	resource, err := sp.acquire(ctx)
+	if err != nil {
+		return sp.wrapErr(op, "", err)
+	}
+
+	if err := fn(resource.Value()); err != nil {
+		if meta.ShouldCloseConnection(err) {
+			sp.destroy(resource)
+		} else {
+			sp.release(resource)
+		}
+		return sp.wrapErr(op, "", err)
+	}
+
+	sp.release(resource)
+	return nil
+}
+
 func (sp *ServerPool) Address() string {
 	return sp.addr
 }
@@ -117,6 +277,10 @@ func (sp *ServerPool) Metrics() PoolMetrics {
 //
 // Failures are returned as *OpError carrying the operation, key, and server address.
 func (sp *ServerPool) Execute(ctx context.Context, req *meta.Request) (*meta.Response, error) {
+	if sp.slowOpThreshold > 0 || sp.latency != nil {
+		defer sp.observeOp(string(req.Command), len(req.Key), time.Now())
+	}
+
 	if sp.circuitBreaker == nil {
 		return sp.execRequestDirect(ctx, req)
 	}
@@ -137,6 +301,18 @@ func (sp *ServerPool) Execute(ctx context.Context, req *meta.Request) (*meta.Res
 	return resp, execErr
 }
 
+// observeOp records the latency of an operation started at start, and
+// reports it to the OnSlowOp hook if it exceeded the threshold.
+func (sp *ServerPool) observeOp(op string, keyLen int, start time.Time) {
+	elapsed := time.Since(start)
+	if sp.latency != nil {
+		sp.latency.record(elapsed)
+	}
+	if sp.slowOpThreshold > 0 && elapsed > sp.slowOpThreshold {
+		sp.hooks.OnSlowOp(sp.addr, op, keyLen, elapsed)
+	}
+}
+
 // wrapErr wraps an error with operation and server context, unless it
 // already carries it.
 func (sp *ServerPool) wrapErr(op, key string, err error) error {
@@ -144,12 +320,25 @@ func (sp *ServerPool) wrapErr(op, key string, err error) error {
 	if errors.As(err, &opErr) {
 		return err
 	}
-	return &OpError{Op: op, Key: key, Server: sp.addr, Err: err}
+	return &OpError{Op: op, Key: key, Server: sp.addr, Err: err, keyInMessage: sp.keyInErrors}
+}
+
+// deadlineErr makes a socket timeout caused by the context deadline match
+// context.DeadlineExceeded: the caller's own latency budget ran out, as opposed
+// to Config.Timeout capping the operation. The cause is the deadline conn was
+// given, not ctx.Err(): the context may not report its expiry yet when the
+// socket deadline fires at the same instant.
+func deadlineErr(conn *Connection, err error) error {
+	if errors.Is(err, os.ErrDeadlineExceeded) && conn.contextDeadline {
+		return fmt.Errorf("%w: %w", context.DeadlineExceeded, err)
+	}
+	return err
 }
 
 // breakerError filters out errors that don't indicate server trouble, so they
 // don't count as failures and trip the circuit breaker: a caller canceling its
-// context or passing an invalid key says nothing about the server's health.
+// context, passing an invalid key or an unsupported request says nothing
+// about the server's health.
 func breakerError(err error) error {
 	if err == nil || errors.Is(err, context.Canceled) {
 		return nil
@@ -158,6 +347,10 @@ func breakerError(err error) error {
 	if errors.As(err, &invalidKey) {
 		return nil
 	}
+	var unsupported *UnsupportedRequestError
+	if errors.As(err, &unsupported) {
+		return nil
+	}
 	return err
 }
 
@@ -165,7 +358,7 @@ func breakerError(err error) error {
 func (sp *ServerPool) execRequestDirect(ctx context.Context, req *meta.Request) (*meta.Response, error) {
 	op := string(req.Command)
 
-	resource, err := sp.pool.Acquire(ctx)
+	resource, err := sp.acquire(ctx)
 	if err != nil {
 		return nil, sp.wrapErr(op, req.Key, err)
 	}
@@ -175,24 +368,88 @@ func (sp *ServerPool) execRequestDirect(ctx context.Context, req *meta.Request)
 	resp, err := conn.Execute(ctx, req)
 	if err != nil {
 		if meta.ShouldCloseConnection(err) {
-			resource.Destroy()
+			sp.destroy(resource)
 		} else {
 			sp.release(resource)
 		}
-		return nil, sp.wrapErr(op, req.Key, err)
+		return nil, sp.wrapErr(op, req.Key, deadlineErr(conn, err))
 	}
 
 	// Protocol errors are reported in resp.Error rather than as Go errors;
 	// some of them (e.g. CLIENT_ERROR) corrupt the protocol state and require
 	// closing the connection instead of returning it to the pool.
 	if resp.Error != nil && meta.ShouldCloseConnection(resp.Error) {
-		resource.Destroy()
+		sp.destroy(resource)
 	} else {
 		sp.release(resource)
 	}
 	return resp, nil
 }
 
+// ExecuteStream is Execute for a get whose value may be streamed, see
+// Connection.ExecuteStream. The connection of a returned stream stays out of
+// the pool until the stream is closed.
+func (sp *ServerPool) ExecuteStream(ctx context.Context, req *meta.Request, threshold int) (*meta.Response, *ValueStream, error) {
+	if sp.circuitBreaker == nil {
+		return sp.execStreamDirect(ctx, req, threshold)
+	}
+
+	var resp *meta.Response
+	var stream *ValueStream
+	var execErr error
+
+	_, err := sp.circuitBreaker.Execute(func() (bool, error) {
+		resp, stream, execErr = sp.execStreamDirect(ctx, req, threshold)
+		return execErr == nil, breakerError(execErr)
+	})
+
+	if err != nil {
+		return nil, nil, sp.wrapErr(string(req.Command), req.Key, err)
+	}
+	return resp, stream, execErr
+}
+
+// execStreamDirect performs the streaming request execution without circuit
+// breaker.
+func (sp *ServerPool) execStreamDirect(ctx context.Context, req *meta.Request, threshold int) (*meta.Response, *ValueStream, error) {
+	op := string(req.Command)
+
+	resource, err := sp.acquire(ctx)
+	if err != nil {
+		return nil, nil, sp.wrapErr(op, req.Key, err)
+	}
+
+	conn := resource.Value()
+
+	resp, stream, err := conn.ExecuteStream(ctx, req, threshold)
+	if err != nil {
+		if meta.ShouldCloseConnection(err) {
+			sp.destroy(resource)
+		} else {
+			sp.release(resource)
+		}
+		return nil, nil, sp.wrapErr(op, req.Key, deadlineErr(conn, err))
+	}
+
+	if stream != nil {
+		stream.release = func(reusable bool) {
+			if reusable {
+				sp.release(resource)
+			} else {
+				sp.destroy(resource)
+			}
+		}
+		return resp, stream, nil
+	}
+
+	if resp.Error != nil && meta.ShouldCloseConnection(resp.Error) {
+		sp.destroy(resource)
+	} else {
+		sp.release(resource)
+	}
+	return resp, nil, nil
+}
+
 // ExecuteBatch executes multiple requests in a pipeline using the NoOp marker strategy.
 // Sends all requests followed by a NoOp command, then reads responses until the NoOp response.
 // This leverages memcached's FIFO guarantee for optimal performance.
@@ -207,6 +464,10 @@ func (sp *ServerPool) ExecuteBatch(ctx context.Context, reqs []*meta.Request) ([
 		return nil, nil
 	}
 
+	if sp.slowOpThreshold > 0 || sp.latency != nil {
+		defer sp.observeOp(OpBatch, 0, time.Now())
+	}
+
 	if sp.circuitBreaker == nil {
 		return sp.execBatchDirect(ctx, reqs)
 	}
@@ -225,9 +486,56 @@ func (sp *ServerPool) ExecuteBatch(ctx context.Context, reqs []*meta.Request) ([
 	return responses, execErr
 }
 
+// ExecuteBatchSeq streams the responses of a pipelined batch as they arrive,
+// in the order of the requests (see Connection.ExecuteBatchSeq). Unlike
+// ExecuteBatch, it is not wrapped with the circuit breaker.
+//
+// The connection is returned to the pool once the batch is fully read, and
+// destroyed if the iteration stops early: its responses are left unread.
+func (sp *ServerPool) ExecuteBatchSeq(ctx context.Context, reqs []*meta.Request) iter.Seq2[*meta.Response, error] {
+	return func(yield func(*meta.Response, error) bool) {
+		if len(reqs) == 0 {
+			return
+		}
+
+		resource, err := sp.acquire(ctx)
+		if err != nil {
+			yield(nil, sp.wrapErr(OpBatch, "", err))
+			return
+		}
+
+		conn := resource.Value()
+		destroy := false
+		for resp, err := range conn.ExecuteBatchSeq(ctx, reqs) {
+			if err != nil {
+				if meta.ShouldCloseConnection(err) {
+					sp.destroy(resource)
+				} else {
+					sp.release(resource)
+				}
+				yield(nil, sp.wrapErr(OpBatch, "", deadlineErr(conn, err)))
+				return
+			}
+			if resp.Error != nil && meta.ShouldCloseConnection(resp.Error) {
+				destroy = true
+			}
+			if !yield(resp, nil) {
+				sp.destroy(resource)
+				return
+			}
+		}
+
+		if destroy {
+			sp.destroy(resource)
+		} else {
+			sp.release(resource)
+		}
+	}
+}
+
 // execBatchDirect performs the actual batch execution without circuit breaker.
 func (sp *ServerPool) execBatchDirect(ctx context.Context, reqs []*meta.Request) ([]*meta.Response, error) {
-	resource, err := sp.pool.Acquire(ctx)
+	resource, err := sp.acquire(ctx)
 	if err != nil {
 		return nil, sp.wrapErr(OpBatch, "", err)
 	}
@@ -237,11 +545,11 @@ func (sp *ServerPool) execBatchDirect(ctx context.Context, reqs []*meta.Request)
 	responses, err := conn.ExecuteBatch(ctx, reqs)
 	if err != nil {
 		if meta.ShouldCloseConnection(err) {
-			resource.Destroy()
+			sp.destroy(resource)
 		} else {
 			sp.release(resource)
 		}
-		return nil, sp.wrapErr(OpBatch, "", err)
+		return nil, sp.wrapErr(OpBatch, "", deadlineErr(conn, err))
 	}
 
 	// A response carrying a connection-corrupting protocol error (e.g.
@@ -254,7 +562,7 @@ func (sp *ServerPool) execBatchDirect(ctx context.Context, reqs []*meta.Request)
 		}
 	}
 	if destroy {
-		resource.Destroy()
+		sp.destroy(resource)
 	} else {
 		sp.release(resource)
 	}
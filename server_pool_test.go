@@ -4,9 +4,11 @@ import (
 	"context"
 	"errors"
 	"net"
+	"runtime"
 	"testing"
 	"time"
 
+	"github.com/pior/memcache/internal/testutils"
 	"github.com/pior/memcache/meta"
 	"github.com/sony/gobreaker/v2"
 	"github.com/stretchr/testify/assert"
@@ -22,14 +24,15 @@ func tripFastSettings() *gobreaker.Settings {
 	}
 }
 
-func newBreakerServerPool(t *testing.T, dialer Dialer) *ServerPool {
+// newTestServerPool creates a server pool for "test:11211" with the given
+// config, defaulting to a single connection from a puddle pool.
+func newTestServerPool(t *testing.T, config Config) *ServerPool {
 	t.Helper()
-	config := Config{
-		MaxSize:                2,
-		Timeout:                time.Second,
-		Dialer:                 dialer,
-		NewPool:                NewPuddlePool,
-		CircuitBreakerSettings: tripFastSettings(),
+	if config.MaxSize == 0 {
+		config.MaxSize = 1
+	}
+	if config.NewPool == nil {
+		config.NewPool = NewPuddlePool
 	}
 	sp, err := NewServerPool("test:11211", config)
 	require.NoError(t, err)
@@ -37,6 +40,16 @@ func newBreakerServerPool(t *testing.T, dialer Dialer) *ServerPool {
 	return sp
 }
 
+func newBreakerServerPool(t *testing.T, dialer Dialer) *ServerPool {
+	t.Helper()
+	return newTestServerPool(t, Config{
+		MaxSize:                2,
+		Timeout:                time.Second,
+		Dialer:                 dialer,
+		CircuitBreakerSettings: tripFastSettings(),
+	})
+}
+
 func TestServerPool_BreakerOpensOnDialFailures(t *testing.T) {
 	dialer := &mockDialer{error: net.ErrClosed}
 	sp := newBreakerServerPool(t, dialer)
@@ -54,6 +67,28 @@ func TestServerPool_BreakerOpensOnDialFailures(t *testing.T) {
 	assert.ErrorIs(t, err, gobreaker.ErrOpenState)
 }
 
+// Connections are dialed within the breaker: an open breaker stops all
+// reconnect attempts to the server, whatever the number of callers.
+func TestServerPool_BreakerStopsReconnects(t *testing.T) {
+	dialer := &mockDialer{error: net.ErrClosed}
+	sp := newBreakerServerPool(t, dialer)
+	req := meta.NewRequest(meta.CmdGet, "key", nil)
+
+	for range 2 {
+		_, err := sp.Execute(context.Background(), req)
+		require.Error(t, err)
+	}
+	require.Equal(t, gobreaker.StateOpen, sp.circuitBreaker.State())
+	dials := dialer.dials.Load()
+
+	for range 10 {
+		_, err := sp.Execute(context.Background(), req)
+		require.ErrorIs(t, err, gobreaker.ErrOpenState)
+	}
+
+	assert.Equal(t, dials, dialer.dials.Load(), "no dial must be attempted while the breaker is open")
+}
+
 // A caller canceling its context says nothing about the server: it must not
 // count as a failure and open the breaker.
 func TestServerPool_BreakerIgnoresCanceledContext(t *testing.T) {
@@ -89,6 +124,24 @@ func TestServerPool_BreakerIgnoresInvalidKey(t *testing.T) {
 		"invalid keys must not open the breaker")
 }
 
+// A request the operation doesn't support is rejected client-side: the
+// connection is kept, and the breaker doesn't count a failure.
+func TestServerPool_BreakerIgnoresUnsupportedRequest(t *testing.T) {
+	dialer := &mockDialer{conn: newPingableMockConn()}
+	sp := newBreakerServerPool(t, dialer)
+	reqs := []*meta.Request{meta.NewRequest(meta.CmdNoOp, "", nil)}
+
+	for range 5 {
+		_, err := sp.ExecuteBatch(context.Background(), reqs)
+		var unsupported *UnsupportedRequestError
+		require.ErrorAs(t, err, &unsupported)
+	}
+
+	assert.Equal(t, gobreaker.StateClosed, sp.circuitBreaker.State(),
+		"unsupported requests must not open the breaker")
+	assert.Equal(t, int32(1), dialer.dials.Load(), "the connection must be reused")
+}
+
 func newPingableMockConn() net.Conn {
 	return idleNetConn{}
 }
@@ -151,6 +204,11 @@ func TestOpError_Message(t *testing.T) {
 			err:  &OpError{Op: "ms", Key: "user:42:email", Server: "s:1", Err: errors.New("x")},
 			want: "memcache: ms on s:1: x",
 		},
+		{
+			name: "key in message when opted in",
+			err:  &OpError{Op: "ms", Key: "user:42:email", Server: "s:1", Err: errors.New("x"), keyInMessage: true},
+			want: `memcache: ms key "user:42:email" on s:1: x`,
+		},
 	}
 
 	for _, tt := range tests {
@@ -203,3 +261,213 @@ func TestOpError_Wrapping(t *testing.T) {
 		assert.False(t, stillWrapped, "the cause must not be another OpError")
 	})
 }
+
+func TestServerPool_Hooks(t *testing.T) {
+	type event struct {
+		name string
+		addr string
+		err  error
+	}
+
+	newHookedServerPool := func(t *testing.T, dialer Dialer) (*ServerPool, *[]event) {
+		t.Helper()
+		var events []event
+		sp := newTestServerPool(t, Config{
+			Dialer: dialer,
+			Hooks: Hooks{
+				OnConnect:      func(addr string) { events = append(events, event{"connect", addr, nil}) },
+				OnConnectError: func(addr string, err error) { events = append(events, event{"connect_error", addr, err}) },
+				OnReconnect:    func(addr string) { events = append(events, event{"reconnect", addr, nil}) },
+			},
+		})
+		return sp, &events
+	}
+
+	t.Run("connect", func(t *testing.T) {
+		sp, events := newHookedServerPool(t, &mockDialer{conn: testutils.NewConnectionMock("HD\r\n")})
+
+		_, err := sp.Execute(context.Background(), meta.NewRequest(meta.CmdGet, "key", nil))
+		require.NoError(t, err)
+
+		assert.Equal(t, []event{{"connect", "test:11211", nil}}, *events)
+	})
+
+	t.Run("connect error", func(t *testing.T) {
+		sp, events := newHookedServerPool(t, &mockDialer{error: net.ErrClosed})
+
+		_, err := sp.Execute(context.Background(), meta.NewRequest(meta.CmdGet, "key", nil))
+		require.Error(t, err)
+
+		assert.Equal(t, []event{{"connect_error", "test:11211", net.ErrClosed}}, *events)
+	})
+
+	t.Run("reconnect after connection error", func(t *testing.T) {
+		// The mock has no response: reads fail with EOF and the connection is destroyed.
+		sp, events := newHookedServerPool(t, &mockDialer{conn: testutils.NewConnectionMock()})
+		req := meta.NewRequest(meta.CmdGet, "key", nil)
+
+		_, err := sp.Execute(context.Background(), req)
+		require.Error(t, err)
+		_, err = sp.Execute(context.Background(), req)
+		require.Error(t, err)
+
+		assert.Equal(t, []event{
+			{"connect", "test:11211", nil},
+			{"connect", "test:11211", nil},
+			{"reconnect", "test:11211", nil},
+		}, *events)
+	})
+
+	t.Run("reconnect after a batch stopped early", func(t *testing.T) {
+		sp, events := newHookedServerPool(t, &mockDialer{conn: testutils.NewConnectionMock("EN\r\nEN\r\nMN\r\n")})
+		reqs := []*meta.Request{meta.NewRequest(meta.CmdGet, "k1", nil), meta.NewRequest(meta.CmdGet, "k2", nil)}
+
+		for _, err := range sp.ExecuteBatchSeq(context.Background(), reqs) {
+			require.NoError(t, err)
+			break
+		}
+		_, _ = sp.Execute(context.Background(), meta.NewRequest(meta.CmdGet, "key", nil))
+
+		assert.Equal(t, []event{
+			{"connect", "test:11211", nil},
+			{"connect", "test:11211", nil},
+			{"reconnect", "test:11211", nil},
+		}, *events)
+	})
+}
+
+func TestServerPool_SlowOpHook(t *testing.T) {
+	type slowOp struct {
+		addr   string
+		op     string
+		keyLen int
+	}
+
+	newSlowOpServerPool := func(t *testing.T, threshold time.Duration, responses ...string) (*ServerPool, *[]slowOp) {
+		t.Helper()
+		var ops []slowOp
+		sp := newTestServerPool(t, Config{
+			Dialer:          &mockDialer{conn: testutils.NewConnectionMock(responses...)},
+			SlowOpThreshold: threshold,
+			Hooks: Hooks{
+				OnSlowOp: func(addr, op string, keyLen int, elapsed time.Duration) {
+					assert.Greater(t, elapsed, threshold)
+					ops = append(ops, slowOp{addr, op, keyLen})
+				},
+			},
+		})
+		return sp, &ops
+	}
+
+	t.Run("operation above the threshold", func(t *testing.T) {
+		sp, ops := newSlowOpServerPool(t, time.Nanosecond, "HD\r\n", "EN\r\nMN\r\n")
+
+		_, err := sp.Execute(context.Background(), meta.NewRequest(meta.CmdGet, "key", nil))
+		require.NoError(t, err)
+		_, err = sp.ExecuteBatch(context.Background(), []*meta.Request{meta.NewRequest(meta.CmdGet, "key", nil)})
+		require.NoError(t, err)
+
+		assert.Equal(t, []slowOp{{"test:11211", "mg", 3}, {"test:11211", OpBatch, 0}}, *ops)
+	})
+
+	t.Run("operation below the threshold", func(t *testing.T) {
+		sp, ops := newSlowOpServerPool(t, time.Hour, "HD\r\n")
+
+		_, err := sp.Execute(context.Background(), meta.NewRequest(meta.CmdGet, "key", nil))
+		require.NoError(t, err)
+
+		assert.Empty(t, *ops)
+	})
+}
+
+// An idle connection past MaxConnLifetime is replaced at checkout, not used
+// once more until its release.
+func TestServerPool_MaxConnLifetimeAtCheckout(t *testing.T) {
+	dialer := &mockDialer{conn: testutils.NewConnectionMock("HD\r\n", "HD\r\n")}
+	sp := newTestServerPool(t, Config{
+		Dialer:          dialer,
+		MaxConnLifetime: 50 * time.Millisecond,
+	})
+
+	_, err := sp.Execute(context.Background(), meta.NewRequest(meta.CmdGet, "key", nil))
+	require.NoError(t, err)
+	require.Equal(t, int32(1), dialer.dials.Load())
+
+	time.Sleep(100 * time.Millisecond)
+
+	_, err = sp.Execute(context.Background(), meta.NewRequest(meta.CmdGet, "key", nil))
+	require.NoError(t, err)
+	assert.Equal(t, int32(2), dialer.dials.Load(), "the expired idle connection must be replaced")
+}
+
+// A connection that was quit, or that the server closed while it was idle
+// (see closedCheckIdle), is never handed out again.
+func TestServerPool_ClosedConnectionsAreDiscarded(t *testing.T) {
+	listener, err := net.Listen("tcp", "127.0.0.1:0")
+	require.NoError(t, err)
+	t.Cleanup(func() { listener.Close() })
+
+	// The server closes every connection once told to.
+	closeConns := make(chan struct{})
+	go func() {
+		for {
+			conn, err := listener.Accept()
+			if err != nil {
+				return
+			}
+			go func() {
+				<-closeConns
+				conn.Close()
+			}()
+		}
+	}()
+
+	sp, err := NewServerPool(listener.Addr().String(), Config{
+		MaxSize: 1,
+		Timeout: time.Second,
+		Dialer:  &net.Dialer{},
+		NewPool: NewPuddlePool,
+	})
+	require.NoError(t, err)
+	t.Cleanup(sp.pool.Close)
+	ctx := context.Background()
+
+	t.Run("quit", func(t *testing.T) {
+		resource, err := sp.acquire(ctx)
+		require.NoError(t, err)
+		quit := resource.Value()
+		require.NoError(t, quit.Quit(ctx))
+		sp.release(resource)
+
+		resource, err = sp.acquire(ctx)
+		require.NoError(t, err)
+		assert.NotSame(t, quit, resource.Value())
+		sp.release(resource)
+	})
+
+	t.Run("closed by the server", func(t *testing.T) {
+		resource, err := sp.acquire(ctx)
+		require.NoError(t, err)
+		closed := resource.Value()
+		sp.release(resource)
+
+		close(closeConns)
+		if runtime.GOOS != "linux" {
+			t.Skip("closed connections are only detected on Linux")
+		}
+		require.Eventually(t, closed.IsClosed, 2*time.Second, 10*time.Millisecond)
+
+		// A connection used moments ago is handed out without checking.
+		sp.closedCheckIdle = time.Hour
+		resource, err = sp.acquire(ctx)
+		require.NoError(t, err)
+		assert.Same(t, closed, resource.Value())
+		sp.release(resource)
+
+		sp.closedCheckIdle = 0
+		resource, err = sp.acquire(ctx)
+		require.NoError(t, err)
+		assert.NotSame(t, closed, resource.Value())
+		sp.release(resource)
+	})
+}
@@ -206,4 +206,7 @@ func TestClient_NoServers(t *testing.T) {
 
 	_, err = client.Stats(context.Background())
 	require.ErrorIs(t, err, ErrNoServers)
+
+	err = client.SetMemoryLimit(context.Background(), 1024)
+	require.ErrorIs(t, err, ErrNoServers)
 }
@@ -0,0 +1,33 @@
+package memcache
+
+import "strconv"
+
+// ServerSettings is the typed configuration of a server, as reported by
+// "stats settings". It lets the client adapt to the actual server (e.g. its
+// item size limit) instead of assuming defaults.
+//
+// Settings missing from the report are left zero. Every setting, including
+// those not mapped to a field, is kept verbatim in Raw.
+type ServerSettings struct {
+	MaxItemSize  int     // item_size_max: largest item stored, in bytes
+	MaxBytes     int64   // maxbytes: memory limit, in bytes
+	MaxConns     int     // maxconns: maximum simultaneous connections
+	GrowthFactor float64 // growth_factor: slab class size growth factor
+	Evictions    bool    // evictions: items are evicted when memory is full
+	SegmentedLRU bool    // lru_segmented: the segmented LRU is enabled
+
+	Raw map[string]string // all settings (name -> value)
+}
+
+// Settings parses the stats as a server settings report. The stats must come
+// from Client.Stats(ctx, "settings").
+func (s ServerStats) Settings() ServerSettings {
+	settings := ServerSettings{Raw: s.Stats}
+	settings.MaxItemSize, _ = strconv.Atoi(s.Stats["item_size_max"])
+	settings.MaxBytes, _ = strconv.ParseInt(s.Stats["maxbytes"], 10, 64)
+	settings.MaxConns, _ = strconv.Atoi(s.Stats["maxconns"])
+	settings.GrowthFactor, _ = strconv.ParseFloat(s.Stats["growth_factor"], 64)
+	settings.Evictions = s.Stats["evictions"] == "on"
+	settings.SegmentedLRU = s.Stats["lru_segmented"] == "yes"
+	return settings
+}
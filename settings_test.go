@@ -0,0 +1,39 @@
+package memcache
+
+import (
+	"context"
+	"testing"
+
+	"github.com/pior/memcache/internal/testutils"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestServerStats_Settings(t *testing.T) {
+	mockConn := testutils.NewConnectionMock(
+		"STAT maxbytes 67108864\r\n",
+		"STAT maxconns 1024\r\n",
+		"STAT growth_factor 1.25\r\n",
+		"STAT evictions on\r\n",
+		"STAT item_size_max 1048576\r\n",
+		"STAT lru_segmented yes\r\n",
+		"STAT ext_path /data/extstore\r\n",
+		"END\r\n",
+	)
+	client := newTestClient(t, mockConn)
+
+	stats, err := client.Stats(context.Background(), "settings")
+	require.NoError(t, err)
+	require.Len(t, stats, 1)
+	require.NoError(t, stats[0].Error)
+
+	settings := stats[0].Settings()
+	assert.Equal(t, 1048576, settings.MaxItemSize)
+	assert.Equal(t, int64(67108864), settings.MaxBytes)
+	assert.Equal(t, 1024, settings.MaxConns)
+	assert.InDelta(t, 1.25, settings.GrowthFactor, 1e-9)
+	assert.True(t, settings.Evictions)
+	assert.True(t, settings.SegmentedLRU)
+	assert.Equal(t, "/data/extstore", settings.Raw["ext_path"], "unmapped settings are kept")
+	assertRequest(t, mockConn, "stats settings\r\n")
+}
@@ -0,0 +1,40 @@
+package memcache
+
+import "net"
+
+// tcpConnOf returns the TCP connection underlying conn, unwrapping
+// connections that expose it with a NetConn method (e.g. *tls.Conn).
+func tcpConnOf(conn net.Conn) (*net.TCPConn, bool) {
+	for {
+		switch c := conn.(type) {
+		case *net.TCPConn:
+			return c, true
+		case interface{ NetConn() net.Conn }:
+			conn = c.NetConn()
+		default:
+			return nil, false
+		}
+	}
+}
+
+// setBufferSizes sets the kernel receive and send buffer sizes (SO_RCVBUF and
+// SO_SNDBUF) of the connection. Zero leaves a size unchanged. Connections that
+// are not TCP are left unchanged.
+func setBufferSizes(conn net.Conn, readBuffer, writeBuffer int) error {
+	tcpConn, ok := tcpConnOf(conn)
+	if !ok {
+		return nil
+	}
+
+	if readBuffer > 0 {
+		if err := tcpConn.SetReadBuffer(readBuffer); err != nil {
+			return err
+		}
+	}
+	if writeBuffer > 0 {
+		if err := tcpConn.SetWriteBuffer(writeBuffer); err != nil {
+			return err
+		}
+	}
+	return nil
+}
@@ -0,0 +1,63 @@
+//go:build linux
+
+package memcache
+
+import (
+	"errors"
+	"net"
+	"syscall"
+)
+
+// setQuickAck enables TCP_QUICKACK on the connection. Connections that are
+// not TCP (e.g. unix sockets) are left unchanged.
+func setQuickAck(conn net.Conn) error {
+	tcpConn, ok := tcpConnOf(conn)
+	if !ok {
+		return nil
+	}
+
+	raw, err := tcpConn.SyscallConn()
+	if err != nil {
+		return err
+	}
+
+	var sockErr error
+	err = raw.Control(func(fd uintptr) {
+		sockErr = syscall.SetsockoptInt(int(fd), syscall.IPPROTO_TCP, syscall.TCP_QUICKACK, 1)
+	})
+	if err != nil {
+		return err
+	}
+	return sockErr
+}
+
+// peerClosed reports whether the server closed the connection, with a
+// non-blocking peek that reads the end of the stream (or fails) once it did.
+// Unread data means the connection is open. Connections that are not TCP are
+// assumed open.
+func peerClosed(conn net.Conn) bool {
+	tcpConn, ok := tcpConnOf(conn)
+	if !ok {
+		return false
+	}
+
+	raw, err := tcpConn.SyscallConn()
+	if err != nil {
+		return false
+	}
+
+	closed := false
+	err = raw.Read(func(fd uintptr) bool {
+		var buf [1]byte
+		n, _, err := syscall.Recvfrom(int(fd), buf[:], syscall.MSG_PEEK|syscall.MSG_DONTWAIT)
+		switch {
+		case err == nil:
+			closed = n == 0
+		case errors.Is(err, syscall.EAGAIN), errors.Is(err, syscall.EINTR):
+		default:
+			closed = true
+		}
+		return true
+	})
+	return err == nil && closed
+}
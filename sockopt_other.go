@@ -0,0 +1,16 @@
+//go:build !linux
+
+package memcache
+
+import "net"
+
+// setQuickAck is a no-op: TCP_QUICKACK is Linux-specific.
+func setQuickAck(conn net.Conn) error {
+	return nil
+}
+
+// peerClosed is always false: detecting a connection closed by the server
+// is only implemented on Linux, elsewhere the next operation fails.
+func peerClosed(conn net.Conn) bool {
+	return false
+}
@@ -0,0 +1,58 @@
+package memcache
+
+import (
+	"crypto/tls"
+	"net"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestTCPConnOf(t *testing.T) {
+	listener, err := net.Listen("tcp", "127.0.0.1:0")
+	require.NoError(t, err)
+	t.Cleanup(func() { listener.Close() })
+
+	conn, err := net.Dial("tcp", listener.Addr().String())
+	require.NoError(t, err)
+	t.Cleanup(func() { conn.Close() })
+
+	tcpConn, ok := tcpConnOf(conn)
+	assert.True(t, ok)
+	assert.Same(t, conn, tcpConn)
+
+	tcpConn, ok = tcpConnOf(tls.Client(conn, &tls.Config{}))
+	assert.True(t, ok, "TLS connections must be unwrapped")
+	assert.Same(t, conn, tcpConn)
+
+	_, ok = tcpConnOf(idleNetConn{})
+	assert.False(t, ok)
+}
+
+func TestSetQuickAck(t *testing.T) {
+	listener, err := net.Listen("tcp", "127.0.0.1:0")
+	require.NoError(t, err)
+	t.Cleanup(func() { listener.Close() })
+
+	conn, err := net.Dial("tcp", listener.Addr().String())
+	require.NoError(t, err)
+	t.Cleanup(func() { conn.Close() })
+
+	require.NoError(t, setQuickAck(conn))
+	require.NoError(t, setQuickAck(idleNetConn{}), "non-TCP connections are left unchanged")
+}
+
+func TestSetBufferSizes(t *testing.T) {
+	listener, err := net.Listen("tcp", "127.0.0.1:0")
+	require.NoError(t, err)
+	t.Cleanup(func() { listener.Close() })
+
+	conn, err := net.Dial("tcp", listener.Addr().String())
+	require.NoError(t, err)
+	t.Cleanup(func() { conn.Close() })
+
+	require.NoError(t, setBufferSizes(conn, 1<<20, 1<<20))
+	require.NoError(t, setBufferSizes(conn, 0, 0), "zero keeps the system default")
+	require.NoError(t, setBufferSizes(idleNetConn{}, 1<<20, 1<<20), "non-TCP connections are left unchanged")
+}
@@ -0,0 +1,95 @@
+package memcache
+
+import (
+	"context"
+	"errors"
+	"io"
+	"time"
+
+	"github.com/pior/memcache/meta"
+)
+
+// DefaultStreamThreshold is the value size, in bytes, above which
+// GetBuilder.DoStream streams a value when no threshold is given.
+const DefaultStreamThreshold = 1 << 20
+
+var errStreamClosed = errors.New("memcache: read on closed value stream")
+
+// StreamExecutor is an optional interface for executing a get whose value may
+// be streamed rather than buffered, see GetBuilder.DoStream.
+type StreamExecutor interface {
+	ExecuteStream(ctx context.Context, req *meta.Request, threshold int) (*meta.Response, *ValueStream, error)
+}
+
+// ValueStream reads a value from the connection it is being received on,
+// without buffering it whole (see GetBuilder.DoStream).
+//
+// The connection is held by the stream until Close, which the caller must
+// call once done, whether the value was read entirely or not. A stream is not
+// safe for concurrent use.
+type ValueStream struct {
+	ctx  context.Context
+	conn *Connection
+	data *meta.DataReader
+	size int
+
+	// release returns the connection to its pool, or discards it when it is
+	// not reusable. Nil for a stream opened on a bare Connection.
+	release func(reusable bool)
+
+	err    error
+	closed bool
+}
+
+var _ io.ReadCloser = (*ValueStream)(nil)
+
+// Size returns the size of the value, in bytes.
+func (s *ValueStream) Size() int {
+	return s.size
+}
+
+// Read implements io.Reader. Each read is bounded by the connection timeout
+// and by the deadline of the context the stream was opened with.
+func (s *ValueStream) Read(p []byte) (int, error) {
+	if s.closed {
+		return 0, errStreamClosed
+	}
+	if s.err != nil {
+		return 0, s.err
+	}
+
+	if !s.data.Done() {
+		if _, err := s.conn.setDeadline(s.ctx); err != nil {
+			s.err = err
+			return 0, err
+		}
+	}
+
+	n, err := s.data.Read(p)
+	if err != nil && err != io.EOF {
+		s.err = deadlineErr(s.conn, err)
+		return n, s.err
+	}
+	return n, err
+}
+
+// Close releases the connection. Once the value was read entirely, the
+// connection is reused; otherwise the rest of the value is still on the wire
+// and the connection is closed.
+func (s *ValueStream) Close() error {
+	if s.closed {
+		return nil
+	}
+	s.closed = true
+
+	_ = s.conn.conn.SetDeadline(time.Time{})
+	reusable := s.data.Done()
+	if s.release != nil {
+		s.release(reusable)
+		return nil
+	}
+	if !reusable {
+		return s.conn.Close()
+	}
+	return nil
+}
@@ -5,6 +5,7 @@ import (
 	"errors"
 	"fmt"
 	"net"
+	"os"
 	"strings"
 	"sync"
 	"testing"
@@ -635,6 +636,92 @@ func TestTimeout_HungServerBoundedByConfigTimeout(t *testing.T) {
 	})
 }
 
+// A context deadline is the per-call latency budget: when it is what stops the
+// operation, the error matches context.DeadlineExceeded. When Config.Timeout
+// stops it first, the error is only a socket timeout.
+func TestTimeout_ContextDeadlineExceeded(t *testing.T) {
+	addr := newHungServer(t)
+
+	t.Run("context deadline", func(t *testing.T) {
+		client := NewClient(StaticServers(addr), Config{MaxSize: 2, Timeout: time.Hour})
+		t.Cleanup(client.Close)
+
+		ctx, cancel := context.WithTimeout(context.Background(), 50*time.Millisecond)
+		defer cancel()
+
+		_, err := client.Get(ctx, "test:budget:single")
+		require.ErrorIs(t, err, context.DeadlineExceeded)
+		require.ErrorIs(t, err, os.ErrDeadlineExceeded)
+
+		_, err = NewBatchCommands(client).MultiGet(ctx, []string{"test:budget:1", "test:budget:2"})
+		require.ErrorIs(t, err, context.DeadlineExceeded)
+	})
+
+	t.Run("config timeout", func(t *testing.T) {
+		client := NewClient(StaticServers(addr), Config{MaxSize: 2, Timeout: 50 * time.Millisecond})
+		t.Cleanup(client.Close)
+
+		ctx, cancel := context.WithTimeout(context.Background(), time.Hour)
+		defer cancel()
+
+		_, err := client.Get(ctx, "test:budget:single")
+		require.ErrorIs(t, err, os.ErrDeadlineExceeded)
+		assert.NotErrorIs(t, err, context.DeadlineExceeded)
+	})
+}
+
+// The cause of a socket timeout is the deadline the connection was given:
+// when the context deadline fires with the socket deadline, ctx.Err() may
+// still be nil.
+func TestTimeout_DeadlineErrFollowsChosenDeadline(t *testing.T) {
+	conn, _ := newMockConnection() // 1s timeout cap
+	timeoutErr := fmt.Errorf("read: %w", os.ErrDeadlineExceeded)
+
+	ctx, cancel := context.WithTimeout(context.Background(), 500*time.Millisecond)
+	defer cancel()
+	_, err := conn.setDeadline(ctx)
+	require.NoError(t, err)
+	require.NoError(t, ctx.Err(), "the context has not expired yet")
+	assert.ErrorIs(t, deadlineErr(conn, timeoutErr), context.DeadlineExceeded)
+
+	ctx, cancel = context.WithTimeout(context.Background(), time.Hour)
+	defer cancel()
+	_, err = conn.setDeadline(ctx)
+	require.NoError(t, err)
+	assert.NotErrorIs(t, deadlineErr(conn, timeoutErr), context.DeadlineExceeded)
+}
+
+// WithOperationTimeout replaces Config.Timeout for one call, in both
+// directions, and a call stopped by it closes its connection like one stopped
+// by Config.Timeout.
+func TestTimeout_WithOperationTimeout(t *testing.T) {
+	addr := newHungServer(t)
+
+	t.Run("longer than the default", func(t *testing.T) {
+		client := NewClient(StaticServers(addr), Config{MaxSize: 2, Timeout: 20 * time.Millisecond})
+		t.Cleanup(client.Close)
+
+		start := time.Now()
+		_, err := client.Get(WithOperationTimeout(context.Background(), 300*time.Millisecond), "test:override:long")
+		require.ErrorIs(t, err, os.ErrDeadlineExceeded)
+		assert.GreaterOrEqual(t, time.Since(start), 250*time.Millisecond, "the override must extend the default timeout")
+
+		assert.Eventually(t, func() bool {
+			return client.PoolMetrics()[0].Conns.DestroyedConns == 1
+		}, 2*time.Second, 10*time.Millisecond, "the timed out connection must be closed")
+	})
+
+	t.Run("shorter than the default", func(t *testing.T) {
+		client := NewClient(StaticServers(addr), Config{MaxSize: 2, Timeout: time.Hour})
+		t.Cleanup(client.Close)
+
+		ctx := WithOperationTimeout(context.Background(), 50*time.Millisecond)
+		_, err := NewBatchCommands(client).MultiGet(ctx, []string{"test:override:1", "test:override:2"})
+		require.ErrorIs(t, err, os.ErrDeadlineExceeded)
+		assert.NotErrorIs(t, err, context.DeadlineExceeded)
+	})
+}
+
 // TestTimeout_BareCancellationDoesNotInterruptOp documents a deliberate design
 // choice: like go-redis (and gomemcache), an in-flight blocking read is bounded
 // only by the socket deadline, not by context cancellation. Canceling a context
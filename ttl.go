@@ -13,22 +13,63 @@ const minAbsoluteExptime = int64(maxRelativeTTL/time.Second) + 1
 
 // TTL specifies when an item expires.
 // The zero value (NoTTL) means the item never expires (it persists until
-// evicted). Use ExpiresIn for an expiration relative to now, ExpiresAt for
-// an absolute point in time.
+// evicted), unless the client has a Config.DefaultTTL. Use ExpiresIn for an
+// expiration relative to now, ExpiresAt for an absolute point in time.
+//
+// "Never expires" is memcached's exptime 0 (T0 on the wire). It is only ever
+// expressed by NoTTL or NeverExpire: a zero or negative duration is not a way
+// to ask for it, and expires the item immediately.
 type TTL struct {
 	duration time.Duration
 	at       time.Time
+	never    bool
 }
 
 // NoTTL is the zero TTL: the item never expires (it persists until evicted).
+// When the client has a Config.DefaultTTL, NoTTL stands for that default.
 var NoTTL = TTL{}
 
+// NeverExpire is a TTL for items that never expire, even when the client has
+// a Config.DefaultTTL.
+var NeverExpire = TTL{never: true}
+
+// isUnset reports whether t specifies no expiration at all (NoTTL), as opposed
+// to NeverExpire.
+func (t TTL) isUnset() bool {
+	return !t.never && t.at.IsZero() && t.duration == 0
+}
+
+// orDefault returns def when t is unset.
+func (t TTL) orDefault(def TTL) TTL {
+	if t.isUnset() {
+		return def
+	}
+	return t
+}
+
+// jitter returns t with its duration moved by a random offset within ±spread,
+// drawn with randN (which returns a number in [0, n)). Only relative TTLs are
+// jittered: NoTTL, NeverExpire and absolute times are returned unchanged. The
+// result is at least one second, so the item is never expired by the jitter.
+func (t TTL) jitter(spread time.Duration, randN func(n int64) int64) TTL {
+	if t.duration <= 0 || spread <= 0 {
+		return t
+	}
+	d := t.duration + time.Duration(randN(2*int64(spread)+1)) - spread
+	return TTL{duration: max(d, time.Second)}
+}
+
 // ExpiresIn returns a TTL expiring d after the request is sent.
 // Sub-second durations are rounded up to one second, memcached's resolution.
 // Durations longer than 30 days are encoded on the wire as an absolute unix
 // timestamp, as the memcached protocol requires; the meaning is unchanged.
-// A non-positive d is NoTTL.
+// A non-positive d expires the item immediately, like an ExpiresAt in the
+// past. This is a breaking change: ExpiresIn(0) used to mean no expiration.
+// For an item that never expires, use NeverExpire (or NoTTL).
 func ExpiresIn(d time.Duration) TTL {
+	if d <= 0 {
+		return TTL{at: time.Unix(0, 0)} // already expired
+	}
 	return TTL{duration: d}
 }
 
@@ -53,7 +94,7 @@ func (t TTL) Expiration() int {
 		// encode the oldest valid absolute timestamp: already expired.
 		return int(minAbsoluteExptime)
 	}
-	if t.duration <= 0 {
+	if t.duration == 0 {
 		return 0
 	}
 	seconds := int((t.duration + time.Second - 1) / time.Second)
@@ -0,0 +1,100 @@
+package memcache
+
+import (
+	"context"
+	"errors"
+	"iter"
+	"slices"
+
+	"github.com/pior/memcache/meta"
+)
+
+// DefaultWarmBatchSize is the batch size used by Warm when none is given.
+const DefaultWarmBatchSize = 100
+
+// WarmFailure is an item that Warm could not store.
+type WarmFailure struct {
+	Key string
+	Err error
+}
+
+// WarmSummary is the outcome of Warm.
+type WarmSummary struct {
+	// Stored is the number of items stored.
+	Stored int
+
+	// Failures lists the items that were not stored.
+	Failures []WarmFailure
+}
+
+// Warm stores the items of a sequence in pipelined batches of batchSize items
+// (DefaultWarmBatchSize if batchSize <= 0), e.g. to reload a cache from its
+// source of truth after a deploy. The sequence is consumed as the batches are
+// stored, so it can stream from a database without holding every item.
+//
+// A failed item, e.g. one with an invalid key, doesn't stop the warm-up: it is
+// recorded in the summary, as are all the items of a batch that failed as a
+// whole (e.g. a server being down). Warm only stops early when ctx is done,
+// returning ctx.Err() and the summary so far.
+//
+// progress, if not nil, is called after each batch with the summary so far.
+func (b *BatchCommands) Warm(ctx context.Context, items iter.Seq[Item], batchSize int, progress func(WarmSummary)) (WarmSummary, error) {
+	if batchSize <= 0 {
+		batchSize = DefaultWarmBatchSize
+	}
+
+	var summary WarmSummary
+	batch := make([]Item, 0, batchSize)
+
+	flush := func() error {
+		results, err := b.MultiStore(ctx, batch, SetModeSet)
+
+		// An invalid key rejects the whole batch before anything is sent:
+		// record it and store the other items.
+		var keyErr *meta.BatchKeyError
+		for errors.As(err, &keyErr) && keyErr.Index < len(batch) {
+			summary.Failures = append(summary.Failures, WarmFailure{Key: batch[keyErr.Index].Key, Err: keyErr.Err})
+			batch = slices.Delete(batch, keyErr.Index, keyErr.Index+1)
+			results, err = b.MultiStore(ctx, batch, SetModeSet)
+		}
+
+		if err != nil && ctx.Err() != nil {
+			return ctx.Err()
+		}
+
+		for i, item := range batch {
+			itemErr := err
+			if err == nil {
+				itemErr = results[i]
+			}
+			if itemErr != nil {
+				summary.Failures = append(summary.Failures, WarmFailure{Key: item.Key, Err: itemErr})
+			} else {
+				summary.Stored++
+			}
+		}
+		batch = batch[:0]
+
+		if progress != nil {
+			progress(summary)
+		}
+		return nil
+	}
+
+	for item := range items {
+		batch = append(batch, item)
+		if len(batch) == batchSize {
+			if err := flush(); err != nil {
+				return summary, err
+			}
+		}
+	}
+
+	if len(batch) > 0 {
+		if err := flush(); err != nil {
+			return summary, err
+		}
+	}
+
+	return summary, nil
+}
@@ -0,0 +1,78 @@
+package memcache
+
+import (
+	"context"
+	"slices"
+	"testing"
+
+	"github.com/pior/memcache/meta"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestBatchCommands_Warm(t *testing.T) {
+	items := func(keys ...string) []Item {
+		var items []Item
+		for _, key := range keys {
+			items = append(items, Item{Key: key, Value: []byte("v")})
+		}
+		return items
+	}
+
+	t.Run("stores in batches and reports progress", func(t *testing.T) {
+		bc, mock := newBatchTestClient(t,
+			"HD\r\n", "HD\r\n", "MN\r\n",
+			"NS\r\n", "HD\r\n", "MN\r\n",
+			"HD\r\n", "MN\r\n",
+		)
+
+		var progress []int
+		summary, err := bc.Warm(context.Background(), slices.Values(items("k1", "k2", "k3", "k4", "k5")), 2,
+			func(s WarmSummary) { progress = append(progress, s.Stored+len(s.Failures)) })
+		require.NoError(t, err)
+
+		assert.Equal(t, 4, summary.Stored)
+		require.Len(t, summary.Failures, 1)
+		assert.Equal(t, "k3", summary.Failures[0].Key)
+		assert.ErrorIs(t, summary.Failures[0].Err, ErrNotStored)
+		assert.Equal(t, []int{2, 4, 5}, progress)
+		assert.Equal(t, "ms k1 1\r\nv\r\nms k2 1\r\nv\r\nmn\r\n"+
+			"ms k3 1\r\nv\r\nms k4 1\r\nv\r\nmn\r\n"+
+			"ms k5 1\r\nv\r\nmn\r\n", mock.GetWrittenRequest())
+	})
+
+	t.Run("invalid key does not fail its batch", func(t *testing.T) {
+		bc, mock := newBatchTestClient(t, "HD\r\n", "HD\r\n", "MN\r\n")
+
+		summary, err := bc.Warm(context.Background(), slices.Values(items("k1", "bad key", "k3")), 10, nil)
+		require.NoError(t, err)
+
+		assert.Equal(t, 2, summary.Stored)
+		require.Len(t, summary.Failures, 1)
+		assert.Equal(t, "bad key", summary.Failures[0].Key)
+		var keyErr *meta.InvalidKeyError
+		assert.ErrorAs(t, summary.Failures[0].Err, &keyErr)
+		assert.Equal(t, "ms k1 1\r\nv\r\nms k3 1\r\nv\r\nmn\r\n", mock.GetWrittenRequest())
+	})
+
+	t.Run("failed batch is recorded and the warm-up continues", func(t *testing.T) {
+		// The mock runs out of responses: the first batch fails on EOF, and
+		// the second one too, on a new connection.
+		bc, _ := newBatchTestClient(t)
+
+		summary, err := bc.Warm(context.Background(), slices.Values(items("k1", "k2", "k3")), 2, nil)
+		require.NoError(t, err)
+
+		assert.Zero(t, summary.Stored)
+		assert.Len(t, summary.Failures, 3)
+	})
+
+	t.Run("stops when the context is done", func(t *testing.T) {
+		bc, _ := newBatchTestClient(t)
+		ctx, cancel := context.WithCancel(context.Background())
+		cancel()
+
+		_, err := bc.Warm(ctx, slices.Values(items("k1")), 2, nil)
+		require.ErrorIs(t, err, context.Canceled)
+	})
+}
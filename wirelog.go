@@ -0,0 +1,65 @@
+package memcache
+
+import (
+	"context"
+	"fmt"
+	"log/slog"
+	"net"
+	"strconv"
+)
+
+// defaultWireLogMaxBytes is the dump length used when Config.WireLogMaxBytes is zero.
+const defaultWireLogMaxBytes = 256
+
+// wireLogConn wraps a net.Conn to log the bytes written to and read from the
+// server, for protocol-level debugging (see Config.WireLogger).
+type wireLogConn struct {
+	net.Conn
+	logger   *slog.Logger
+	addr     string
+	maxBytes int
+}
+
+func newWireLogConn(conn net.Conn, logger *slog.Logger, addr string, maxBytes int) *wireLogConn {
+	if maxBytes <= 0 {
+		maxBytes = defaultWireLogMaxBytes
+	}
+	return &wireLogConn{Conn: conn, logger: logger, addr: addr, maxBytes: maxBytes}
+}
+
+func (c *wireLogConn) Read(b []byte) (int, error) {
+	n, err := c.Conn.Read(b)
+	if n > 0 {
+		c.log("memcache: recv", b[:n])
+	}
+	return n, err
+}
+
+func (c *wireLogConn) Write(b []byte) (int, error) {
+	n, err := c.Conn.Write(b)
+	if n > 0 {
+		c.log("memcache: send", b[:n])
+	}
+	return n, err
+}
+
+func (c *wireLogConn) log(msg string, data []byte) {
+	ctx := context.Background()
+	if !c.logger.Enabled(ctx, slog.LevelDebug) {
+		return
+	}
+	c.logger.LogAttrs(ctx, slog.LevelDebug, msg,
+		slog.String("server", c.addr),
+		slog.Int("len", len(data)),
+		slog.String("data", dumpWire(data, c.maxBytes)),
+	)
+}
+
+// dumpWire renders data as an escaped string, so that control characters and
+// binary values stay readable, truncated to maxBytes.
+func dumpWire(data []byte, maxBytes int) string {
+	if len(data) <= maxBytes {
+		return strconv.Quote(string(data))
+	}
+	return fmt.Sprintf("%s... (%d more bytes)", strconv.Quote(string(data[:maxBytes])), len(data)-maxBytes)
+}
@@ -0,0 +1,44 @@
+package memcache
+
+import (
+	"bytes"
+	"context"
+	"log/slog"
+	"testing"
+
+	"github.com/pior/memcache/internal/testutils"
+	"github.com/pior/memcache/meta"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestWireLogConn(t *testing.T) {
+	var buf bytes.Buffer
+	logger := slog.New(slog.NewTextHandler(&buf, &slog.HandlerOptions{Level: slog.LevelDebug}))
+
+	mockConn := testutils.NewConnectionMock("VA 2\r\nhi\r\n")
+	conn := NewConnection(newWireLogConn(mockConn, logger, "test:11211", 0), 0)
+
+	_, err := conn.Execute(context.Background(), meta.NewRequest(meta.CmdGet, "key", nil).AddReturnValue())
+	require.NoError(t, err)
+
+	assert.Contains(t, buf.String(), `msg="memcache: send" server=test:11211 len=10 data="\"mg key v\\r\\n\""`)
+	assert.Contains(t, buf.String(), `msg="memcache: recv" server=test:11211 len=10 data="\"VA 2\\r\\nhi\\r\\n\""`)
+}
+
+func TestWireLogConn_DisabledLevel(t *testing.T) {
+	var buf bytes.Buffer
+	logger := slog.New(slog.NewTextHandler(&buf, &slog.HandlerOptions{Level: slog.LevelInfo}))
+
+	conn := newWireLogConn(testutils.NewConnectionMock(), logger, "test:11211", 0)
+	_, err := conn.Write([]byte("mn\r\n"))
+	require.NoError(t, err)
+
+	assert.Empty(t, buf.String())
+}
+
+func TestDumpWire(t *testing.T) {
+	assert.Equal(t, `"mg key\r\n"`, dumpWire([]byte("mg key\r\n"), 16))
+	assert.Equal(t, `"\x00\x01"`, dumpWire([]byte{0, 1}, 16))
+	assert.Equal(t, `"abcd"... (4 more bytes)`, dumpWire([]byte("abcdefgh"), 4))
+}